@@ -4,7 +4,7 @@ use std::process;
 
 use rand::Rng;
 
-use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::commands::{check_writable, JoshutoCommand, JoshutoRunnable, ReloadDirList};
 use crate::context::JoshutoContext;
 use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
 use crate::ui::TuiBackend;
@@ -12,17 +12,19 @@ use crate::ui::TuiBackend;
 const ENV_EDITOR: &str = "EDITOR";
 
 #[derive(Clone, Debug)]
-pub struct BulkRename;
+pub struct BulkRename {
+    dry_run: bool,
+}
 
 impl BulkRename {
-    pub fn new() -> Self {
-        BulkRename {}
+    pub fn new(dry_run: bool) -> Self {
+        BulkRename { dry_run }
     }
     pub const fn command() -> &'static str {
         "bulk_rename"
     }
 
-    pub fn bulk_rename(context: &mut JoshutoContext) -> JoshutoResult<()> {
+    pub fn bulk_rename(&self, context: &mut JoshutoContext) -> JoshutoResult<()> {
         const PREFIX: &str = "joshuto-";
         let editor = match std::env::var(ENV_EDITOR) {
             Ok(s) => s,
@@ -51,6 +53,9 @@ impl BulkRename {
             Some(s) => s.get_selected_paths(),
             None => Vec::new(),
         };
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
         {
             let mut file = std::fs::File::create(&file_path)?;
             for path in &paths {
@@ -100,15 +105,18 @@ impl BulkRename {
         for (p, q) in paths.iter().zip(paths_renamed.iter()) {
             println!("{:?} -> {:?}", p, q);
         }
-        print!("Continue with rename? (Y/n): ");
-        std::io::stdout().flush()?;
-
         let mut user_input = String::with_capacity(4);
-        std::io::stdin().read_line(&mut user_input)?;
-        user_input = user_input.to_lowercase();
+        if self.dry_run {
+            println!("Dry run, no files were renamed");
+        } else {
+            print!("Continue with rename? (Y/n): ");
+            std::io::stdout().flush()?;
+            std::io::stdin().read_line(&mut user_input)?;
+            user_input = user_input.to_lowercase();
+        }
 
         let user_input_trimmed = user_input.trim();
-        if user_input_trimmed != "n" || user_input_trimmed != "no" {
+        if !self.dry_run && user_input_trimmed != "n" && user_input_trimmed != "no" {
             for (p, q) in paths.iter().zip(paths_renamed.iter()) {
                 let mut handle = process::Command::new("mv")
                     .arg("-iv")
@@ -128,20 +136,32 @@ impl BulkRename {
     }
 }
 
-impl JoshutoCommand for BulkRename {}
+impl JoshutoCommand for BulkRename {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for BulkRename {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", Self::command())
+        write!(f, "{}", Self::command())?;
+        if self.dry_run {
+            f.write_str(" --dry-run")?;
+        }
+        Ok(())
     }
 }
 
 impl JoshutoRunnable for BulkRename {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if !self.dry_run {
+            check_writable(Self::command(), context)?;
+        }
         backend.terminal_drop();
-        let res = Self::bulk_rename(context);
+        let res = self.bulk_rename(context);
         backend.terminal_restore()?;
-        ReloadDirList::reload(context.curr_tab_index, context)?;
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
         res
     }
 }
@@ -0,0 +1,61 @@
+use std::path;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+use crate::util::dir_size;
+
+/*
+ * adds up the size of the selected directories in the background,
+ * with progress on the status line and the totals posted once done
+ */
+#[derive(Clone, Debug)]
+pub struct CalculateSize;
+
+impl CalculateSize {
+    pub fn new() -> Self {
+        CalculateSize
+    }
+    pub const fn command() -> &'static str {
+        "calculate_size"
+    }
+}
+
+impl JoshutoCommand for CalculateSize {}
+
+impl std::fmt::Display for CalculateSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for CalculateSize {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => list
+                .get_selected_paths()
+                .into_iter()
+                .filter(|p| p.is_dir())
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: No directories selected", Self::command()),
+            ));
+        }
+        for path in paths {
+            /* one already running just gets its total posted too */
+            if let Some(report) = context.size_pending.get_mut(&path) {
+                *report = true;
+                continue;
+            }
+            context.size_pending.insert(path.clone(), true);
+            dir_size::spawn(path, context.events.event_tx.clone());
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,26 @@
+use std::cmp;
+use std::fs;
 use std::path;
 
-use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use globset::{GlobBuilder, GlobMatcher};
+
+use crate::commands::{cursor_move, escape_arg, JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
 use crate::history::DirectoryHistory;
+use crate::ui::widgets::{TuiMenu, TuiTextField};
 use crate::ui::TuiBackend;
 use crate::util::load_child::LoadChild;
 
+use crate::HOME_DIR;
+
+// characters that make a cd argument a glob rather than a path
+const GLOB_CHARS: &[char] = &['*', '?', '[', '{'];
+// most directories the picker offers when several match
+const LIST_LIMIT: usize = 10;
+// how far ** descends, as the walk holds up the ui
+const GLOB_MAX_DEPTH: usize = 8;
+
 #[derive(Clone, Debug)]
 pub struct ChangeDirectory {
     path: path::PathBuf,
@@ -20,11 +34,21 @@ impl ChangeDirectory {
         "cd"
     }
 
+    /*
+     * relative paths start from the current directory. tabs and frecency
+     * only ever get the canonical path, which stays valid from anywhere
+     */
+    fn absolute(path: &path::Path, context: &JoshutoContext) -> std::io::Result<path::PathBuf> {
+        context.curr_tab_ref().curr_path.join(path).canonicalize()
+    }
+
     pub fn cd(path: &path::Path, context: &mut JoshutoContext) -> std::io::Result<()> {
-        std::env::set_current_dir(path)?;
+        let path = Self::absolute(path, context)?;
+        std::env::set_current_dir(&path)?;
 
+        context.frecency.visit(&path);
         let curr_tab = &mut context.tabs[context.curr_tab_index];
-        curr_tab.curr_path = path.to_path_buf();
+        curr_tab.curr_path = path;
 
         Ok(())
     }
@@ -33,7 +57,8 @@ impl ChangeDirectory {
         path: &path::Path,
         context: &mut JoshutoContext,
     ) -> std::io::Result<()> {
-        Self::cd(path, context)?;
+        let path = Self::absolute(path, context)?;
+        Self::cd(&path, context)?;
 
         let curr_tab = &mut context.tabs[context.curr_tab_index];
         curr_tab
@@ -42,21 +67,361 @@ impl ChangeDirectory {
 
         Ok(())
     }
+
+    /*
+     * the directories matching pattern, relative to curr_path. a pattern
+     * without glob characters matches directories starting with it
+     */
+    fn glob_dirs(
+        pattern: &path::Path,
+        curr_path: &path::Path,
+    ) -> JoshutoResult<Vec<path::PathBuf>> {
+        let mut pattern = pattern.to_string_lossy().into_owned();
+        if !pattern.contains(GLOB_CHARS) {
+            pattern.push('*');
+        }
+
+        /*
+         * walking starts below the components without glob characters,
+         * the rest is matched against paths relative to there. that way
+         * glob characters in curr_path itself are taken literally
+         */
+        let mut base = curr_path.to_path_buf();
+        let mut glob: Vec<String> = Vec::new();
+        for component in path::Path::new(&pattern).components() {
+            let component_str = component.as_os_str().to_string_lossy();
+            if glob.is_empty() && !component_str.contains(GLOB_CHARS) {
+                base.push(component);
+            } else {
+                glob.push(component_str.into_owned());
+            }
+        }
+        let glob = glob.join("/");
+        let matcher = match GlobBuilder::new(&glob).literal_separator(true).build() {
+            Ok(glob) => glob.compile_matcher(),
+            Err(e) => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    format!("{}: {}", Self::command(), e),
+                ))
+            }
+        };
+        let depth = glob.split('/').count();
+        let depth = if glob.contains("**") {
+            cmp::max(depth, GLOB_MAX_DEPTH)
+        } else {
+            depth
+        };
+
+        let mut dirs = Vec::new();
+        Self::walk_dirs(base.as_path(), base.as_path(), depth, &matcher, &mut dirs);
+        dirs.sort();
+        Ok(dirs)
+    }
+
+    // symlinks to directories can match, but aren't walked into
+    fn walk_dirs(
+        base: &path::Path,
+        dir: &path::Path,
+        depth: usize,
+        matcher: &GlobMatcher,
+        dirs: &mut Vec<path::PathBuf>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        let entries = match fs::read_dir(dir) {
+            Ok(s) => s,
+            Err(_) => return,
+        };
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let path = entry.path();
+            let relative = path.strip_prefix(base).unwrap_or(path.as_path());
+            if path.is_dir() && matcher.is_match(relative) {
+                dirs.push(path.clone());
+            }
+            if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+                Self::walk_dirs(base, path.as_path(), depth - 1, matcher, dirs);
+            }
+        }
+    }
+
+    // lets the user pick one of several matching directories by its number
+    fn pick_dir(
+        dirs: &[path::PathBuf],
+        context: &JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<Option<path::PathBuf>> {
+        const PROMPT: &str = "cd ";
+
+        let user_input: Option<String> = {
+            let mut menu_options: Vec<String> = dirs
+                .iter()
+                .take(LIST_LIMIT)
+                .enumerate()
+                .map(|(i, p)| format!("  {} | {}", i, p.to_string_lossy()))
+                .collect();
+            if dirs.len() > LIST_LIMIT {
+                menu_options.push(format!("  and {} more", dirs.len() - LIST_LIMIT));
+            }
+            let menu_options_str: Vec<&str> = menu_options.iter().map(|e| e.as_str()).collect();
+            let menu_widget = TuiMenu::new(&menu_options_str);
+
+            let mut textfield = TuiTextField::default()
+                .prompt(":")
+                .prefix(PROMPT)
+                .menu(menu_widget);
+            textfield.get_input(backend, &context)
+        };
+
+        match user_input.as_ref() {
+            Some(user_input) if user_input.starts_with(PROMPT) => {
+                let user_input = user_input[PROMPT.len()..].trim();
+                match user_input.parse::<usize>() {
+                    Ok(n) if n < dirs.len().min(LIST_LIMIT) => Ok(Some(dirs[n].clone())),
+                    Err(_) if user_input.is_empty() => Ok(None),
+                    _ => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        String::from("option does not exist"),
+                    )),
+                }
+            }
+            _ => Ok(None),
+        }
+    }
 }
 
 impl JoshutoCommand for ChangeDirectory {}
 
 impl std::fmt::Display for ChangeDirectory {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} {}", Self::command(), self.path.to_str().unwrap())
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            escape_arg(&self.path.to_string_lossy())
+        )
     }
 }
 
 impl JoshutoRunnable for ChangeDirectory {
-    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
-        Self::change_directories(&self.path, context)?;
+    /*
+     * paths that exist are changed to as they are. otherwise the path is
+     * taken as a glob, going to the only directory matching it or asking
+     * which one when there are several
+     */
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if self.path.exists() {
+            Self::change_directories(&self.path, context)?;
+            LoadChild::load_child(context)?;
+            return Ok(());
+        }
+
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let dirs = Self::glob_dirs(self.path.as_path(), curr_path.as_path())?;
+        let path = match dirs.len() {
+            0 => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IONotFound,
+                    format!(
+                        "{}: No directory matches {}",
+                        Self::command(),
+                        self.path.to_string_lossy()
+                    ),
+                ))
+            }
+            1 => dirs[0].clone(),
+            _ => match Self::pick_dir(&dirs, context, backend)? {
+                Some(path) => path,
+                None => return Ok(()),
+            },
+        };
+        Self::change_directories(path.as_path(), context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ChangeDirectoryInteractive;
+
+impl ChangeDirectoryInteractive {
+    pub fn new() -> Self {
+        ChangeDirectoryInteractive
+    }
+    pub const fn command() -> &'static str {
+        "cd_interactive"
+    }
+
+    fn expand_path(input: &str, curr_path: &path::Path) -> path::PathBuf {
+        let path = match (input.strip_prefix("~"), HOME_DIR.as_ref()) {
+            (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+                home.join(rest.trim_start_matches('/'))
+            }
+            _ => path::PathBuf::from(input),
+        };
+        curr_path.join(path)
+    }
+}
+
+impl JoshutoCommand for ChangeDirectoryInteractive {}
+
+impl std::fmt::Display for ChangeDirectoryInteractive {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for ChangeDirectoryInteractive {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let mut prefix = curr_path.to_string_lossy().into_owned();
+        if !prefix.ends_with('/') {
+            prefix.push('/');
+        }
+
+        let user_input: Option<String> = TuiTextField::default()
+            .prompt("cd ")
+            .prefix(prefix.as_str())
+            .dir_completion(true)
+            .get_input(backend, &context);
+
+        let user_input = match user_input.as_ref().map(|s| s.trim()) {
+            Some(s) if !s.is_empty() => s,
+            _ => return Ok(()),
+        };
+        let path = Self::expand_path(user_input, curr_path.as_path());
+        if !path.is_dir() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                format!("{}: {} is not a directory", Self::command(), user_input),
+            ));
+        }
+        let path = path.canonicalize()?;
+        ChangeDirectory::change_directories(path.as_path(), context)?;
         LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+/*
+ * goes to the directory holding a file and puts the cursor on it,
+ * for paths printed by other programs. directories are entered
+ */
+#[derive(Clone, Debug)]
+pub struct Reveal {
+    path: path::PathBuf,
+}
+
+impl Reveal {
+    pub fn new(path: path::PathBuf) -> Self {
+        Reveal { path }
+    }
+    pub const fn command() -> &'static str {
+        "reveal"
+    }
+}
+
+impl JoshutoCommand for Reveal {}
 
+impl std::fmt::Display for Reveal {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            escape_arg(&self.path.to_string_lossy())
+        )
+    }
+}
+
+impl JoshutoRunnable for Reveal {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let path = self.path.as_path();
+        if !path.is_absolute() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: {} is not an absolute path",
+                    Self::command(),
+                    path.to_string_lossy()
+                ),
+            ));
+        }
+        /* broken symlinks can still be revealed */
+        if path.symlink_metadata().is_err() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                format!(
+                    "{}: {} does not exist",
+                    Self::command(),
+                    path.to_string_lossy()
+                ),
+            ));
+        }
+
+        let parent = match path.parent() {
+            Some(parent) if !path.is_dir() => parent,
+            _ => {
+                ChangeDirectory::change_directories(path, context)?;
+                LoadChild::load_child(context)?;
+                return Ok(());
+            }
+        };
+        ChangeDirectory::change_directories(parent, context)?;
+        let index = context
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|l| l.contents.iter().position(|e| e.file_path() == path));
+        match index {
+            Some(index) => cursor_move::cursor_move(index, context),
+            None => context.message_queue.push_back(format!(
+                "{}: {} is not listed, it may be hidden",
+                Self::command(),
+                path.to_string_lossy()
+            )),
+        }
+        LoadChild::load_child(context)?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir::TempDir;
+
+    #[test]
+    fn glob_characters_in_the_current_path_are_literal() {
+        let dir = TempDir::new();
+        let curr_path = dir.mkdir("dir[1]");
+        let abc = dir.mkdir("dir[1]/abc");
+        let abd = dir.mkdir("dir[1]/abd");
+        dir.mkdir("dir[1]/xyz");
+
+        let dirs = ChangeDirectory::glob_dirs(path::Path::new("ab"), &curr_path).unwrap();
+        assert_eq!(dirs, vec![abc.clone(), abd]);
+        let dirs = ChangeDirectory::glob_dirs(path::Path::new("*c"), &curr_path).unwrap();
+        assert_eq!(dirs, vec![abc]);
+    }
+
+    #[test]
+    fn double_star_stops_descending() {
+        let dir = TempDir::new();
+        let mut name = String::from("target");
+        for _ in 0..GLOB_MAX_DEPTH {
+            name = format!("d/{}", name);
+        }
+        let mut deepest = dir.path().to_path_buf();
+        for component in path::Path::new(&name).components() {
+            deepest.push(component);
+            fs::create_dir(&deepest).unwrap();
+        }
+
+        let dirs = ChangeDirectory::glob_dirs(path::Path::new("**/target"), dir.path()).unwrap();
+        assert!(dirs.is_empty());
+        let shallower = deepest.parent().unwrap().to_path_buf();
+        let dirs = ChangeDirectory::glob_dirs(path::Path::new("**/d"), dir.path()).unwrap();
+        assert!(dirs.contains(&shallower));
+    }
+}
@@ -0,0 +1,213 @@
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::io::IOWorkerThread;
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+const PERMISSION_BITS: u32 = 0o7777;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SymbolicOp {
+    Add,
+    Remove,
+    Set,
+}
+
+// one comma separated part of a symbolic mode, like u+x or go=r
+#[derive(Clone, Debug)]
+struct SymbolicClause {
+    who: u32,
+    op: SymbolicOp,
+    perms: String,
+}
+
+impl SymbolicClause {
+    fn parse(s: &str) -> Option<Self> {
+        let op_index = s.find(|c| c == '+' || c == '-' || c == '=')?;
+        let (who_str, rest) = s.split_at(op_index);
+
+        let mut who = 0;
+        for c in who_str.chars() {
+            who |= match c {
+                'u' => 0o4700,
+                'g' => 0o2070,
+                'o' => 0o1007,
+                'a' => 0o7777,
+                _ => return None,
+            };
+        }
+        /* like chmod, leaving out who means everyone */
+        if who == 0 {
+            who = 0o7777;
+        }
+
+        let op = match rest.chars().next()? {
+            '+' => SymbolicOp::Add,
+            '-' => SymbolicOp::Remove,
+            _ => SymbolicOp::Set,
+        };
+        let perms = rest[1..].to_string();
+        if !perms.chars().all(|c| "rwxXst".contains(c)) {
+            return None;
+        }
+        Some(Self { who, op, perms })
+    }
+
+    fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        let mut bits = 0;
+        for c in self.perms.chars() {
+            bits |= match c {
+                'r' => 0o444,
+                'w' => 0o222,
+                'x' => 0o111,
+                /* execute only for directories or files already executable by someone */
+                'X' if is_dir || mode & 0o111 != 0 => 0o111,
+                's' => 0o6000,
+                't' => 0o1000,
+                _ => 0,
+            };
+        }
+        let bits = bits & self.who;
+        match self.op {
+            SymbolicOp::Add => mode | bits,
+            SymbolicOp::Remove => mode & !bits,
+            SymbolicOp::Set => (mode & !(self.who & PERMISSION_BITS)) | bits,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum ChmodMode {
+    Octal(u32),
+    Symbolic(Vec<SymbolicClause>),
+}
+
+impl ChmodMode {
+    // accepts octal modes like 644 and symbolic ones like u+x,go-w
+    fn parse(s: &str) -> Option<Self> {
+        if !s.is_empty() && s.chars().all(|c| c.is_digit(8)) {
+            return match u32::from_str_radix(s, 8) {
+                Ok(mode) if mode <= PERMISSION_BITS => Some(ChmodMode::Octal(mode)),
+                _ => None,
+            };
+        }
+        let clauses: Option<Vec<SymbolicClause>> =
+            s.split(',').map(SymbolicClause::parse).collect();
+        clauses.map(ChmodMode::Symbolic)
+    }
+
+    fn apply(&self, mode: u32, is_dir: bool) -> u32 {
+        match self {
+            ChmodMode::Octal(m) => (mode & !PERMISSION_BITS) | m,
+            ChmodMode::Symbolic(clauses) => clauses
+                .iter()
+                .fold(mode, |mode, clause| clause.apply(mode, is_dir)),
+        }
+    }
+}
+
+// does not follow symlinks, their permissions are meaningless
+fn chmod_path(path: &path::Path, mode: &ChmodMode, recursive: bool) -> std::io::Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() {
+        return Ok(0);
+    }
+    let mut permissions = metadata.permissions();
+    permissions.set_mode(mode.apply(permissions.mode(), metadata.is_dir()));
+    fs::set_permissions(path, permissions)?;
+
+    let mut total = metadata.len();
+    if recursive && metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            total += chmod_path(entry?.path().as_path(), mode, recursive)?;
+        }
+    }
+    Ok(total)
+}
+
+#[derive(Clone, Debug)]
+pub struct Chmod {
+    mode_str: String,
+    mode: ChmodMode,
+    recursive: bool,
+}
+
+impl Chmod {
+    pub fn new(mode_str: String, recursive: bool) -> Option<Self> {
+        let mode = ChmodMode::parse(mode_str.as_str())?;
+        Some(Chmod {
+            mode_str,
+            mode,
+            recursive,
+        })
+    }
+    pub const fn command() -> &'static str {
+        "chmod"
+    }
+
+    // directory trees can be large, so recursive changes happen in a worker
+    fn chmod_recursive(&self, context: &mut JoshutoContext, paths: Vec<path::PathBuf>) {
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let mode = self.mode.clone();
+
+        let thread = IOWorkerThread::spawn(vec![curr_path.clone()], curr_path, move || {
+            let mut total = 0;
+            for path in paths {
+                total += chmod_path(path.as_path(), &mode, true)?;
+            }
+            Ok(total)
+        });
+        context.add_new_worker(thread);
+    }
+}
+
+impl JoshutoCommand for Chmod {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for Chmod {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.recursive {
+            f.write_str(" -R")?;
+        }
+        write!(f, " {}", self.mode_str)
+    }
+}
+
+impl JoshutoRunnable for Chmod {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+            Some(s) => s.get_selected_paths().into_iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                String::from("No files selected"),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+
+        if self.recursive {
+            self.chmod_recursive(context, paths);
+            return Ok(());
+        }
+        for path in paths.iter() {
+            chmod_path(path.as_path(), &self.mode, false)?;
+        }
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
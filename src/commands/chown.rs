@@ -0,0 +1,207 @@
+use std::ffi::CString;
+use std::fs;
+use std::os::unix::ffi::OsStrExt;
+use std::path;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::io::IOWorkerThread;
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+// passing -1 to chown leaves the id unchanged
+const UNCHANGED: u32 = u32::max_value();
+const ROOT_HINT: &str = "changing owners usually requires root";
+
+// a numeric uid is taken as is, the login group is only known if it has a passwd entry
+fn resolve_uid(user: &str) -> Option<(u32, u32)> {
+    match user.parse::<u32>() {
+        Ok(uid) => {
+            let login_gid = users::get_user_by_uid(uid).map(|u| u.primary_group_id());
+            Some((uid, login_gid.unwrap_or(UNCHANGED)))
+        }
+        Err(_) => {
+            let user = users::get_user_by_name(user)?;
+            Some((user.uid(), user.primary_group_id()))
+        }
+    }
+}
+
+fn resolve_gid(group: &str) -> Option<u32> {
+    match group.parse::<u32>() {
+        Ok(gid) => Some(gid),
+        Err(_) => users::get_group_by_name(group).map(|g| g.gid()),
+    }
+}
+
+/*
+ * parses user, user:group, :group and user: (the user's login group)
+ * into a uid and gid, either of which may be left unchanged
+ */
+pub fn parse_owner(s: &str) -> Result<(u32, u32), String> {
+    let (user, group) = match s.find(':') {
+        Some(i) => (&s[..i], Some(&s[i + 1..])),
+        None => (s, None),
+    };
+    if user.is_empty() && group.map(|g| g.is_empty()).unwrap_or(true) {
+        return Err(format!("invalid owner {}", s));
+    }
+
+    let (uid, login_gid) = if user.is_empty() {
+        (UNCHANGED, UNCHANGED)
+    } else {
+        resolve_uid(user).ok_or_else(|| format!("unknown user {}", user))?
+    };
+    let gid = match group {
+        None => UNCHANGED,
+        Some("") => login_gid,
+        Some(group) => resolve_gid(group).ok_or_else(|| format!("unknown group {}", group))?,
+    };
+    Ok((uid, gid))
+}
+
+// changes the owner of path itself, not of what a symlink points to
+fn lchown(path: &path::Path, uid: u32, gid: u32) -> std::io::Result<()> {
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+    let res = unsafe { libc::lchown(c_path.as_ptr(), uid, gid) };
+    if res == 0 {
+        Ok(())
+    } else {
+        Err(std::io::Error::last_os_error())
+    }
+}
+
+fn chown_path(path: &path::Path, uid: u32, gid: u32, recursive: bool) -> std::io::Result<u64> {
+    let metadata = fs::symlink_metadata(path)?;
+    lchown(path, uid, gid)?;
+
+    let mut total = metadata.len();
+    if recursive && metadata.is_dir() {
+        for entry in fs::read_dir(path)? {
+            total += chown_path(entry?.path().as_path(), uid, gid, recursive)?;
+        }
+    }
+    Ok(total)
+}
+
+#[derive(Clone, Debug)]
+pub struct Chown {
+    owner: String,
+    uid: u32,
+    gid: u32,
+    recursive: bool,
+}
+
+impl Chown {
+    pub fn new(owner: String, recursive: bool) -> Result<Self, String> {
+        let (uid, gid) = parse_owner(owner.as_str())?;
+        Ok(Chown {
+            owner,
+            uid,
+            gid,
+            recursive,
+        })
+    }
+    pub const fn command() -> &'static str {
+        "chown"
+    }
+
+    fn permission_error(err: std::io::Error) -> JoshutoError {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => JoshutoError::new(
+                JoshutoErrorKind::IOPermissionDenied,
+                format!("{}: {}, {}", Self::command(), err, ROOT_HINT),
+            ),
+            _ => JoshutoError::from(err),
+        }
+    }
+
+    // the same hint for the worker, which can only report an io::Error
+    fn permission_io_error(err: std::io::Error) -> std::io::Error {
+        match err.kind() {
+            std::io::ErrorKind::PermissionDenied => std::io::Error::new(
+                err.kind(),
+                format!("{}: {}, {}", Self::command(), err, ROOT_HINT),
+            ),
+            _ => err,
+        }
+    }
+
+    // directory trees can be large, so recursive changes happen in a worker
+    fn chown_recursive(&self, context: &mut JoshutoContext, paths: Vec<path::PathBuf>) {
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let (uid, gid) = (self.uid, self.gid);
+
+        let thread = IOWorkerThread::spawn(vec![curr_path.clone()], curr_path, move || {
+            let mut total = 0;
+            for path in paths {
+                total += chown_path(path.as_path(), uid, gid, true)
+                    .map_err(Self::permission_io_error)?;
+            }
+            Ok(total)
+        });
+        context.add_new_worker(thread);
+    }
+}
+
+impl JoshutoCommand for Chown {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for Chown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.recursive {
+            f.write_str(" -R")?;
+        }
+        write!(f, " {}", self.owner)
+    }
+}
+
+impl JoshutoRunnable for Chown {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+            Some(s) => s.get_selected_paths().into_iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                String::from("No files selected"),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+
+        if self.recursive {
+            self.chown_recursive(context, paths);
+            return Ok(());
+        }
+        for path in paths.iter() {
+            chown_path(path.as_path(), self.uid, self.gid, false)
+                .map_err(Self::permission_error)?;
+        }
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_uid_without_passwd_entry() {
+        let uid = u32::max_value() - 2;
+        assert!(users::get_user_by_uid(uid).is_none());
+        assert_eq!(parse_owner(&uid.to_string()), Ok((uid, UNCHANGED)));
+        assert_eq!(parse_owner(&format!("{}:", uid)), Ok((uid, UNCHANGED)));
+    }
+}
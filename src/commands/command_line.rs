@@ -30,20 +30,33 @@ impl CommandLine {
         let user_input: Option<String> = textfield.get_input(backend, &context);
 
         if let Some(s) = user_input {
-            let trimmed = s.trim_start();
-            let command = commands::parse_command(trimmed)?;
-            command.execute(context, backend)
+            let trimmed = s.trim();
+            /* a bare number opens the entry at that index */
+            if let Ok(index) = trimmed.parse::<usize>() {
+                let command = commands::OpenIndex::new(index);
+                return commands::run_command(&command, context, backend);
+            }
+            let command = commands::parse_command(s.trim_start())?;
+            commands::run_command(command.as_ref(), context, backend)
         } else {
             Ok(())
         }
     }
 }
 
-impl JoshutoCommand for CommandLine {}
+impl JoshutoCommand for CommandLine {
+    fn is_repeatable(&self) -> bool {
+        false
+    }
+}
 
 impl std::fmt::Display for CommandLine {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}: {} {}", Self::command(), self.prefix, self.suffix)
+        f.write_str(Self::command())?;
+        if !self.prefix.is_empty() || !self.suffix.is_empty() {
+            write!(f, " {}{}", self.prefix, self.suffix)?;
+        }
+        Ok(())
     }
 }
 
@@ -45,7 +45,7 @@ impl CursorMoveDown {
         Self { movement }
     }
     pub const fn command() -> &'static str {
-        "cursor_move_up"
+        "cursor_move_down"
     }
 }
 
@@ -81,7 +81,7 @@ impl CursorMoveUp {
         Self { movement }
     }
     pub const fn command() -> &'static str {
-        "cursor_move_down"
+        "cursor_move_up"
     }
 }
 
@@ -208,6 +208,91 @@ impl JoshutoRunnable for CursorMovePageDown {
     }
 }
 
+// half the number of rows the file lists are drawn in, at least 1
+fn half_page_rows(backend: &TuiBackend) -> usize {
+    match backend.terminal.as_ref().unwrap().size() {
+        Ok(rect) => std::cmp::max((rect.height as usize).saturating_sub(2) / 2, 1),
+        _ => 5,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CursorMoveHalfPageUp;
+
+impl CursorMoveHalfPageUp {
+    pub fn new() -> Self {
+        Self
+    }
+    pub const fn command() -> &'static str {
+        "cursor_move_half_page_up"
+    }
+}
+
+impl JoshutoCommand for CursorMoveHalfPageUp {}
+
+impl std::fmt::Display for CursorMoveHalfPageUp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::command())
+    }
+}
+
+impl JoshutoRunnable for CursorMoveHalfPageUp {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let half_page = half_page_rows(backend);
+
+        let movement = match context.curr_tab_ref().curr_list_ref() {
+            Some(curr_list) => curr_list.index.map(|idx| idx.saturating_sub(half_page)),
+            None => None,
+        };
+
+        if let Some(s) = movement {
+            cursor_move(s, context);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CursorMoveHalfPageDown;
+
+impl CursorMoveHalfPageDown {
+    pub fn new() -> Self {
+        Self
+    }
+    pub const fn command() -> &'static str {
+        "cursor_move_half_page_down"
+    }
+}
+
+impl JoshutoCommand for CursorMoveHalfPageDown {}
+
+impl std::fmt::Display for CursorMoveHalfPageDown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", Self::command())
+    }
+}
+
+impl JoshutoRunnable for CursorMoveHalfPageDown {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let half_page = half_page_rows(backend);
+
+        let movement = match context.curr_tab_ref().curr_list_ref() {
+            Some(curr_list) => {
+                let dir_len = curr_list.contents.len();
+                curr_list
+                    .index
+                    .map(|idx| std::cmp::min(idx + half_page, dir_len.saturating_sub(1)))
+            }
+            None => None,
+        };
+
+        if let Some(s) = movement {
+            cursor_move(s, context);
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct CursorMoveHome;
 
@@ -289,3 +374,106 @@ impl JoshutoRunnable for CursorMoveEnd {
         Ok(())
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct GotoIndex {
+    index: usize,
+}
+
+impl GotoIndex {
+    pub fn new(index: usize) -> Self {
+        Self { index }
+    }
+    pub const fn command() -> &'static str {
+        "goto_index"
+    }
+}
+
+impl JoshutoCommand for GotoIndex {}
+
+impl std::fmt::Display for GotoIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.index)
+    }
+}
+
+impl JoshutoRunnable for GotoIndex {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        /* indices are 1-based, as shown by the line numbers. past the end goes to the last one */
+        cursor_move(self.index.saturating_sub(1), context);
+        Ok(())
+    }
+}
+
+/*
+ * moves the cursor of the directory previewed on the right without
+ * entering it, so it starts out there once the directory is opened
+ */
+fn preview_cursor_move(movement: isize, context: &mut JoshutoContext) {
+    let curr_tab = &mut context.tabs[context.curr_tab_index];
+    if let Some(child_list) = curr_tab.child_list_mut() {
+        if let Some(idx) = child_list.index {
+            let dir_len = child_list.contents.len() as isize;
+            let new_index = (idx as isize + movement).max(0).min(dir_len - 1);
+            child_list.index = Some(new_index as usize);
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PreviewCursorMoveDown {
+    movement: usize,
+}
+
+impl PreviewCursorMoveDown {
+    pub fn new(movement: usize) -> Self {
+        Self { movement }
+    }
+    pub const fn command() -> &'static str {
+        "preview_cursor_move_down"
+    }
+}
+
+impl JoshutoCommand for PreviewCursorMoveDown {}
+
+impl std::fmt::Display for PreviewCursorMoveDown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.movement)
+    }
+}
+
+impl JoshutoRunnable for PreviewCursorMoveDown {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        preview_cursor_move(self.movement as isize, context);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PreviewCursorMoveUp {
+    movement: usize,
+}
+
+impl PreviewCursorMoveUp {
+    pub fn new(movement: usize) -> Self {
+        Self { movement }
+    }
+    pub const fn command() -> &'static str {
+        "preview_cursor_move_up"
+    }
+}
+
+impl JoshutoCommand for PreviewCursorMoveUp {}
+
+impl std::fmt::Display for PreviewCursorMoveUp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.movement)
+    }
+}
+
+impl JoshutoRunnable for PreviewCursorMoveUp {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        preview_cursor_move(-(self.movement as isize), context);
+        Ok(())
+    }
+}
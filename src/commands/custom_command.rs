@@ -0,0 +1,125 @@
+use lazy_static::lazy_static;
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+use crate::commands::{self, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+
+lazy_static! {
+    static ref CUSTOM_COMMANDS: Mutex<HashMap<String, Vec<String>>> = Mutex::new(HashMap::new());
+}
+
+/*
+ * a user defined sequence of commands, run in order until one of them fails.
+ * custom commands may only be made of built-in commands, so they can never
+ * expand into themselves
+ */
+#[derive(Debug)]
+pub struct CustomCommand {
+    name: String,
+    commands: Vec<Box<dyn JoshutoCommand>>,
+}
+
+impl std::clone::Clone for CustomCommand {
+    fn clone(&self) -> Self {
+        CustomCommand {
+            name: self.name.clone(),
+            commands: self.commands.iter().map(|c| c.clone_command()).collect(),
+        }
+    }
+}
+
+impl CustomCommand {
+    /*
+     * registers every custom command whose steps are all built-in commands,
+     * returning why the others were left out. steps are checked against the
+     * names of all of them, so one defined further down can't be run either
+     */
+    pub fn register_all(commands: Vec<(String, Vec<String>)>) -> Vec<String> {
+        let names: HashSet<String> = commands.iter().map(|(name, _)| name.clone()).collect();
+        let mut custom_commands = CUSTOM_COMMANDS.lock().unwrap();
+        let mut errors = Vec::new();
+        for (name, steps) in commands {
+            match Self::check_steps(name.as_str(), &steps, &names) {
+                Ok(()) => {
+                    custom_commands.insert(name, steps);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+        errors
+    }
+
+    fn check_steps(name: &str, steps: &[String], names: &HashSet<String>) -> Result<(), String> {
+        if steps.is_empty() {
+            return Err(format!("Error: custom command {} is empty", name));
+        }
+        for s in steps.iter() {
+            let command = s.split_whitespace().next().unwrap_or("");
+            if names.contains(command) {
+                return Err(format!(
+                    "Error: custom command {} cannot run custom command {}",
+                    name, command
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    // returns None if no custom command is named name
+    pub fn from_name(name: &str) -> Option<JoshutoResult<Self>> {
+        let custom_commands = CUSTOM_COMMANDS.lock().unwrap();
+        let command_strs = custom_commands.get(name)?.clone();
+        drop(custom_commands);
+
+        let commands: JoshutoResult<Vec<Box<dyn JoshutoCommand>>> = command_strs
+            .iter()
+            .map(|s| commands::parse_command(s.as_str()))
+            .collect();
+        Some(commands.map(|commands| CustomCommand {
+            name: name.to_string(),
+            commands,
+        }))
+    }
+}
+
+impl JoshutoCommand for CustomCommand {}
+
+impl std::fmt::Display for CustomCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(self.name.as_str())
+    }
+}
+
+impl JoshutoRunnable for CustomCommand {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        for command in self.commands.iter() {
+            commands::run_command(command.as_ref(), context, backend)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(steps: &[&str]) -> Vec<String> {
+        steps.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn steps_may_not_run_custom_commands_defined_later() {
+        let names: HashSet<String> = vec!["first".to_string(), "second".to_string()]
+            .into_iter()
+            .collect();
+        assert!(CustomCommand::check_steps("first", &steps(&["reload_dirlist"]), &names).is_ok());
+        assert!(CustomCommand::check_steps("first", &steps(&["second"]), &names).is_err());
+        assert!(CustomCommand::check_steps("second", &steps(&["first"]), &names).is_err());
+        assert!(CustomCommand::check_steps("first", &steps(&["first"]), &names).is_err());
+        assert!(CustomCommand::check_steps("first", &[], &names).is_err());
+    }
+}
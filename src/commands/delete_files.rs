@@ -1,41 +1,108 @@
 use std::fs;
 use std::path;
 
+use globset::GlobMatcher;
 use termion::event::Key;
 
-use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::commands::{
+    check_writable, cursor_move, escape_arg, report_dry_run, report_results, ChangeDirectory,
+    JoshutoCommand, JoshutoRunnable, ReloadDirList,
+};
+use crate::config::EmptyDirMode;
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
-use crate::history::DirectoryHistory;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::io::IOResults;
 use crate::ui::widgets::TuiPrompt;
 use crate::ui::TuiBackend;
+use crate::util::format;
 use crate::util::load_child::LoadChild;
 
 #[derive(Clone, Debug)]
-pub struct DeleteFiles;
+pub struct DeleteFiles {
+    dry_run: bool,
+}
 
 impl DeleteFiles {
-    pub fn new() -> Self {
-        DeleteFiles
+    pub fn new(dry_run: bool) -> Self {
+        DeleteFiles { dry_run }
     }
     pub const fn command() -> &'static str {
         "delete_files"
     }
 
-    pub fn remove_files(paths: &[&path::PathBuf]) -> std::io::Result<()> {
+    // keeps going past entries that can't be removed, recording why
+    pub fn remove_files(paths: &[&path::PathBuf]) -> IOResults {
+        let mut results = IOResults::new("delete");
         for path in paths {
-            if let Ok(metadata) = fs::symlink_metadata(path) {
+            let res = fs::symlink_metadata(path).and_then(|metadata| {
                 if metadata.is_dir() {
-                    fs::remove_dir_all(&path)?;
+                    fs::remove_dir_all(&path)
                 } else {
-                    fs::remove_file(&path)?;
+                    fs::remove_file(&path)
                 }
+            });
+            results.push(path, res);
+        }
+        results
+    }
+
+    /*
+     * the first of paths that some tab is currently inside of, with the
+     * index of that tab. deleting it would leave the tab in a directory
+     * that no longer exists
+     */
+    pub fn tab_inside<'a>(
+        paths: &[&'a path::PathBuf],
+        context: &JoshutoContext,
+    ) -> Option<(&'a path::PathBuf, usize)> {
+        paths.iter().find_map(|p| {
+            context
+                .tabs
+                .iter()
+                .position(|tab| tab.curr_path.starts_with(p))
+                .map(|i| (*p, i))
+        })
+    }
+
+    /*
+     * once nothing is left to list in the current directory, either say so
+     * or change to its parent with the cursor on it, as empty_after_delete
+     * asks
+     */
+    pub fn leave_if_empty(context: &mut JoshutoContext) -> JoshutoResult<()> {
+        let is_empty = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => list.contents.is_empty(),
+            None => false,
+        };
+        if !is_empty {
+            return Ok(());
+        }
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let parent = match curr_path.parent() {
+            Some(parent) if context.config_t.empty_after_delete == EmptyDirMode::Parent => parent,
+            _ => {
+                context
+                    .message_queue
+                    .push_back(format!("Nothing left in {}", curr_path.to_string_lossy()));
+                return Ok(());
             }
+        };
+        ChangeDirectory::change_directories(parent, context)?;
+        let index = context
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|l| l.contents.iter().position(|e| e.file_path() == &curr_path));
+        if let Some(index) = index {
+            cursor_move::cursor_move(index, context);
         }
         Ok(())
     }
 
-    fn delete_files(context: &mut JoshutoContext, backend: &mut TuiBackend) -> std::io::Result<()> {
+    fn delete_files(
+        &self,
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<()> {
         let curr_tab = &context.tabs[context.curr_tab_index];
         let paths = match curr_tab.curr_list_ref() {
             Some(s) => s.get_selected_paths(),
@@ -44,14 +111,39 @@ impl DeleteFiles {
         let paths_len = paths.len();
 
         if paths_len == 0 {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "no files selected",
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOOther,
+                String::from("no files selected"),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+        if let Some((path, index)) = Self::tab_inside(&paths, context) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot delete {}, tab {} is inside of it",
+                    Self::command(),
+                    path.to_string_lossy(),
+                    index + 1
+                ),
             ));
         }
 
+        if self.dry_run {
+            let lines: Vec<String> = paths
+                .iter()
+                .map(|p| format!("Would delete {}", p.to_string_lossy()))
+                .collect();
+            report_dry_run(&lines, context, backend);
+            return Ok(());
+        }
+
+        /* tallied before anything is removed */
+        let summary = format::selection_summary(&paths);
         let ch = {
-            let prompt_str = format!("Delete {} files? (Y/n)", paths_len);
+            let prompt_str = format!("Delete {}? (Y/n)", summary);
             let mut prompt = TuiPrompt::new(&prompt_str);
             prompt.get_key(backend, &context)
         };
@@ -64,40 +156,191 @@ impl DeleteFiles {
                     prompt.get_key(backend, &context)
                 };
                 if ch == Key::Char('y') {
-                    Self::remove_files(&paths)?;
-                    ReloadDirList::reload(context.curr_tab_index, context)?;
-                    let msg = format!("Deleted {} files", paths_len);
-                    context.message_queue.push_back(msg);
+                    let results = Self::remove_files(&paths);
+                    let msg = format!("Deleted {}", summary);
+                    report_results(&results, msg, context, backend);
                 }
             } else {
-                Self::remove_files(&paths)?;
-                ReloadDirList::reload(context.curr_tab_index, context)?;
-                let msg = format!("Deleted {} files", paths_len);
-                context.message_queue.push_back(msg);
+                let results = Self::remove_files(&paths);
+                let msg = format!("Deleted {}", summary);
+                report_results(&results, msg, context, backend);
             }
         }
         Ok(())
     }
 }
 
-impl JoshutoCommand for DeleteFiles {}
+impl JoshutoCommand for DeleteFiles {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for DeleteFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(Self::command())
+        f.write_str(Self::command())?;
+        if self.dry_run {
+            f.write_str(" --dry-run")?;
+        }
+        Ok(())
     }
 }
 
 impl JoshutoRunnable for DeleteFiles {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
-        Self::delete_files(context, backend)?;
+        check_writable(Self::command(), context)?;
+        self.delete_files(context, backend)?;
 
-        let options = &context.config_t.sort_option;
         let curr_path = context.tabs[context.curr_tab_index].curr_path.clone();
-        for tab in context.tabs.iter_mut() {
-            tab.history.reload(&curr_path, options)?;
+        ReloadDirList::reload_path(&curr_path, context)?;
+        DeleteFiles::leave_if_empty(context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DeletePattern {
+    pattern: GlobMatcher,
+    // also match hidden files while they aren't shown
+    hidden: bool,
+}
+
+impl DeletePattern {
+    pub fn new(pattern: GlobMatcher, hidden: bool) -> Self {
+        DeletePattern { pattern, hidden }
+    }
+    pub const fn command() -> &'static str {
+        "delete_pattern"
+    }
+
+    // entries of the current directory whose names match, sorted by name
+    fn matching_paths(&self, context: &JoshutoContext) -> std::io::Result<Vec<path::PathBuf>> {
+        let curr_tab = context.curr_tab_ref();
+        let mut paths: Vec<path::PathBuf> = if self.hidden {
+            fs::read_dir(curr_tab.curr_path.as_path())?
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .collect()
+        } else {
+            /* only what is listed, so hidden files stay out of it unless shown */
+            match curr_tab.curr_list_ref() {
+                Some(curr_list) => curr_list
+                    .contents
+                    .iter()
+                    .map(|e| e.file_path().clone())
+                    .collect(),
+                None => Vec::new(),
+            }
+        };
+        paths.retain(|p| match p.file_name() {
+            Some(name) => self.pattern.is_match(name),
+            None => false,
+        });
+        paths.sort();
+        Ok(paths)
+    }
+}
+
+impl JoshutoCommand for DeletePattern {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for DeletePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            escape_arg(self.pattern.glob().glob())
+        )?;
+        if self.hidden {
+            f.write_str(" --hidden")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for DeletePattern {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        check_writable(Self::command(), context)?;
+        let paths = self.matching_paths(context)?;
+        if paths.is_empty() {
+            context.message_queue.push_back(format!(
+                "{}: Nothing matches {}",
+                Self::command(),
+                self.pattern.glob()
+            ));
+            return Ok(());
+        }
+        let paths: Vec<&path::PathBuf> = paths.iter().collect();
+        if let Some((path, index)) = DeleteFiles::tab_inside(&paths, context) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot delete {}, tab {} is inside of it",
+                    Self::command(),
+                    path.to_string_lossy(),
+                    index + 1
+                ),
+            ));
         }
+
+        let summary = format::selection_summary(&paths);
+        let ch = {
+            let names: Vec<String> = paths
+                .iter()
+                .map(|p| format!("  {}", p.file_name().unwrap_or_default().to_string_lossy()))
+                .collect();
+            let names: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+            /* a pattern can match more than expected, so only an explicit y will do */
+            let prompt_str = format!("Delete {}? (y/N)", summary);
+            let mut prompt = TuiPrompt::new(&prompt_str).menu(&names);
+            prompt.get_key(backend, &context)
+        };
+        if ch != Key::Char('y') {
+            return Ok(());
+        }
+
+        let results = DeleteFiles::remove_files(&paths);
+
+        let curr_path = context.tabs[context.curr_tab_index].curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        DeleteFiles::leave_if_empty(context)?;
         LoadChild::load_child(context)?;
+        let msg = format!("Deleted {}", summary);
+        report_results(&results, msg, context, backend);
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::JoshutoConfig;
+    use crate::tab::JoshutoTab;
+    use crate::util::test_dir::TempDir;
+
+    #[test]
+    fn finds_the_tab_inside_a_directory() {
+        let dir = TempDir::new();
+        let target = dir.mkdir("target");
+        let inner = dir.mkdir("target/inner");
+        let other = dir.mkdir("other");
+
+        let mut context = JoshutoContext::without_state(JoshutoConfig::default());
+        let options = context.config_t.sort_option.clone();
+        for path in &[dir.path().to_path_buf(), inner] {
+            let tab = JoshutoTab::new(path.clone(), &options).unwrap();
+            context.push_tab(tab);
+        }
+
+        assert_eq!(
+            DeleteFiles::tab_inside(&[&other, &target], &context),
+            Some((&target, 1))
+        );
+        assert_eq!(DeleteFiles::tab_inside(&[&other], &context), None);
+    }
+}
@@ -0,0 +1,108 @@
+use std::io::Write;
+use std::path;
+use std::process;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct Diff;
+
+impl Diff {
+    pub fn new() -> Self {
+        Diff
+    }
+    pub const fn command() -> &'static str {
+        "diff"
+    }
+
+    pub fn diff(
+        program: &str,
+        left: &path::Path,
+        right: &path::Path,
+    ) -> std::io::Result<process::ExitStatus> {
+        let mut words = program.split_whitespace();
+        let program = words.next().unwrap_or("diff");
+
+        let mut command = process::Command::new(program);
+        command.args(words);
+        /* plain diff needs to be told to descend into directories */
+        if left.is_dir() && right.is_dir() && program == "diff" {
+            command.arg("-r");
+        }
+        command.arg("--").arg(left).arg(right);
+
+        let status = command.spawn()?.wait()?;
+
+        print!("Press ENTER to continue...");
+        std::io::stdout().flush()?;
+        let mut user_input = String::new();
+        std::io::stdin().read_line(&mut user_input)?;
+        Ok(status)
+    }
+}
+
+impl JoshutoCommand for Diff {}
+
+impl std::fmt::Display for Diff {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for Diff {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+            Some(s) => s
+                .selected_entries()
+                .map(|e| e.file_path().clone())
+                .collect(),
+            None => Vec::new(),
+        };
+        if paths.len() != 2 {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Select exactly two files to compare, {} selected",
+                    Self::command(),
+                    paths.len()
+                ),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+        if paths[0].is_dir() != paths[1].is_dir() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot compare a file with a directory",
+                    Self::command()
+                ),
+            ));
+        }
+
+        let program = context.config_t.diff_program.clone();
+        backend.terminal_drop();
+        let res = Self::diff(program.as_str(), &paths[0], &paths[1]);
+        backend.terminal_restore()?;
+
+        match res {
+            Ok(status) if status.code() == Some(0) => {
+                context
+                    .message_queue
+                    .push_back("No differences found".to_string());
+            }
+            Ok(_) => {}
+            Err(e) => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::from(e.kind()),
+                    format!("{}: {}: {}", Self::command(), program, e),
+                ))
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct ToggleExtensionColumn;
+
+impl ToggleExtensionColumn {
+    pub fn new() -> Self {
+        ToggleExtensionColumn
+    }
+    pub const fn command() -> &'static str {
+        "toggle_extension_column"
+    }
+}
+
+impl JoshutoCommand for ToggleExtensionColumn {}
+
+impl std::fmt::Display for ToggleExtensionColumn {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for ToggleExtensionColumn {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        context.config_t.extension_column = !context.config_t.extension_column;
+        Ok(())
+    }
+}
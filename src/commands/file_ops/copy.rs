@@ -39,3 +39,37 @@ impl JoshutoRunnable for CopyFiles {
         Ok(())
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct CopyAppend;
+
+impl CopyAppend {
+    pub fn new() -> Self {
+        CopyAppend
+    }
+    pub const fn command() -> &'static str {
+        "copy_append"
+    }
+}
+
+impl JoshutoCommand for CopyAppend {}
+
+impl std::fmt::Display for CopyAppend {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for CopyAppend {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let count = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => LocalState::append_selected_files(list, FileOp::Copy)?,
+            None => return Ok(()),
+        };
+        LocalState::set_tab_src(context.curr_tab_index);
+        context
+            .message_queue
+            .push_back(format!("{} files to copy", count));
+        Ok(())
+    }
+}
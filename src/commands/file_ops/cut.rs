@@ -39,3 +39,37 @@ impl JoshutoRunnable for CutFiles {
         Ok(())
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct CutAppend;
+
+impl CutAppend {
+    pub fn new() -> Self {
+        CutAppend
+    }
+    pub const fn command() -> &'static str {
+        "cut_append"
+    }
+}
+
+impl JoshutoCommand for CutAppend {}
+
+impl std::fmt::Display for CutAppend {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for CutAppend {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let count = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => LocalState::append_selected_files(list, FileOp::Cut)?,
+            None => return Ok(()),
+        };
+        LocalState::set_tab_src(context.curr_tab_index);
+        context
+            .message_queue
+            .push_back(format!("{} files to cut", count));
+        Ok(())
+    }
+}
@@ -11,7 +11,7 @@ lazy_static! {
     static ref TAB_SRC: atomic::AtomicUsize = atomic::AtomicUsize::new(0);
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum FileOp {
     Cut,
     Copy,
@@ -45,11 +45,92 @@ impl LocalState {
         }
     }
 
+    /*
+     * adds the selection to the files already gathered for operation,
+     * returning how many there are now. files gathered for the other
+     * operation are left alone and the selection is rejected
+     */
+    pub fn append_selected_files(
+        dirlist: &JoshutoDirList,
+        operation: FileOp,
+    ) -> std::io::Result<usize> {
+        let selected = dirlist.get_selected_paths();
+        if selected.is_empty() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "no files selected",
+            ));
+        }
+
+        let mut data = SELECTED_FILES.lock().unwrap();
+        let mut file_op = FILE_OPERATION.lock().unwrap();
+        let files = match data.as_mut() {
+            Some(files) if !files.is_empty() => {
+                if *file_op != operation {
+                    let msg = match *file_op {
+                        FileOp::Copy => "files to copy were already gathered, paste them first",
+                        FileOp::Cut => "files to cut were already gathered, paste them first",
+                    };
+                    return Err(std::io::Error::new(std::io::ErrorKind::Other, msg));
+                }
+                files
+            }
+            _ => {
+                *file_op = operation;
+                data.get_or_insert_with(Vec::new)
+            }
+        };
+        for path in selected {
+            if !files.contains(path) {
+                files.push(path.clone());
+            }
+        }
+        Ok(files.len())
+    }
+
+    pub fn set_selected_files(paths: Option<Vec<path::PathBuf>>) {
+        let mut data = SELECTED_FILES.lock().unwrap();
+        *data = paths;
+    }
+
+    pub fn get_selected_files() -> Option<Vec<path::PathBuf>> {
+        SELECTED_FILES.lock().unwrap().clone()
+    }
+
     pub fn take_selected_files() -> Option<Vec<path::PathBuf>> {
         SELECTED_FILES.lock().unwrap().take()
     }
 
+    // the directories paths are in, each once, as gathered from several by appending
+    pub fn parent_dirs(paths: &[path::PathBuf]) -> Vec<path::PathBuf> {
+        let mut dirs: Vec<path::PathBuf> = Vec::new();
+        for dir in paths.iter().filter_map(|p| p.parent()) {
+            if !dirs.iter().any(|d| d == dir) {
+                dirs.push(dir.to_path_buf());
+            }
+        }
+        dirs
+    }
+
     pub fn get_file_operation() -> FileOp {
         (*FILE_OPERATION.lock().unwrap()).clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parent_dirs_lists_each_directory_once() {
+        let paths: Vec<path::PathBuf> = ["/a/x", "/b/y", "/a/z"]
+            .iter()
+            .map(path::PathBuf::from)
+            .collect();
+        let dirs = LocalState::parent_dirs(&paths);
+        assert_eq!(
+            dirs,
+            vec![path::PathBuf::from("/a"), path::PathBuf::from("/b")]
+        );
+    }
+}
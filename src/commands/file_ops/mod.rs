@@ -5,7 +5,10 @@ mod name_resolution;
 mod paste;
 mod paste_copy;
 mod paste_cut;
+mod register;
 
-pub use self::copy::CopyFiles;
-pub use self::cut::CutFiles;
-pub use self::paste::PasteFiles;
+pub use self::copy::{CopyAppend, CopyFiles};
+pub use self::cut::{CutAppend, CutFiles};
+pub use self::local_state::FileOp;
+pub use self::paste::{PasteFiles, PasteIntoPreview, PasteToTarget};
+pub use self::register::{PasteFromRegister, YankToRegister};
@@ -1,5 +1,8 @@
+use std::fs;
 use std::path;
 
+use crate::io::Options;
+
 pub fn rename_filename_conflict(path: &mut path::PathBuf) {
     let file_name = path.file_name().unwrap().to_os_string();
     for i in 0.. {
@@ -13,3 +16,26 @@ pub fn rename_filename_conflict(path: &mut path::PathBuf) {
         path.push(file_name);
     }
 }
+
+/*
+ * where src goes when pasted into dest. with overwrite it replaces an entry
+ * of the same name, otherwise it gets a name that is still free
+ */
+pub fn paste_destination(dest: &path::Path, src: &path::Path, options: &Options) -> path::PathBuf {
+    let mut dest_buf = dest.to_path_buf();
+    if let Some(s) = src.file_name() {
+        dest_buf.push(s);
+    }
+    if !options.overwrite {
+        rename_filename_conflict(&mut dest_buf);
+    }
+    dest_buf
+}
+
+// symlinks can't be created over an existing entry, so it goes first
+pub fn remove_overwritten_link(dest: &path::Path, options: &Options) -> std::io::Result<()> {
+    match fs::symlink_metadata(dest) {
+        Ok(metadata) if options.overwrite && !metadata.is_dir() => fs::remove_file(dest),
+        _ => Ok(()),
+    }
+}
@@ -1,28 +1,59 @@
-use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use std::path::PathBuf;
+
+use termion::event::Key;
+
+use crate::commands::{escape_arg, report_dry_run, JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
 use crate::io::Options;
+use crate::ui::widgets::TuiPrompt;
 use crate::ui::TuiBackend;
+use crate::util::format;
+use crate::util::unix;
 
 use super::local_state::{FileOp, LocalState};
+use super::name_resolution::paste_destination;
 use super::paste_copy::paste_copy;
 use super::paste_cut::paste_cut;
 
+#[derive(Clone)]
 pub struct PasteFiles {
     options: Options,
+    // only list where each file would go
+    dry_run: bool,
 }
 
-impl JoshutoCommand for PasteFiles {}
+impl JoshutoCommand for PasteFiles {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for PasteFiles {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(
-            f,
-            "{} overwrite={} skip_exist={}",
-            Self::command(),
-            self.options.overwrite,
-            self.options.skip_exist,
-        )
+        f.write_str(Self::command())?;
+        if self.options.overwrite {
+            f.write_str(" --overwrite")?;
+        }
+        if self.options.skip_exist {
+            f.write_str(" --skip_exist")?;
+        }
+        if self.options.dereference {
+            f.write_str(" --recursive-dereference")?;
+        }
+        if self.dry_run {
+            f.write_str(" --dry-run")?;
+        }
+        if self.options.preserve_times {
+            f.write_str(" --preserve")?;
+        }
+        if self.options.preserve_xattrs {
+            f.write_str(" --preserve-xattrs")?;
+        }
+        if let Some(destination) = self.options.destination.as_ref() {
+            write!(f, " --to {}", escape_arg(&destination.to_string_lossy()))?;
+        }
+        Ok(())
     }
 }
 
@@ -33,23 +64,241 @@ impl std::fmt::Debug for PasteFiles {
 }
 
 impl JoshutoRunnable for PasteFiles {
-    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
         let file_operation = LocalState::get_file_operation();
-        let thread = match file_operation {
-            FileOp::Copy => paste_copy(context, self.options.clone()),
-            FileOp::Cut => paste_cut(context, self.options.clone()),
+        let mut options = self.options.clone();
+        options.destination = Some(Self::destination(context, &options)?);
+        options.fsync = context.config_t.sync_after_io;
+
+        if self.dry_run {
+            Self::dry_run(context, backend, file_operation, &options)?;
+            return Ok(());
+        }
+        let summary = LocalState::get_selected_files()
+            .map(|paths| format::selection_summary(&paths))
+            .unwrap_or_default();
+        let (thread, action) = match file_operation {
+            FileOp::Copy => (paste_copy(context, options), "Copying"),
+            FileOp::Cut => (paste_cut(context, options), "Moving"),
         };
         let thread = thread?;
+        context
+            .message_queue
+            .push_back(format!("{} {}", action, summary));
         context.add_new_worker(thread);
         Ok(())
     }
 }
 
 impl PasteFiles {
-    pub fn new(options: Options) -> Self {
-        PasteFiles { options }
+    pub fn new(options: Options, dry_run: bool) -> Self {
+        PasteFiles { options, dry_run }
     }
     pub const fn command() -> &'static str {
         "paste_files"
     }
+
+    // the directory to paste into, relative paths starting from the current one
+    fn destination(context: &JoshutoContext, options: &Options) -> JoshutoResult<PathBuf> {
+        let curr_path = &context.curr_tab_ref().curr_path;
+        let destination = match options.destination.as_ref() {
+            Some(destination) => curr_path.join(destination),
+            None => curr_path.clone(),
+        };
+        if !destination.is_dir() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                format!(
+                    "{}: {} is not a directory",
+                    Self::command(),
+                    destination.to_string_lossy()
+                ),
+            ));
+        }
+        if !unix::is_writable(destination.as_path()) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOPermissionDenied,
+                format!(
+                    "{}: {} is read-only",
+                    Self::command(),
+                    destination.to_string_lossy()
+                ),
+            ));
+        }
+        Ok(destination)
+    }
+
+    // reports where each selected file would be pasted, keeping the selection
+    fn dry_run(
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+        file_operation: FileOp,
+        options: &Options,
+    ) -> std::io::Result<()> {
+        let paths = match LocalState::get_selected_files() {
+            Some(paths) if !paths.is_empty() => paths,
+            _ => {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    "no files selected",
+                ))
+            }
+        };
+        let action = match file_operation {
+            FileOp::Copy => "copy",
+            FileOp::Cut => "move",
+        };
+        let dest = match options.destination.as_ref() {
+            Some(destination) => destination.clone(),
+            None => context.curr_tab_ref().curr_path.clone(),
+        };
+        let lines: Vec<String> = paths
+            .iter()
+            .map(|path| {
+                let dest_buf = paste_destination(dest.as_path(), path.as_path(), options);
+                format!(
+                    "Would {} {} -> {}",
+                    action,
+                    path.to_string_lossy(),
+                    dest_buf.to_string_lossy()
+                )
+            })
+            .collect();
+        report_dry_run(&lines, context, backend);
+        Ok(())
+    }
+}
+
+/*
+ * pastes into the directory under the cursor, the one the right pane
+ * previews, without having to enter it first
+ */
+#[derive(Clone, Debug)]
+pub struct PasteIntoPreview;
+
+impl PasteIntoPreview {
+    pub fn new() -> Self {
+        PasteIntoPreview
+    }
+    pub const fn command() -> &'static str {
+        "paste_into_preview"
+    }
+}
+
+impl JoshutoCommand for PasteIntoPreview {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for PasteIntoPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for PasteIntoPreview {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let destination = match context
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|list| list.get_curr_ref())
+        {
+            Some(entry) if entry.file_path().is_dir() => entry.file_path().clone(),
+            _ => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: The cursor is not on a directory", Self::command()),
+                ))
+            }
+        };
+        let paths = match LocalState::get_selected_files() {
+            Some(paths) if !paths.is_empty() => paths,
+            _ => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: No files to paste", Self::command()),
+                ))
+            }
+        };
+
+        if let Some(path) = paths.iter().find(|p| destination.starts_with(p)) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot paste {} into itself",
+                    Self::command(),
+                    path.to_string_lossy()
+                ),
+            ));
+        }
+
+        let ch = {
+            let action = match LocalState::get_file_operation() {
+                FileOp::Copy => "Copy",
+                FileOp::Cut => "Move",
+            };
+            let prompt_str = format!(
+                "{} {} into {}? (y/N)",
+                action,
+                format::selection_summary(&paths),
+                destination.to_string_lossy()
+            );
+            let mut prompt = TuiPrompt::new(&prompt_str);
+            prompt.get_key(backend, &context)
+        };
+        if ch != Key::Char('y') {
+            return Ok(());
+        }
+
+        let options = Options {
+            destination: Some(destination),
+            ..Options::default()
+        };
+        PasteFiles::new(options, false).execute(context, backend)
+    }
+}
+
+// pastes into the current directory of the tab marked by set_target_tab
+#[derive(Clone, Debug)]
+pub struct PasteToTarget;
+
+impl PasteToTarget {
+    pub fn new() -> Self {
+        PasteToTarget
+    }
+    pub const fn command() -> &'static str {
+        "paste_to_target"
+    }
+}
+
+impl JoshutoCommand for PasteToTarget {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for PasteToTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for PasteToTarget {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let destination = match context.target_tab.and_then(|i| context.tabs.get(i)) {
+            Some(tab) => tab.curr_path.clone(),
+            None => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: No target tab, see set_target_tab", Self::command()),
+                ))
+            }
+        };
+        let options = Options {
+            destination: Some(destination),
+            ..Options::default()
+        };
+        PasteFiles::new(options, false).execute(context, backend)
+    }
 }
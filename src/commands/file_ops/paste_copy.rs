@@ -1,41 +1,161 @@
 use std::fs;
+use std::os::unix::fs::MetadataExt;
 use std::path::Path;
 use std::sync::mpsc;
 use std::thread;
 
+use filetime::FileTime;
+
 use crate::context::JoshutoContext;
-use crate::io::{IOWorkerThread, Options};
+use crate::io::{IOResults, IOWorkerProgress, IOWorkerThread, Options};
+use crate::util::event::Event;
+use crate::util::load_child::recursive_size;
+use crate::util::unix;
 
 use super::local_state::LocalState;
-use super::name_resolution::rename_filename_conflict;
+use super::name_resolution::{paste_destination, remove_overwritten_link};
+
+/*
+ * progress is called with the size of every file once it has been copied,
+ * xattr_failures counts the entries whose extended attributes couldn't be
+ * preserved, which doesn't stop the copy
+ */
+pub fn recursive_copy(
+    dest: &Path,
+    src: &Path,
+    options: &Options,
+    progress: &mut dyn FnMut(u64),
+    xattr_failures: &mut usize,
+) -> std::io::Result<u64> {
+    let mut ancestors = Vec::new();
+    copy_entry(dest, src, options, &mut ancestors, progress, xattr_failures)
+}
+
+/*
+ * copies every extended attribute of src onto dest. src has already been
+ * resolved to a file or directory, so symlinks are followed
+ */
+fn copy_xattrs(src: &Path, dest: &Path) -> std::io::Result<()> {
+    for name in xattr::list_deref(src)? {
+        if let Some(value) = xattr::get_deref(src, &name)? {
+            xattr::set(dest, &name, &value)?;
+        }
+    }
+    Ok(())
+}
 
-pub fn recursive_copy(dest: &Path, src: &Path, options: &Options) -> std::io::Result<u64> {
-    let mut dest_buf = dest.to_path_buf();
-    if let Some(s) = src.file_name() {
-        dest_buf.push(s);
+// sets the access and modification times of dest to those in metadata
+fn copy_times(metadata: &fs::Metadata, dest: &Path) -> std::io::Result<()> {
+    let atime = FileTime::from_last_access_time(metadata);
+    let mtime = FileTime::from_last_modification_time(metadata);
+    if metadata.file_type().is_symlink() {
+        filetime::set_symlink_file_times(dest, atime, mtime)
+    } else {
+        filetime::set_file_times(dest, atime, mtime)
     }
-    rename_filename_conflict(&mut dest_buf);
-    let file_type = fs::symlink_metadata(src)?.file_type();
+}
+
+/*
+ * ancestors holds the (st_dev, st_ino) of every directory being copied
+ * above src. inode numbers are only unique per device, so both are needed
+ * to tell if a dereferenced symlink leads back into one of them,
+ * which would otherwise recurse forever
+ */
+fn copy_entry(
+    dest: &Path,
+    src: &Path,
+    options: &Options,
+    ancestors: &mut Vec<(u64, u64)>,
+    progress: &mut dyn FnMut(u64),
+    xattr_failures: &mut usize,
+) -> std::io::Result<u64> {
+    let dest_buf = paste_destination(dest, src, options);
+    let metadata = if options.dereference {
+        fs::metadata(src)?
+    } else {
+        fs::symlink_metadata(src)?
+    };
+    let file_type = metadata.file_type();
     if file_type.is_dir() {
-        fs::create_dir(dest_buf.as_path())?;
+        let id = (metadata.dev(), metadata.ino());
+        if ancestors.contains(&id) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("symlink loop detected at {}", src.to_string_lossy()),
+            ));
+        }
+        /* overwriting merges into a directory that is already there */
+        if !(options.overwrite && dest_buf.is_dir()) {
+            fs::create_dir(dest_buf.as_path())?;
+        }
+        if options.preserve_xattrs && copy_xattrs(src, dest_buf.as_path()).is_err() {
+            *xattr_failures += 1;
+        }
+        ancestors.push(id);
         let mut total = 0;
         for entry in fs::read_dir(src)? {
             let entry = entry?;
             let entry_path = entry.path();
-            total += recursive_copy(dest_buf.as_path(), entry_path.as_path(), options)?;
+            total += copy_entry(
+                dest_buf.as_path(),
+                entry_path.as_path(),
+                options,
+                ancestors,
+                progress,
+                xattr_failures,
+            )?;
+        }
+        ancestors.pop();
+        /* only once it's filled, as adding entries changes its times */
+        if options.preserve_times {
+            copy_times(&metadata, dest_buf.as_path())?;
         }
         Ok(total)
     } else if file_type.is_file() {
-        fs::copy(src, dest_buf)
+        let copied = fs::copy(src, dest_buf.as_path())?;
+        if options.preserve_times {
+            copy_times(&metadata, dest_buf.as_path())?;
+        }
+        if options.fsync {
+            unix::fsync(dest_buf.as_path())?;
+        }
+        if options.preserve_xattrs && copy_xattrs(src, dest_buf.as_path()).is_err() {
+            *xattr_failures += 1;
+        }
+        progress(copied);
+        Ok(copied)
     } else if file_type.is_symlink() {
         let link_path = fs::read_link(src)?;
-        std::os::unix::fs::symlink(link_path, dest_buf)?;
+        remove_overwritten_link(dest_buf.as_path(), options)?;
+        std::os::unix::fs::symlink(link_path, dest_buf.as_path())?;
+        if options.preserve_times {
+            copy_times(&metadata, dest_buf.as_path())?;
+        }
         Ok(0)
     } else {
       Ok(0)
     }
 }
 
+// total size of everything to be copied
+fn total_size(paths: &[std::path::PathBuf], options: &Options) -> u64 {
+    paths
+        .iter()
+        .map(|p| {
+            let metadata = if options.dereference {
+                fs::metadata(p)
+            } else {
+                fs::symlink_metadata(p)
+            };
+            match metadata {
+                Ok(metadata) if metadata.is_dir() => recursive_size(p.as_path()),
+                Ok(metadata) => metadata.len(),
+                Err(_) => 0,
+            }
+        })
+        .sum()
+}
+
 pub fn paste_copy(
     context: &mut JoshutoContext,
     options: Options,
@@ -50,20 +170,53 @@ pub fn paste_copy(
     }
 
     let tab_dest = context.curr_tab_index;
-    let thread_dest = context.tabs[tab_dest].curr_path.clone();
+    let thread_dest = match options.destination.as_ref() {
+        Some(destination) => destination.clone(),
+        None => context.tabs[tab_dest].curr_path.clone(),
+    };
     let dest = thread_dest.clone();
-    let src = paths[0].parent().unwrap().to_path_buf();
+    let src = LocalState::parent_dirs(&paths);
+    let precount = context.config_t.paste_precount;
 
     let (tx_start, rx_start) = mpsc::channel();
     let (tx, rx) = mpsc::channel();
+    let event_tx = context.events.event_tx.clone();
 
     let handle: thread::JoinHandle<std::io::Result<u64>> =
         thread::spawn(move || match rx_start.recv() {
             Ok(_) => {
+                /* counted here, as walking large trees would hold up the ui */
+                if precount {
+                    let _ = tx.send(IOWorkerProgress::Total(total_size(&paths, &options)));
+                }
                 let mut total = 0;
+                let mut progress = |copied: u64| {
+                    total += copied;
+                    let _ = tx.send(IOWorkerProgress::Processed(total));
+                };
+                let mut xattr_failures = 0;
+                /* a failed entry doesn't stop the rest from being copied */
+                let mut results = IOResults::new("copy");
                 for path in paths {
-                    total += recursive_copy(thread_dest.as_path(), path.as_path(), &options)?;
-                    tx.send(total);
+                    let res = recursive_copy(
+                        thread_dest.as_path(),
+                        path.as_path(),
+                        &options,
+                        &mut progress,
+                        &mut xattr_failures,
+                    );
+                    results.push(path.as_path(), res);
+                }
+                if options.fsync {
+                    let _ = unix::fsync(thread_dest.as_path());
+                }
+                let _ = event_tx.send(Event::IOWorkerResults(results));
+                if xattr_failures > 0 {
+                    let msg = format!(
+                        "Could not preserve extended attributes of {} entries",
+                        xattr_failures
+                    );
+                    let _ = event_tx.send(Event::IOWorkerWarning(msg));
                 }
                 Ok(total)
             }
@@ -80,3 +233,161 @@ pub fn paste_copy(
 
     Ok(thread)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir::TempDir;
+
+    fn mtime(path: &Path) -> FileTime {
+        FileTime::from_last_modification_time(&fs::symlink_metadata(path).unwrap())
+    }
+
+    fn copy(dest: &Path, src: &Path, preserve_times: bool) {
+        let options = Options {
+            preserve_times,
+            ..Options::default()
+        };
+        let mut xattr_failures = 0;
+        recursive_copy(dest, src, &options, &mut |_| {}, &mut xattr_failures).unwrap();
+    }
+
+    fn copy_tree(dest: &Path, src: &Path, dereference: bool) -> std::io::Result<u64> {
+        let options = Options {
+            dereference,
+            ..Options::default()
+        };
+        let mut xattr_failures = 0;
+        recursive_copy(dest, src, &options, &mut |_| {}, &mut xattr_failures)
+    }
+
+    // a directory holding a file and a relative symlink to it
+    fn tree_with_symlink(src: &TempDir) -> std::path::PathBuf {
+        let dir = src.mkdir("dir");
+        fs::write(dir.join("file"), "data").unwrap();
+        std::os::unix::fs::symlink("file", dir.join("link")).unwrap();
+        dir
+    }
+
+    #[test]
+    fn preserve_keeps_modification_times() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let dir = src.mkdir("dir");
+        let file = src.touch("dir/file");
+        let past = FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_mtime(&file, past).unwrap();
+        filetime::set_file_mtime(&dir, past).unwrap();
+
+        copy(dest.path(), dir.as_path(), true);
+        assert_eq!(mtime(&dest.path().join("dir")), past);
+        assert_eq!(mtime(&dest.path().join("dir/file")), past);
+    }
+
+    #[test]
+    fn copies_get_new_times_without_preserve() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let file = src.touch("file");
+        let past = FileTime::from_unix_time(1_000_000_000, 0);
+        filetime::set_file_mtime(&file, past).unwrap();
+
+        copy(dest.path(), file.as_path(), false);
+        assert_ne!(mtime(&dest.path().join("file")), past);
+    }
+
+    #[test]
+    fn symlinks_in_tree_are_copied_as_links() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let dir = tree_with_symlink(&src);
+
+        copy_tree(dest.path(), dir.as_path(), false).unwrap();
+        let link = dest.path().join("dir/link");
+        let file_type = fs::symlink_metadata(&link).unwrap().file_type();
+        assert!(file_type.is_symlink());
+        assert_eq!(fs::read_link(&link).unwrap(), Path::new("file"));
+    }
+
+    #[test]
+    fn dereference_copies_what_symlinks_point_to() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let dir = tree_with_symlink(&src);
+
+        copy_tree(dest.path(), dir.as_path(), true).unwrap();
+        let link = dest.path().join("dir/link");
+        let file_type = fs::symlink_metadata(&link).unwrap().file_type();
+        assert!(file_type.is_file());
+        assert_eq!(fs::read_to_string(&link).unwrap(), "data");
+    }
+
+    #[test]
+    fn dereference_stops_at_symlink_loops() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let dir = src.mkdir("dir");
+        std::os::unix::fs::symlink(".", dir.join("loop")).unwrap();
+
+        assert!(copy_tree(dest.path(), dir.as_path(), true).is_err());
+        /* without dereferencing the loop is just another link */
+        copy_tree(dest.path(), dir.as_path(), false).unwrap();
+    }
+
+    #[test]
+    fn preserve_xattrs_copies_extended_attributes() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let file = src.touch("file");
+        /* tmpfs and some other filesystems don't take user attributes */
+        if !xattr::SUPPORTED_PLATFORM || xattr::set(&file, "user.joshuto", b"value").is_err() {
+            return;
+        }
+
+        let options = Options {
+            preserve_xattrs: true,
+            ..Options::default()
+        };
+        let mut xattr_failures = 0;
+        recursive_copy(
+            dest.path(),
+            &file,
+            &options,
+            &mut |_| {},
+            &mut xattr_failures,
+        )
+        .unwrap();
+        assert_eq!(xattr_failures, 0);
+        let value = xattr::get(dest.path().join("file"), "user.joshuto").unwrap();
+        assert_eq!(value, Some(b"value".to_vec()));
+    }
+
+    #[test]
+    fn overwrite_replaces_instead_of_renaming() {
+        let src = TempDir::new();
+        let dest = TempDir::new();
+        let dir = src.mkdir("dir");
+        fs::write(dir.join("file"), "new").unwrap();
+        dest.mkdir("dir");
+        fs::write(dest.path().join("dir/file"), "old").unwrap();
+        fs::write(dest.path().join("dir/kept"), "kept").unwrap();
+
+        let options = Options {
+            overwrite: true,
+            ..Options::default()
+        };
+        let mut xattr_failures = 0;
+        recursive_copy(
+            dest.path(),
+            &dir,
+            &options,
+            &mut |_| {},
+            &mut xattr_failures,
+        )
+        .unwrap();
+        let copied = dest.path().join("dir");
+        assert_eq!(fs::read_to_string(copied.join("file")).unwrap(), "new");
+        assert_eq!(fs::read_to_string(copied.join("kept")).unwrap(), "kept");
+        assert!(!dest.path().join("dir_0").exists());
+    }
+}
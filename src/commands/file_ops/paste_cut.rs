@@ -4,17 +4,15 @@ use std::sync::mpsc;
 use std::thread;
 
 use crate::context::JoshutoContext;
-use crate::io::{IOWorkerThread, Options};
+use crate::io::{IOResults, IOWorkerProgress, IOWorkerThread, Options};
+use crate::util::event::Event;
+use crate::util::unix;
 
 use super::local_state::LocalState;
-use super::name_resolution::rename_filename_conflict;
+use super::name_resolution::{paste_destination, remove_overwritten_link};
 
 pub fn recursive_cut(dest: &Path, src: &Path, options: &Options) -> std::io::Result<u64> {
-    let mut dest_buf = dest.to_path_buf();
-    if let Some(s) = src.file_name() {
-        dest_buf.push(s);
-    }
-    rename_filename_conflict(&mut dest_buf);
+    let dest_buf = paste_destination(dest, src, options);
     let metadata = fs::symlink_metadata(src)?;
     let file_type = metadata.file_type();
     if file_type.is_dir() {
@@ -22,7 +20,10 @@ pub fn recursive_cut(dest: &Path, src: &Path, options: &Options) -> std::io::Res
             Ok(_) => Ok(metadata.len()),
             Err(_) => {
                 let mut total = 0;
-                fs::create_dir(dest_buf.as_path())?;
+                /* overwriting merges into a directory that is already there */
+                if !(options.overwrite && dest_buf.is_dir()) {
+                    fs::create_dir(dest_buf.as_path())?;
+                }
                 for entry in fs::read_dir(src)? {
                     let entry = entry?;
                     let entry_path = entry.path();
@@ -35,11 +36,16 @@ pub fn recursive_cut(dest: &Path, src: &Path, options: &Options) -> std::io::Res
     } else if file_type.is_file() {
         if fs::rename(src, dest_buf.as_path()).is_err() {
             fs::copy(src, dest_buf.as_path())?;
+            /* the copy has to be on disk before the original goes */
+            if options.fsync {
+                unix::fsync(dest_buf.as_path())?;
+            }
             fs::remove_file(src)?;
         }
         Ok(metadata.len())
     } else if file_type.is_symlink() {
         let link_path = fs::read_link(src)?;
+        remove_overwritten_link(dest_buf.as_path(), options)?;
         std::os::unix::fs::symlink(link_path, dest_buf)?;
         fs::remove_file(src)?;
         Ok(metadata.len())
@@ -62,21 +68,34 @@ pub fn paste_cut(
     }
 
     let tab_dest = context.curr_tab_index;
-    let thread_dest = context.tabs[tab_dest].curr_path.clone();
+    let thread_dest = match options.destination.as_ref() {
+        Some(destination) => destination.clone(),
+        None => context.tabs[tab_dest].curr_path.clone(),
+    };
     let dest = thread_dest.clone();
-    let src = paths[0].parent().unwrap().to_path_buf();
+    let src = LocalState::parent_dirs(&paths);
 
     let (tx_start, rx_start) = mpsc::channel();
     let (tx, rx) = mpsc::channel();
+    let event_tx = context.events.event_tx.clone();
 
     let handle: thread::JoinHandle<std::io::Result<u64>> =
         thread::spawn(move || match rx_start.recv() {
             Ok(_) => {
                 let mut total = 0;
+                /* a failed entry doesn't stop the rest from being moved */
+                let mut results = IOResults::new("move");
                 for path in paths {
-                    total += recursive_cut(thread_dest.as_path(), path.as_path(), &options)?;
-                    tx.send(total);
+                    let res = recursive_cut(thread_dest.as_path(), path.as_path(), &options);
+                    if let Some(moved) = results.push(path.as_path(), res) {
+                        total += moved;
+                        let _ = tx.send(IOWorkerProgress::Processed(total));
+                    }
+                }
+                if options.fsync {
+                    let _ = unix::fsync(thread_dest.as_path());
                 }
+                let _ = event_tx.send(Event::IOWorkerResults(results));
                 Ok(total)
             }
             Err(_) => Ok(0),
@@ -0,0 +1,121 @@
+use crate::commands::{escape_arg, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::io::Options;
+use crate::ui::TuiBackend;
+
+use super::local_state::{FileOp, LocalState};
+use super::paste::PasteFiles;
+
+/*
+ * keeps the selected files in a named register, apart from the clipboard
+ * copy_files and cut_files fill, to be pasted later by paste_from_register
+ */
+#[derive(Clone, Debug)]
+pub struct YankToRegister {
+    name: String,
+    operation: FileOp,
+}
+
+impl YankToRegister {
+    pub fn new(name: String, operation: FileOp) -> Self {
+        YankToRegister { name, operation }
+    }
+    pub const fn command() -> &'static str {
+        "yank_to_register"
+    }
+}
+
+impl JoshutoCommand for YankToRegister {}
+
+impl std::fmt::Display for YankToRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), escape_arg(&self.name))?;
+        if self.operation == FileOp::Cut {
+            f.write_str(" --cut")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for YankToRegister {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<_> = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => list.get_selected_paths().into_iter().cloned().collect(),
+            None => return Ok(()),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: No files selected", Self::command()),
+            ));
+        }
+        let msg = format!("{} files in register {}", paths.len(), self.name);
+        context
+            .registers
+            .insert(self.name.clone(), (self.operation.clone(), paths));
+        context.message_queue.push_back(msg);
+        Ok(())
+    }
+}
+
+/*
+ * pastes the files of a register into the current directory, leaving the
+ * clipboard as it was. registers of cut files are emptied by pasting them
+ */
+#[derive(Clone, Debug)]
+pub struct PasteFromRegister {
+    name: String,
+}
+
+impl PasteFromRegister {
+    pub fn new(name: String) -> Self {
+        PasteFromRegister { name }
+    }
+    pub const fn command() -> &'static str {
+        "paste_from_register"
+    }
+}
+
+impl JoshutoCommand for PasteFromRegister {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for PasteFromRegister {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), escape_arg(&self.name))
+    }
+}
+
+impl JoshutoRunnable for PasteFromRegister {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let (operation, paths) = match context.registers.get(&self.name) {
+            Some(register) => register.clone(),
+            None => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Register {} is empty", Self::command(), self.name),
+                ))
+            }
+        };
+
+        /* the paste takes its files from the clipboard when it starts */
+        let clipboard = (
+            LocalState::get_selected_files(),
+            LocalState::get_file_operation(),
+        );
+        LocalState::set_selected_files(Some(paths));
+        LocalState::set_file_op(operation.clone());
+        let res = PasteFiles::new(Options::default(), false).execute(context, backend);
+        LocalState::set_selected_files(clipboard.0);
+        LocalState::set_file_op(clipboard.1);
+        res?;
+
+        if operation == FileOp::Cut {
+            context.registers.remove(&self.name);
+        }
+        Ok(())
+    }
+}
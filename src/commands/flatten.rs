@@ -0,0 +1,98 @@
+use std::thread;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::fs::JoshutoDirList;
+use crate::ui::TuiBackend;
+use crate::util::event::Event;
+use crate::util::load_child::LoadChild;
+
+/*
+ * lists the files below the current directory in place of its entries,
+ * named by their relative paths so commands work across the whole subtree.
+ * depth limits how many levels of subdirectories are descended into
+ */
+#[derive(Clone, Debug)]
+pub struct Flatten {
+    depth: Option<usize>,
+}
+
+impl Flatten {
+    pub fn new(depth: Option<usize>) -> Self {
+        Flatten { depth }
+    }
+    pub const fn command() -> &'static str {
+        "flatten"
+    }
+}
+
+impl JoshutoCommand for Flatten {}
+
+impl std::fmt::Display for Flatten {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.depth {
+            Some(depth) => write!(f, "{} {}", Self::command(), depth),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl JoshutoRunnable for Flatten {
+    /* large trees take a while, so they are read on a separate thread */
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let flatten_depth = Some(self.depth.unwrap_or(usize::MAX));
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        match context.tabs[context.curr_tab_index].curr_list_mut() {
+            Some(curr_list) => curr_list.flatten_depth = flatten_depth,
+            None => return Ok(()),
+        }
+
+        let event_tx = context.events.event_tx.clone();
+        let sort_option = context.config_t.sort_option.clone();
+        let path = curr_path.clone();
+        thread::spawn(move || {
+            let contents = JoshutoDirList::read(path.as_path(), flatten_depth, &sort_option);
+            let _ = event_tx.send(Event::DirReload(path, flatten_depth, contents));
+        });
+        context
+            .message_queue
+            .push_back(format!("Flattening {}...", curr_path.to_string_lossy()));
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Unflatten;
+
+impl Unflatten {
+    pub fn new() -> Self {
+        Unflatten
+    }
+    pub const fn command() -> &'static str {
+        "unflatten"
+    }
+}
+
+impl JoshutoCommand for Unflatten {}
+
+impl std::fmt::Display for Unflatten {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for Unflatten {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let sort_option = &context.config_t.sort_option;
+        if let Some(curr_list) = context.tabs[context.curr_tab_index].curr_list_mut() {
+            if curr_list.flatten_depth.is_none() {
+                return Ok(());
+            }
+            curr_list.flatten_depth = None;
+            curr_list.reload_contents(sort_option)?;
+        }
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+#[derive(Clone, Debug)]
+pub struct ToggleFollowNewest;
+
+impl ToggleFollowNewest {
+    pub fn new() -> Self {
+        ToggleFollowNewest
+    }
+    pub const fn command() -> &'static str {
+        "toggle_follow_newest"
+    }
+}
+
+impl JoshutoCommand for ToggleFollowNewest {}
+
+impl std::fmt::Display for ToggleFollowNewest {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for ToggleFollowNewest {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let follow_newest = !context.config_t.follow_newest;
+        context.config_t.follow_newest = follow_newest;
+
+        let msg = if follow_newest {
+            if let Some(curr_list) = context.curr_tab_mut().curr_list_mut() {
+                curr_list.select_newest();
+            }
+            LoadChild::load_child(context)?;
+            "Following newest entry"
+        } else {
+            "Stopped following newest entry"
+        };
+        context.message_queue.push_back(msg.to_string());
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+use lazy_static::lazy_static;
+
+use std::io::{BufRead, BufReader};
+use std::path;
+use std::process;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use crate::commands::{cursor_move, escape_arg, ChangeDirectory, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::widgets::{TuiMenu, TuiTextField};
+use crate::ui::TuiBackend;
+use crate::util::event::Event;
+use crate::util::load_child::LoadChild;
+
+const ENV_EDITOR: &str = "EDITOR";
+// more matches than this are cut off, a menu cannot show them anyway
+const MAX_MATCHES: usize = 500;
+
+lazy_static! {
+    // the search that is running, if any
+    static ref GREP_PROCESS: Mutex<Option<process::Child>> = Mutex::new(None);
+}
+
+#[derive(Clone, Debug)]
+pub struct GrepMatch {
+    pub path: path::PathBuf,
+    pub line: usize,
+    // only known when ripgrep did the search
+    pub column: Option<usize>,
+    pub text: String,
+}
+
+#[derive(Debug)]
+pub struct GrepResults {
+    pub pattern: String,
+    pub edit: bool,
+    pub matches: std::io::Result<Vec<GrepMatch>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Grep {
+    pattern: Option<String>,
+    edit: bool,
+}
+
+impl Grep {
+    pub fn new(pattern: Option<String>, edit: bool) -> Self {
+        Grep { pattern, edit }
+    }
+    pub const fn command() -> &'static str {
+        "grep"
+    }
+
+    /*
+     * ripgrep if it is installed, grep otherwise.
+     * also returns whether the output has columns, which only ripgrep gives
+     */
+    fn search_command(pattern: &str, dir: &path::Path) -> (process::Command, bool) {
+        let rg_available = process::Command::new("rg")
+            .arg("--version")
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null())
+            .status()
+            .is_ok();
+
+        let mut command = if rg_available {
+            let mut command = process::Command::new("rg");
+            command.args(&[
+                "--line-number",
+                "--column",
+                "--no-heading",
+                "--null",
+                "--color",
+                "never",
+            ]);
+            command
+        } else {
+            let mut command = process::Command::new("grep");
+            command.args(&["-rnIZ"]);
+            command
+        };
+        command
+            .arg("--")
+            .arg(pattern)
+            .arg(".")
+            .current_dir(dir)
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::piped())
+            .stderr(process::Stdio::null());
+        (command, rg_available)
+    }
+
+    /*
+     * path\0line:text as printed by grep, path\0line:column:text by rg.
+     * the path ends at a NUL as it may contain colons itself
+     */
+    fn parse_match(dir: &path::Path, line: &str, with_column: bool) -> Option<GrepMatch> {
+        let mut parts = line.splitn(2, '\0');
+        let path = parts.next()?;
+        let mut fields = parts.next()?.splitn(if with_column { 3 } else { 2 }, ':');
+        let line = fields.next()?.parse::<usize>().ok()?;
+        let column = if with_column {
+            Some(fields.next()?.parse::<usize>().ok()?)
+        } else {
+            None
+        };
+        let text = fields.next()?.trim().to_string();
+        Some(GrepMatch {
+            path: dir.join(path.trim_start_matches("./")),
+            line,
+            column,
+            text,
+        })
+    }
+
+    /*
+     * the arguments that open the file of m at its position in program,
+     * going by the program's name. unknown editors only get the file
+     */
+    fn editor_args(program: &str, m: &GrepMatch, context: &JoshutoContext) -> Vec<String> {
+        let file = m.path.to_string_lossy().to_string();
+        let name = path::Path::new(program)
+            .file_name()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let template = match context.config_t.editor_positions.get(&name) {
+            Some(template) => template,
+            None => return vec![file],
+        };
+        let mut args: Vec<String> = template
+            .iter()
+            .map(|arg| {
+                arg.replace("{file}", &file)
+                    .replace("{line}", &m.line.to_string())
+                    .replace("{column}", &m.column.unwrap_or(1).to_string())
+            })
+            .collect();
+        if !template.iter().any(|arg| arg.contains("{file}")) {
+            args.push(file);
+        }
+        args
+    }
+
+    pub fn spawn_search(
+        pattern: String,
+        edit: bool,
+        dir: path::PathBuf,
+        results_slot: Arc<Mutex<Option<GrepResults>>>,
+        event_tx: mpsc::Sender<Event>,
+    ) -> std::io::Result<()> {
+        Self::cancel();
+
+        let (mut command, with_column) = Self::search_command(pattern.as_str(), dir.as_path());
+        let mut child = command.spawn()?;
+        let pid = child.id();
+        let stdout = child.stdout.take();
+        *GREP_PROCESS.lock().unwrap() = Some(child);
+
+        thread::spawn(move || {
+            let matches: Vec<GrepMatch> = match stdout {
+                Some(stdout) => BufReader::new(stdout)
+                    .lines()
+                    .filter_map(|l| l.ok())
+                    .filter_map(|l| Self::parse_match(dir.as_path(), l.as_str(), with_column))
+                    .take(MAX_MATCHES)
+                    .collect(),
+                None => Vec::new(),
+            };
+
+            /* a missing process means the search was cancelled or replaced */
+            let child = {
+                let mut process = GREP_PROCESS.lock().unwrap();
+                match process.as_ref() {
+                    Some(c) if c.id() == pid => process.take(),
+                    _ => None,
+                }
+            };
+            if let Some(mut child) = child {
+                let _ = child.kill();
+                let _ = child.wait();
+                let results = GrepResults {
+                    pattern,
+                    edit,
+                    matches: Ok(matches),
+                };
+                *results_slot.lock().unwrap() = Some(results);
+                let _ = event_tx.send(Event::GrepDone);
+            }
+        });
+        Ok(())
+    }
+
+    // stops the running search, returns whether there was one
+    pub fn cancel() -> bool {
+        match GREP_PROCESS.lock().unwrap().take() {
+            Some(mut child) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn show_results(
+        results: GrepResults,
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<()> {
+        const PROMPT: &str = "grep ";
+
+        let matches = results.matches?;
+        if matches.is_empty() {
+            context.message_queue.push_back(format!(
+                "{}: No matches for {}",
+                Self::command(),
+                results.pattern
+            ));
+            return Ok(());
+        }
+
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let user_input: Option<String> = {
+            let menu_options: Vec<String> = matches
+                .iter()
+                .enumerate()
+                .map(|(i, m)| {
+                    let path = m.path.strip_prefix(&curr_path).unwrap_or(m.path.as_path());
+                    format!(
+                        "  {} | {}:{}: {}",
+                        i + 1,
+                        path.to_string_lossy(),
+                        m.line,
+                        m.text
+                    )
+                })
+                .collect();
+            let menu_options_str: Vec<&str> = menu_options.iter().map(|e| e.as_str()).collect();
+            let menu_widget = TuiMenu::new(&menu_options_str);
+
+            let mut textfield = TuiTextField::default()
+                .prompt(":")
+                .prefix(PROMPT)
+                .menu(menu_widget);
+            textfield.get_input(backend, &context)
+        };
+
+        match user_input.as_ref() {
+            Some(user_input) if user_input.starts_with(PROMPT) => {
+                let user_input = user_input[PROMPT.len()..].trim();
+                match user_input.parse::<usize>() {
+                    Ok(n) if n >= 1 && n <= matches.len() => {
+                        Self::goto_match(&matches[n - 1], results.edit, context, backend)
+                    }
+                    Ok(n) => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!("{}: no match numbered {}", Self::command(), n),
+                    )),
+                    Err(_) if user_input.is_empty() => Ok(()),
+                    Err(e) => Err(JoshutoError::new(
+                        JoshutoErrorKind::ParseError,
+                        format!("{}: {}", Self::command(), e),
+                    )),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn goto_match(
+        m: &GrepMatch,
+        edit: bool,
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<()> {
+        let parent = match m.path.parent() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        ChangeDirectory::change_directories(parent, context)?;
+        let index = context
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|l| l.contents.iter().position(|e| e.file_path() == &m.path));
+        if let Some(index) = index {
+            cursor_move::cursor_move(index, context);
+        }
+        LoadChild::load_child(context)?;
+
+        if edit {
+            let editor = match std::env::var(ENV_EDITOR) {
+                Ok(s) => s,
+                Err(_) => {
+                    return Err(JoshutoError::new(
+                        JoshutoErrorKind::EnvVarNotPresent,
+                        format!("{} environment variable not set", ENV_EDITOR),
+                    ));
+                }
+            };
+            /* $EDITOR may carry arguments of its own, like code --wait */
+            let mut words = editor.split_whitespace();
+            let program = words.next().unwrap_or_default();
+            let args = Self::editor_args(program, m, context);
+            backend.terminal_drop();
+            let res = process::Command::new(program)
+                .args(words)
+                .args(args)
+                .status();
+            backend.terminal_restore()?;
+            res?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for Grep {}
+
+impl std::fmt::Display for Grep {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.edit {
+            f.write_str(" --edit")?;
+        }
+        if let Some(pattern) = self.pattern.as_ref() {
+            write!(f, " {}", escape_arg(pattern))?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for Grep {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        const PROMPT: &str = "grep ";
+
+        let pattern = match self.pattern.as_ref() {
+            Some(s) => s.clone(),
+            None => {
+                let mut textfield = TuiTextField::default().prompt(":").prefix(PROMPT);
+                match textfield.get_input(backend, &context) {
+                    Some(s) if s.starts_with(PROMPT) && !s[PROMPT.len()..].is_empty() => {
+                        s[PROMPT.len()..].to_string()
+                    }
+                    _ => return Ok(()),
+                }
+            }
+        };
+
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let results_slot = context.grep_results.clone();
+        let event_tx = context.events.event_tx.clone();
+        Self::spawn_search(
+            pattern.clone(),
+            self.edit,
+            curr_path,
+            results_slot,
+            event_tx,
+        )?;
+        context
+            .message_queue
+            .push_back(format!("{}: Searching for {}", Self::command(), pattern));
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GrepCancel;
+
+impl GrepCancel {
+    pub fn new() -> Self {
+        GrepCancel
+    }
+    pub const fn command() -> &'static str {
+        "grep"
+    }
+}
+
+impl JoshutoCommand for GrepCancel {}
+
+impl std::fmt::Display for GrepCancel {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} --cancel", Self::command())
+    }
+}
+
+impl JoshutoRunnable for GrepCancel {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let msg = if Grep::cancel() {
+            "grep: Search cancelled"
+        } else {
+            "grep: No search running"
+        };
+        context.message_queue.push_back(msg.to_string());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_paths_holding_colons() {
+        let dir = path::Path::new("/tmp");
+        let m = Grep::parse_match(dir, "./a:b.txt\u{0}12:3:let x: u8", true).unwrap();
+        assert_eq!(m.path, dir.join("a:b.txt"));
+        assert_eq!(m.line, 12);
+        assert_eq!(m.column, Some(3));
+        assert_eq!(m.text, "let x: u8");
+
+        let m = Grep::parse_match(dir, "./a:b.txt\u{0}12:let x: u8", false).unwrap();
+        assert_eq!(m.path, dir.join("a:b.txt"));
+        assert_eq!(m.column, None);
+        assert_eq!(m.text, "let x: u8");
+    }
+}
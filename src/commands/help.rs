@@ -0,0 +1,331 @@
+use termion::event::Key;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::{Paragraph, Text};
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::widgets::{TuiMenu, TuiView};
+use crate::ui::TuiBackend;
+use crate::util::event::Event;
+
+// every built-in command and what it does, in the order help lists them
+pub const COMMANDS: &[(&str, &str)] = &[
+    ("bulk_rename", "rename the selected files in $EDITOR"),
+    (
+        "calculate_size",
+        "add up the size of the selected directories",
+    ),
+    (
+        "cd",
+        "change to a directory or glob match, home if none is given",
+    ),
+    (
+        "cd_interactive",
+        "change directory, completing as it's typed",
+    ),
+    ("chmod", "change permissions of the selected files"),
+    ("chown", "change the owner of the selected files"),
+    (
+        "clear_open_with",
+        "forget programs remembered by open_file_with",
+    ),
+    ("close_tab", "close the current tab"),
+    (
+        "console",
+        "open the command line, prefilled with the arguments",
+    ),
+    (
+        "copy_append",
+        "add the selected files to those to be copied",
+    ),
+    ("copy_files", "mark the selected files to be copied"),
+    ("cursor_move_down", "move the cursor down"),
+    ("cursor_move_end", "move the cursor to the last entry"),
+    (
+        "cursor_move_half_page_down",
+        "move the cursor down half a page",
+    ),
+    ("cursor_move_half_page_up", "move the cursor up half a page"),
+    ("cursor_move_home", "move the cursor to the first entry"),
+    ("cursor_move_page_down", "move the cursor down a page"),
+    ("cursor_move_page_up", "move the cursor up a page"),
+    ("cursor_move_up", "move the cursor up"),
+    ("cut_append", "add the selected files to those to be moved"),
+    ("cut_files", "mark the selected files to be moved"),
+    ("delete_files", "delete the selected files"),
+    ("delete_pattern", "delete the entries matching a glob"),
+    ("diff", "compare the two selected entries"),
+    ("duplicate_tab", "open a new tab in the current directory"),
+    (
+        "flatten",
+        "list the files below the current directory, to a depth",
+    ),
+    ("force_quit", "quit even if io workers are running"),
+    ("goto_index", "move the cursor to an entry by its number"),
+    ("grep", "search file contents below the current directory"),
+    ("help", "list commands and their keybindings"),
+    ("jump", "change to a frequently visited directory"),
+    ("jump_ancestor", "change to a parent directory"),
+    (
+        "mark_files",
+        "mark the entry under the cursor, or every one, apart from the selection",
+    ),
+    ("mkdir", "create a directory"),
+    ("mkdir_and_enter", "create a directory and change to it"),
+    ("new_tab", "open a new tab"),
+    ("open_file", "open the entry under the cursor"),
+    (
+        "open_file_with",
+        "open the selected files with a program, or choose one",
+    ),
+    (
+        "open_in_gui",
+        "open the current directory in a graphical file manager",
+    ),
+    ("open_index", "open an entry by its number"),
+    (
+        "open_parent_in_tab",
+        "open the parent directory in a new tab",
+    ),
+    ("page", "view the file under the cursor in $PAGER"),
+    ("paste_files", "paste copied or cut files"),
+    ("paste_from_register", "paste the files kept in a register"),
+    (
+        "paste_into_preview",
+        "paste into the directory under the cursor",
+    ),
+    (
+        "paste_to_target",
+        "paste into the directory of the target tab",
+    ),
+    (
+        "preview_cursor_move_down",
+        "move the cursor of the previewed directory down",
+    ),
+    (
+        "preview_cursor_move_up",
+        "move the cursor of the previewed directory up",
+    ),
+    ("quit", "quit"),
+    ("reload_all", "reload every tab"),
+    ("reload_dir_list", "reload the current directory"),
+    ("rename", "rename the entry under the cursor"),
+    (
+        "rename_append",
+        "rename before the extension, or add text there on every selected name",
+    ),
+    (
+        "rename_clean",
+        "replace whitespace in the selected file names",
+    ),
+    (
+        "rename_prepend",
+        "rename from the start, or add text there on every selected name",
+    ),
+    ("repeat", "run the last command again"),
+    (
+        "reveal",
+        "go to the directory of a file and put the cursor on it",
+    ),
+    (
+        "scroll_pane_down",
+        "scroll the parent or child listing down, keeping the cursor",
+    ),
+    (
+        "scroll_pane_up",
+        "scroll the parent or child listing up, keeping the cursor",
+    ),
+    ("search", "move to the next entry containing a pattern"),
+    ("search_next", "move to the next search match"),
+    ("search_prev", "move to the previous search match"),
+    ("select_by_regex", "select entries matching a regex"),
+    ("select_clear", "deselect every entry"),
+    (
+        "select_files",
+        "select the entry under the cursor, or every one",
+    ),
+    (
+        "select_same_extension",
+        "select entries with the extension of the one under the cursor",
+    ),
+    (
+        "select_toggle_hidden",
+        "flip the selection of hidden entries only",
+    ),
+    ("select_type", "select entries of a kind"),
+    ("set_mode", "change permissions interactively"),
+    (
+        "set_target_tab",
+        "make the current tab the paste target, or unset it",
+    ),
+    (
+        "set_view_columns",
+        "choose what the left and right panes show",
+    ),
+    ("shell", "run a shell command"),
+    ("sort", "change how entries are sorted"),
+    ("split", "open a shell in a new terminal pane"),
+    ("sync", "flush pending writes to disk"),
+    (
+        "tab_switch",
+        "switch N tabs away, or to tab N with --index (-1 is the last)",
+    ),
+    ("tab_switch_next", "switch to the next tab, wrapping around"),
+    (
+        "tab_switch_prev",
+        "switch to the previous tab, wrapping around",
+    ),
+    (
+        "toggle_extension_column",
+        "show extensions in their own column",
+    ),
+    (
+        "toggle_follow_newest",
+        "keep the cursor on the newest entry",
+    ),
+    ("toggle_hex_preview", "preview binary files as a hex dump"),
+    ("toggle_hidden", "show or hide hidden files"),
+    ("toggle_line_numbers", "show or hide line numbers"),
+    (
+        "touch_time",
+        "set the modification time of the selected files",
+    ),
+    ("unflatten", "list the current directory normally again"),
+    (
+        "yank_contents",
+        "copy the contents of a file to the clipboard",
+    ),
+    ("yank_path_to_tab", "copy the relative path to another tab"),
+    (
+        "yank_to_register",
+        "keep the selected files in a register, --cut to move them",
+    ),
+];
+
+#[derive(Clone, Debug)]
+pub struct Help;
+
+impl Help {
+    pub fn new() -> Self {
+        Help
+    }
+    pub const fn command() -> &'static str {
+        "help"
+    }
+
+    // one line per command, with every key sequence bound to it
+    fn lines(context: &JoshutoContext) -> Vec<String> {
+        COMMANDS
+            .iter()
+            .map(|(name, description)| {
+                let keys: Vec<&str> = context
+                    .key_bindings
+                    .iter()
+                    .filter(|(_, command)| command.split_whitespace().next() == Some(*name))
+                    .map(|(keys, _)| keys.as_str())
+                    .collect();
+                format!("  {:<28}{:<16}{}", name, keys.join(", "), description)
+            })
+            .collect()
+    }
+
+    /*
+     * shows lines above a prompt until escape or enter is pressed.
+     * typing narrows them down to the ones containing the input,
+     * the arrow and page keys scroll
+     */
+    fn show(
+        lines: &[String],
+        context: &JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> std::io::Result<()> {
+        let mut filter = String::new();
+        let mut offset = 0;
+
+        let terminal = backend.terminal_mut();
+        context.events.flush();
+        loop {
+            let needle = filter.to_lowercase();
+            let shown: Vec<&str> = lines
+                .iter()
+                .filter(|l| l.to_lowercase().contains(needle.as_str()))
+                .map(|l| l.as_str())
+                .collect();
+            let f_size = terminal.size()?;
+            let height = (f_size.height as usize).saturating_sub(2);
+            offset = offset.min(shown.len().saturating_sub(height));
+
+            terminal.draw(|mut frame| {
+                if height == 0 {
+                    return;
+                }
+                {
+                    let mut view = TuiView::new(&context);
+                    view.show_bottom_status = false;
+                    frame.render_widget(view, f_size);
+                }
+
+                let visible = &shown[offset..shown.len().min(offset + height)];
+                let menu_rect = Rect {
+                    x: 0,
+                    y: (f_size.height as usize - visible.len() - 2) as u16,
+                    width: f_size.width,
+                    height: visible.len() as u16,
+                };
+                frame.render_widget(TuiMenu::new(visible), menu_rect);
+
+                let prompt = format!("help: {}", filter);
+                let text = [Text::styled(
+                    prompt,
+                    Style::default().fg(Color::LightYellow),
+                )];
+                let prompt_rect = Rect {
+                    x: 0,
+                    y: f_size.height - 1,
+                    width: f_size.width,
+                    height: 1,
+                };
+                frame.render_widget(Paragraph::new(text.iter()), prompt_rect);
+            })?;
+
+            if let Ok(Event::Input(key)) = context.events.next() {
+                match key {
+                    Key::Esc | Key::Char('\n') => break,
+                    Key::Up => offset = offset.saturating_sub(1),
+                    Key::Down => offset += 1,
+                    Key::PageUp => offset = offset.saturating_sub(height),
+                    Key::PageDown => offset += height,
+                    Key::Backspace => {
+                        filter.pop();
+                        offset = 0;
+                    }
+                    Key::Char(c) => {
+                        filter.push(c);
+                        offset = 0;
+                    }
+                    _ => {}
+                }
+                context.events.flush();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for Help {}
+
+impl std::fmt::Display for Help {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for Help {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let lines = Self::lines(context);
+        Self::show(&lines, context, backend)?;
+        Ok(())
+    }
+}
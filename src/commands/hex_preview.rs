@@ -0,0 +1,31 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct ToggleHexPreview;
+
+impl ToggleHexPreview {
+    pub fn new() -> Self {
+        ToggleHexPreview
+    }
+    pub const fn command() -> &'static str {
+        "toggle_hex_preview"
+    }
+}
+
+impl JoshutoCommand for ToggleHexPreview {}
+
+impl std::fmt::Display for ToggleHexPreview {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for ToggleHexPreview {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        context.config_t.hex_preview = !context.config_t.hex_preview;
+        Ok(())
+    }
+}
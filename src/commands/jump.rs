@@ -0,0 +1,117 @@
+use std::path;
+
+use crate::commands::{ChangeDirectory, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::widgets::{TuiMenu, TuiTextField};
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+const LIST_LIMIT: usize = 10;
+
+#[derive(Clone, Debug)]
+pub struct Jump {
+    keywords: String,
+    list: bool,
+}
+
+impl Jump {
+    pub fn new(keywords: String, list: bool) -> Self {
+        Jump { keywords, list }
+    }
+    pub const fn command() -> &'static str {
+        "jump"
+    }
+
+    pub fn jump(path: &path::Path, context: &mut JoshutoContext) -> JoshutoResult<()> {
+        ChangeDirectory::change_directories(path, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+
+    fn jump_best(&self, context: &mut JoshutoContext) -> JoshutoResult<()> {
+        let path = match context.frecency.query(self.keywords.as_str()).first() {
+            Some(p) => p.to_path_buf(),
+            None => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IONotFound,
+                    format!("{}: no match for {}", Self::command(), self.keywords),
+                ))
+            }
+        };
+        Self::jump(path.as_path(), context)
+    }
+
+    fn jump_list(
+        &self,
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<()> {
+        const PROMPT: &str = "jump ";
+
+        let candidates: Vec<path::PathBuf> = context
+            .frecency
+            .query(self.keywords.as_str())
+            .iter()
+            .take(LIST_LIMIT)
+            .map(|p| p.to_path_buf())
+            .collect();
+
+        let user_input: Option<String> = {
+            let menu_options: Vec<String> = candidates
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("  {} | {}", i, p.to_string_lossy()))
+                .collect();
+            let menu_options_str: Vec<&str> = menu_options.iter().map(|e| e.as_str()).collect();
+            let menu_widget = TuiMenu::new(&menu_options_str);
+
+            let mut textfield = TuiTextField::default()
+                .prompt(":")
+                .prefix(PROMPT)
+                .menu(menu_widget);
+            textfield.get_input(backend, &context)
+        };
+
+        match user_input.as_ref() {
+            Some(user_input) if user_input.starts_with(PROMPT) => {
+                let user_input = user_input[PROMPT.len()..].trim();
+                match user_input.parse::<usize>() {
+                    Ok(n) if n >= candidates.len() => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        String::from("option does not exist"),
+                    )),
+                    Ok(n) => Self::jump(candidates[n].as_path(), context),
+                    Err(_) if user_input.is_empty() => Ok(()),
+                    Err(_) => Self::new(user_input.to_string(), false).jump_best(context),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl JoshutoCommand for Jump {}
+
+impl std::fmt::Display for Jump {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.list {
+            f.write_str(" --list")?;
+        }
+        if !self.keywords.is_empty() {
+            write!(f, " {}", self.keywords)?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for Jump {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if self.list {
+            self.jump_list(context, backend)
+        } else {
+            self.jump_best(context)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::path;
+
+use crate::commands::{ChangeDirectory, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::widgets::{TuiMenu, TuiTextField};
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+#[derive(Clone, Debug)]
+pub struct JumpAncestor {
+    level: Option<usize>,
+}
+
+impl JumpAncestor {
+    pub fn new(level: Option<usize>) -> Self {
+        JumpAncestor { level }
+    }
+    pub const fn command() -> &'static str {
+        "jump_ancestor"
+    }
+
+    // ancestors of the current directory, closest first
+    fn ancestors(context: &JoshutoContext) -> Vec<path::PathBuf> {
+        context
+            .curr_tab_ref()
+            .curr_path
+            .ancestors()
+            .skip(1)
+            .map(|p| p.to_path_buf())
+            .collect()
+    }
+
+    fn jump(&self, level: usize, context: &mut JoshutoContext) -> JoshutoResult<()> {
+        let ancestors = Self::ancestors(context);
+        if level == 0 || level > ancestors.len() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: no ancestor at level {}", Self::command(), level),
+            ));
+        }
+        ChangeDirectory::change_directories(ancestors[level - 1].as_path(), context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+
+    fn jump_list(
+        &self,
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+    ) -> JoshutoResult<()> {
+        const PROMPT: &str = "jump_ancestor ";
+
+        let user_input: Option<String> = {
+            let menu_options: Vec<String> = Self::ancestors(context)
+                .iter()
+                .enumerate()
+                .map(|(i, p)| format!("  {} | {}", i + 1, p.to_string_lossy()))
+                .collect();
+            let menu_options_str: Vec<&str> = menu_options.iter().map(|e| e.as_str()).collect();
+            let menu_widget = TuiMenu::new(&menu_options_str);
+
+            let mut textfield = TuiTextField::default()
+                .prompt(":")
+                .prefix(PROMPT)
+                .menu(menu_widget);
+            textfield.get_input(backend, &context)
+        };
+
+        match user_input.as_ref() {
+            Some(user_input) if user_input.starts_with(PROMPT) => {
+                let user_input = user_input[PROMPT.len()..].trim();
+                match user_input.parse::<usize>() {
+                    Ok(n) => self.jump(n, context),
+                    Err(_) if user_input.is_empty() => Ok(()),
+                    Err(e) => Err(JoshutoError::new(
+                        JoshutoErrorKind::ParseError,
+                        format!("{}: {}", Self::command(), e),
+                    )),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl JoshutoCommand for JumpAncestor {}
+
+impl std::fmt::Display for JumpAncestor {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.level {
+            Some(level) => write!(f, "{} {}", Self::command(), level),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl JoshutoRunnable for JumpAncestor {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        match self.level {
+            Some(level) => self.jump(level, context),
+            None => self.jump_list(context, backend),
+        }
+    }
+}
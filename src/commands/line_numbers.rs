@@ -0,0 +1,43 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::config::LineNumberMode;
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct ToggleLineNumbers {
+    mode: Option<LineNumberMode>,
+}
+
+impl ToggleLineNumbers {
+    pub fn new(mode: Option<LineNumberMode>) -> Self {
+        ToggleLineNumbers { mode }
+    }
+    pub const fn command() -> &'static str {
+        "toggle_line_numbers"
+    }
+}
+
+impl JoshutoCommand for ToggleLineNumbers {}
+
+impl std::fmt::Display for ToggleLineNumbers {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.mode {
+            Some(mode) => write!(f, "{} {}", Self::command(), mode.as_str()),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl JoshutoRunnable for ToggleLineNumbers {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        /* switch to the given mode, or hide the numbers if it is already active */
+        let mode = self.mode.unwrap_or(LineNumberMode::Absolute);
+        context.config_t.line_numbers = match context.config_t.line_numbers {
+            LineNumberMode::None => mode,
+            curr if self.mode.is_some() && curr != mode => mode,
+            _ => LineNumberMode::None,
+        };
+        Ok(())
+    }
+}
@@ -1,55 +1,122 @@
 mod bulk_rename;
+mod calculate_size;
 mod change_directory;
+mod chmod;
+#[cfg(unix)]
+mod chown;
 mod command_line;
 mod cursor_move;
+mod custom_command;
 mod delete_files;
+mod diff;
+mod extension_column;
 mod file_ops;
+mod flatten;
+mod follow_newest;
+mod grep;
+mod help;
+mod hex_preview;
+mod jump;
+mod jump_ancestor;
+mod line_numbers;
 mod new_directory;
 mod open_file;
+mod open_in_gui;
+mod page;
 mod parent_directory;
 mod quit;
 mod reload_dir;
+mod rename_clean;
 mod rename_file;
+mod repeat;
+mod scroll_pane;
 mod search;
 mod selection;
 mod set_mode;
 mod shell;
 mod show_hidden;
 mod sort;
+mod split;
+#[cfg(unix)]
+mod sync;
 mod tab_operations;
 mod tab_switch;
+#[cfg(unix)]
+mod touch_time;
+mod view_columns;
+mod yank_contents;
+mod yank_path_to_tab;
 
 pub use self::bulk_rename::BulkRename;
-pub use self::change_directory::ChangeDirectory;
+pub use self::calculate_size::CalculateSize;
+pub use self::change_directory::{ChangeDirectory, ChangeDirectoryInteractive, Reveal};
+pub use self::chmod::Chmod;
+#[cfg(unix)]
+pub use self::chown::Chown;
 pub use self::command_line::CommandLine;
 pub use self::cursor_move::{
-    CursorMoveDown, CursorMoveEnd, CursorMoveHome, CursorMovePageDown, CursorMovePageUp,
-    CursorMoveUp,
+    CursorMoveDown, CursorMoveEnd, CursorMoveHalfPageDown, CursorMoveHalfPageUp, CursorMoveHome,
+    CursorMovePageDown, CursorMovePageUp, CursorMoveUp, GotoIndex, PreviewCursorMoveDown,
+    PreviewCursorMoveUp,
 };
-pub use self::delete_files::DeleteFiles;
-pub use self::file_ops::{CopyFiles, CutFiles, PasteFiles};
-pub use self::new_directory::NewDirectory;
-pub use self::open_file::{OpenFile, OpenFileWith};
+pub use self::custom_command::CustomCommand;
+pub use self::delete_files::{DeleteFiles, DeletePattern};
+pub use self::diff::Diff;
+pub use self::extension_column::ToggleExtensionColumn;
+pub use self::file_ops::{
+    CopyAppend, CopyFiles, CutAppend, CutFiles, FileOp, PasteFiles, PasteFromRegister,
+    PasteIntoPreview, PasteToTarget, YankToRegister,
+};
+pub use self::flatten::{Flatten, Unflatten};
+pub use self::follow_newest::ToggleFollowNewest;
+pub use self::grep::{Grep, GrepCancel, GrepMatch, GrepResults};
+pub use self::help::Help;
+pub use self::hex_preview::ToggleHexPreview;
+pub use self::jump::Jump;
+pub use self::jump_ancestor::JumpAncestor;
+pub use self::line_numbers::ToggleLineNumbers;
+pub use self::new_directory::{NewDirectory, NewDirectoryEnter};
+pub use self::open_file::{ClearOpenWith, OpenFile, OpenFileWith, OpenIndex};
+pub use self::open_in_gui::OpenInGui;
+pub use self::page::Page;
 pub use self::parent_directory::ParentDirectory;
 pub use self::quit::ForceQuit;
 pub use self::quit::Quit;
-pub use self::reload_dir::ReloadDirList;
+pub use self::reload_dir::{ReloadAll, ReloadDirList};
+pub use self::rename_clean::RenameClean;
 pub use self::rename_file::{RenameFile, RenameFileAppend, RenameFilePrepend};
+pub use self::repeat::Repeat;
+pub use self::scroll_pane::{ScrollPaneDown, ScrollPaneUp};
 pub use self::search::{Search, SearchNext, SearchPrev};
-pub use self::selection::SelectFiles;
+pub use self::selection::{
+    MarkFiles, SelectByRegex, SelectClear, SelectEntryType, SelectFiles, SelectSameExtension,
+    SelectToggleHidden, SelectType,
+};
 pub use self::set_mode::SetMode;
 pub use self::shell::ShellCommand;
 pub use self::show_hidden::ToggleHiddenFiles;
-pub use self::sort::{Sort,SortReverse};
-pub use self::tab_operations::{CloseTab, NewTab};
-pub use self::tab_switch::TabSwitch;
+pub use self::sort::{Sort, SortDirectory, SortReverse};
+pub use self::split::Split;
+#[cfg(unix)]
+pub use self::sync::SyncFilesystems;
+pub use self::tab_operations::{CloseTab, DuplicateTab, NewTab, OpenParentInTab, SetTargetTab};
+pub use self::tab_switch::{TabSwitch, TabSwitchNext, TabSwitchPrev};
+#[cfg(unix)]
+pub use self::touch_time::TouchTime;
+pub use self::view_columns::SetViewColumns;
+pub use self::yank_contents::YankContents;
+pub use self::yank_path_to_tab::YankPathToTab;
 
 use std::path::PathBuf;
 
-use crate::config::JoshutoCommandMapping;
+use globset::Glob;
+
+use crate::config::{JoshutoCommandMapping, LeftPaneMode, LineNumberMode, RightPaneMode};
 use crate::context::JoshutoContext;
 use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
-use crate::io::Options;
+use crate::io::{IOResults, Options};
+use crate::tab::SidePane;
+use crate::ui::widgets::TuiPrompt;
 use crate::ui::TuiBackend;
 use crate::util::sort::SortType;
 
@@ -74,16 +141,215 @@ pub trait JoshutoRunnable {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()>;
 }
 
-pub trait JoshutoCommand: JoshutoRunnable + std::fmt::Display + std::fmt::Debug {}
+pub trait CloneCommand {
+    fn clone_command(&self) -> Box<dyn JoshutoCommand>;
+}
+
+impl<T: 'static + JoshutoCommand + Clone> CloneCommand for T {
+    fn clone_command(&self) -> Box<dyn JoshutoCommand> {
+        Box::new(self.clone())
+    }
+}
+
+pub trait JoshutoCommand:
+    JoshutoRunnable + CloneCommand + std::fmt::Display + std::fmt::Debug
+{
+    // whether the repeat command may run this again
+    fn is_repeatable(&self) -> bool {
+        true
+    }
+    // whether this changes anything on disk, which read-only mode refuses
+    fn modifies_files(&self) -> bool {
+        false
+    }
+}
+
+/*
+ * runs command unless it would change files in read-only mode, remembering
+ * it for repeat. commands run from keys, the console, custom commands and
+ * repeat all go through here
+ */
+pub fn run_command(
+    command: &dyn JoshutoCommand,
+    context: &mut JoshutoContext,
+    backend: &mut TuiBackend,
+) -> JoshutoResult<()> {
+    if context.readonly && command.modifies_files() {
+        let name = command.to_string();
+        let name = name.split_whitespace().next().unwrap_or_default();
+        context
+            .message_queue
+            .push_back(format!("{}: Disabled in read-only mode", name));
+        return Ok(());
+    }
+    let res = command.execute(context, backend);
+    if command.is_repeatable() {
+        context.last_command = Some(command.clone_command());
+    }
+    res
+}
+
+/*
+ * splits arguments on whitespace, except inside single or double quotes.
+ * a backslash escapes the next character, except inside single quotes
+ */
+pub fn tokenize(s: &str) -> Result<Vec<String>, String> {
+    let mut tokens = Vec::new();
+    let mut token = String::new();
+    let mut in_token = false;
+    let mut quote: Option<char> = None;
+
+    let mut chars = s.chars();
+    while let Some(ch) = chars.next() {
+        match (quote, ch) {
+            (Some('\''), '\'') => quote = None,
+            (Some('\''), ch) => token.push(ch),
+            (Some('"'), '"') => quote = None,
+            (_, '\\') => match chars.next() {
+                Some(escaped) => {
+                    token.push(escaped);
+                    in_token = true;
+                }
+                None => return Err(String::from("trailing backslash")),
+            },
+            (Some(_), ch) => token.push(ch),
+            (None, '\'') | (None, '"') => {
+                quote = Some(ch);
+                in_token = true;
+            }
+            (None, ch) if ch.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::replace(&mut token, String::new()));
+                    in_token = false;
+                }
+            }
+            (None, ch) => {
+                token.push(ch);
+                in_token = true;
+            }
+        }
+    }
+    if let Some(q) = quote {
+        return Err(format!("unterminated {} quote", q));
+    }
+    if in_token {
+        tokens.push(token);
+    }
+    Ok(tokens)
+}
+
+// backslash escapes whatever tokenize would otherwise split on or unquote
+pub fn escape_arg(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for ch in s.chars() {
+        if ch.is_whitespace() || ch == '\'' || ch == '"' || ch == '\\' {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+// refuses to go on when the current directory can't be written to
+pub fn check_writable(command: &str, context: &JoshutoContext) -> JoshutoResult<()> {
+    match context.curr_tab_ref().curr_list_ref() {
+        Some(list) if !list.writable => Err(JoshutoError::new(
+            JoshutoErrorKind::IOPermissionDenied,
+            format!(
+                "{}: {} is read-only",
+                command,
+                context.curr_tab_ref().curr_path.to_string_lossy()
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/*
+ * puts done_msg on the status line, or how many entries failed if any did.
+ * operations on more than one entry also list what became of each one
+ * until a key is pressed
+ */
+pub fn report_results(
+    results: &IOResults,
+    done_msg: String,
+    context: &mut JoshutoContext,
+    backend: &mut TuiBackend,
+) {
+    let msg = if results.failed.is_empty() {
+        done_msg
+    } else {
+        results.summary()
+    };
+    context.message_queue.push_back(msg);
+    if results.len() > 1 {
+        let lines = results.lines();
+        let lines: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+        let prompt_str = format!("{} (press any key)", results.summary());
+        let mut prompt = TuiPrompt::new(&prompt_str).menu(&lines);
+        prompt.get_key(backend, &context);
+    }
+}
+
+// lists everything a dry run would have done in one menu, instead of a message each
+pub fn report_dry_run(lines: &[String], context: &JoshutoContext, backend: &mut TuiBackend) {
+    let lines: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+    let mut prompt = TuiPrompt::new("Dry run, nothing was changed (press any key)").menu(&lines);
+    prompt.get_key(backend, context);
+}
+
+fn split_args(command: &str, arg: &str) -> JoshutoResult<Vec<String>> {
+    tokenize(arg)
+        .map_err(|e| JoshutoError::new(JoshutoErrorKind::ParseError, format!("{}: {}", command, e)))
+}
+
+// the argument as a single string with quotes and escapes resolved
+fn join_args(command: &str, arg: &str) -> JoshutoResult<String> {
+    split_args(command, arg).map(|args| args.join(" "))
+}
+
+// a pane followed by how many lines to scroll it, 1 if left out
+fn pane_scroll_args(command: &str, arg: &str) -> JoshutoResult<(SidePane, usize)> {
+    let args = split_args(command, arg)?;
+    let pane = match args.get(0).and_then(|s| SidePane::parse(s)) {
+        Some(pane) => pane,
+        None => {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: Expected parent or child", command),
+            ))
+        }
+    };
+    match args.get(1).map(|s| s.parse::<usize>()) {
+        _ if args.len() > 2 => Err(JoshutoError::new(
+            JoshutoErrorKind::IOInvalidData,
+            format!("{}: unknown option {}", command, args[2]),
+        )),
+        None => Ok((pane, 1)),
+        Some(Ok(movement)) => Ok((pane, movement)),
+        Some(Err(e)) => Err(JoshutoError::new(
+            JoshutoErrorKind::ParseError,
+            format!("{}: {}", command, e),
+        )),
+    }
+}
 
 pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
     let (command, arg) = match s.find(' ') {
-        Some(i) => (&s[..i], s[i+1..].trim_start()),
+        Some(i) => (&s[..i], s[i + 1..].trim_start()),
         None => (s, ""),
     };
 
     match command {
-        "bulk_rename" => Ok(Box::new(self::BulkRename::new())),
+        "bulk_rename" => match arg {
+            "" => Ok(Box::new(self::BulkRename::new(false))),
+            "--dry-run" => Ok(Box::new(self::BulkRename::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
+        "calculate_size" => Ok(Box::new(self::CalculateSize::new())),
         "cd" => match arg {
             "" => match HOME_DIR.as_ref() {
                 Some(s) => Ok(Box::new(self::ChangeDirectory::new(s.clone()))),
@@ -93,15 +359,90 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                 )),
             },
             ".." => Ok(Box::new(self::ParentDirectory::new())),
-            arg => Ok(Box::new(self::ChangeDirectory::new(PathBuf::from(arg)))),
+            arg => {
+                let path = join_args(command, arg)?;
+                Ok(Box::new(self::ChangeDirectory::new(PathBuf::from(path))))
+            }
+        },
+        "cd_interactive" => Ok(Box::new(self::ChangeDirectoryInteractive::new())),
+        "chmod" => {
+            let mut recursive = false;
+            let mut mode = None;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "-R" => recursive = true,
+                    arg if mode.is_none() => mode = Some(arg),
+                    _ => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unexpected argument {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            match mode {
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: missing mode", command),
+                )),
+                Some(mode) => match self::Chmod::new(mode.to_string(), recursive) {
+                    Some(chmod) => Ok(Box::new(chmod)),
+                    None => Err(JoshutoError::new(
+                        JoshutoErrorKind::ParseError,
+                        format!("{}: invalid mode {}", command, mode),
+                    )),
+                },
+            }
+        }
+        #[cfg(unix)]
+        "chown" => {
+            let mut recursive = false;
+            let mut owner = None;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "-R" => recursive = true,
+                    arg if owner.is_none() => owner = Some(arg),
+                    _ => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unexpected argument {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            match owner {
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: missing owner", command),
+                )),
+                Some(owner) => match self::Chown::new(owner.to_string(), recursive) {
+                    Ok(chown) => Ok(Box::new(chown)),
+                    Err(e) => Err(JoshutoError::new(
+                        JoshutoErrorKind::ParseError,
+                        format!("{}: {}", command, e),
+                    )),
+                },
+            }
         }
+        "clear_open_with" => match arg {
+            "" => Ok(Box::new(self::ClearOpenWith::new(None))),
+            arg => Ok(Box::new(self::ClearOpenWith::new(Some(arg.to_string())))),
+        },
         "close_tab" => Ok(Box::new(self::CloseTab::new())),
+        "copy_append" => Ok(Box::new(self::CopyAppend::new())),
         "copy_files" => Ok(Box::new(self::CopyFiles::new())),
-        "console" => Ok(Box::new(self::CommandLine::new(arg.to_owned(), "".to_owned()))),
+        "console" => Ok(Box::new(self::CommandLine::new(
+            arg.to_owned(),
+            "".to_owned(),
+        ))),
         "cursor_move_home" => Ok(Box::new(self::CursorMoveHome::new())),
         "cursor_move_end" => Ok(Box::new(self::CursorMoveEnd::new())),
         "cursor_move_page_up" => Ok(Box::new(self::CursorMovePageUp::new())),
         "cursor_move_page_down" => Ok(Box::new(self::CursorMovePageDown::new())),
+        "cursor_move_half_page_up" => Ok(Box::new(self::CursorMoveHalfPageUp::new())),
+        "cursor_move_half_page_down" => Ok(Box::new(self::CursorMoveHalfPageDown::new())),
         "cursor_move_down" => match arg {
             "" => Ok(Box::new(self::CursorMoveDown::new(1))),
             arg => match arg.parse::<usize>() {
@@ -111,7 +452,7 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                     e.to_string(),
                 )),
             },
-        }
+        },
         "cursor_move_up" => match arg {
             "" => Ok(Box::new(self::CursorMoveUp::new(1))),
             arg => match arg.parse::<usize>() {
@@ -121,27 +462,183 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                     e.to_string(),
                 )),
             },
-        }
+        },
+        "cut_append" => Ok(Box::new(self::CutAppend::new())),
         "cut_files" => Ok(Box::new(self::CutFiles::new())),
-        "delete_files" => Ok(Box::new(self::DeleteFiles::new())),
+        "delete_files" => match arg {
+            "" => Ok(Box::new(self::DeleteFiles::new(false))),
+            "--dry-run" => Ok(Box::new(self::DeleteFiles::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
+        "delete_pattern" => {
+            let mut pattern = None;
+            let mut hidden = false;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "--hidden" => hidden = true,
+                    arg if pattern.is_none() => match Glob::new(arg) {
+                        Ok(glob) => pattern = Some(glob.compile_matcher()),
+                        Err(e) => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::ParseError,
+                                format!("{}: {}", command, e),
+                            ));
+                        }
+                    },
+                    arg => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unknown option {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            match pattern {
+                Some(p) => Ok(Box::new(self::DeletePattern::new(p, hidden))),
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Expected 1, got 0", command),
+                )),
+            }
+        }
+        "diff" => Ok(Box::new(self::Diff::new())),
+        "duplicate_tab" => Ok(Box::new(self::DuplicateTab::new())),
+        "flatten" => match arg {
+            "" => Ok(Box::new(self::Flatten::new(None))),
+            arg => match arg.parse::<usize>() {
+                Ok(depth) => Ok(Box::new(self::Flatten::new(Some(depth)))),
+                Err(e) => Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    format!("{}: {}", command, e.to_string()),
+                )),
+            },
+        },
         "force_quit" => Ok(Box::new(self::ForceQuit::new())),
+        "goto_index" => match arg.parse::<usize>() {
+            Ok(0) => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: indices start at 1", command),
+            )),
+            Ok(s) => Ok(Box::new(self::GotoIndex::new(s))),
+            Err(e) => Err(JoshutoError::new(
+                JoshutoErrorKind::ParseError,
+                format!("{}: {}", command, e.to_string()),
+            )),
+        },
+        "grep" => match arg {
+            "--cancel" => Ok(Box::new(self::GrepCancel::new())),
+            "" => Ok(Box::new(self::Grep::new(None, false))),
+            "--edit" => Ok(Box::new(self::Grep::new(None, true))),
+            arg if arg.starts_with("--edit ") => {
+                let pattern = join_args(command, &arg["--edit ".len()..])?;
+                Ok(Box::new(self::Grep::new(Some(pattern), true)))
+            }
+            arg => {
+                let pattern = join_args(command, arg)?;
+                Ok(Box::new(self::Grep::new(Some(pattern), false)))
+            }
+        },
+        "help" => Ok(Box::new(self::Help::new())),
+        "jump" => match arg {
+            "" => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: missing additional parameter", command),
+            )),
+            "--list" => Ok(Box::new(self::Jump::new(String::new(), true))),
+            arg if arg.starts_with("--list ") => {
+                let keywords = arg["--list ".len()..].trim_start();
+                Ok(Box::new(self::Jump::new(keywords.to_string(), true)))
+            }
+            arg => Ok(Box::new(self::Jump::new(arg.to_string(), false))),
+        },
+        "jump_ancestor" => match arg {
+            "" => Ok(Box::new(self::JumpAncestor::new(None))),
+            arg => match arg.parse::<usize>() {
+                Ok(level) => Ok(Box::new(self::JumpAncestor::new(Some(level)))),
+                Err(e) => Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    format!("{}: {}", command, e.to_string()),
+                )),
+            },
+        },
+        "mark_files" => match arg {
+            "" => Ok(Box::new(self::MarkFiles::new(false))),
+            "--all" => Ok(Box::new(self::MarkFiles::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
         "mkdir" => match arg {
             "" => Err(JoshutoError::new(
                 JoshutoErrorKind::IOInvalidData,
                 format!("{}: missing additional parameter", command),
             )),
-            arg => Ok(Box::new(self::NewDirectory::new(PathBuf::from(arg)))),
-        }
-        "new_tab" => Ok(Box::new(self::NewTab::new())),
+            arg => {
+                let path = join_args(command, arg)?;
+                Ok(Box::new(self::NewDirectory::new(PathBuf::from(path))))
+            }
+        },
+        "mkdir_and_enter" => match arg {
+            "" => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: missing additional parameter", command),
+            )),
+            arg => {
+                let path = join_args(command, arg)?;
+                Ok(Box::new(self::NewDirectoryEnter::new(PathBuf::from(path))))
+            }
+        },
+        "new_tab" => match arg {
+            "" => Ok(Box::new(self::NewTab::new(false))),
+            "--here" => Ok(Box::new(self::NewTab::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
 
         "open_file" => Ok(Box::new(self::OpenFile::new())),
-        "open_file_with" => Ok(Box::new(self::OpenFileWith::new())),
+        "open_file_with" => {
+            let program = split_args(command, arg)?;
+            Ok(Box::new(self::OpenFileWith::new(program)))
+        }
+        "open_in_gui" => Ok(Box::new(self::OpenInGui::new())),
+        "open_parent_in_tab" => Ok(Box::new(self::OpenParentInTab::new())),
+        "open_index" => match arg.parse::<usize>() {
+            Ok(s) => Ok(Box::new(self::OpenIndex::new(s))),
+            Err(e) => Err(JoshutoError::new(
+                JoshutoErrorKind::ParseError,
+                format!("{}: {}", command, e.to_string()),
+            )),
+        },
+        "page" => Ok(Box::new(self::Page::new())),
         "paste_files" => {
             let mut options = Options::default();
-            for arg in arg.split_whitespace() {
+            let mut dry_run = false;
+            let args = split_args(command, arg)?;
+            let mut args = args.iter().map(|s| s.as_str());
+            while let Some(arg) = args.next() {
                 match arg {
+                    "--to" => match args.next() {
+                        Some(dest) => options.destination = Some(PathBuf::from(dest)),
+                        None => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::IOInvalidData,
+                                format!("{}: --to expects a destination", command),
+                            ));
+                        }
+                    },
                     "--overwrite" => options.overwrite = true,
                     "--skip_exist" => options.skip_exist = true,
+                    "--recursive-dereference" => options.dereference = true,
+                    "--dry-run" => dry_run = true,
+                    "--preserve" => options.preserve_times = true,
+                    "--preserve-xattrs" => options.preserve_xattrs = true,
                     _ => {
                         return Err(JoshutoError::new(
                             JoshutoErrorKind::IOInvalidData,
@@ -150,9 +647,39 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                     }
                 }
             }
-            Ok(Box::new(self::PasteFiles::new(options)))
+            Ok(Box::new(self::PasteFiles::new(options, dry_run)))
         }
+        "paste_from_register" => match split_args(command, arg)?.as_slice() {
+            [name] => Ok(Box::new(self::PasteFromRegister::new(name.clone()))),
+            _ => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: Expected a register name", command),
+            )),
+        },
+        "paste_into_preview" => Ok(Box::new(self::PasteIntoPreview::new())),
+        "paste_to_target" => Ok(Box::new(self::PasteToTarget::new())),
+        "preview_cursor_move_down" => match arg {
+            "" => Ok(Box::new(self::PreviewCursorMoveDown::new(1))),
+            arg => match arg.parse::<usize>() {
+                Ok(s) => Ok(Box::new(self::PreviewCursorMoveDown::new(s))),
+                Err(e) => Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    e.to_string(),
+                )),
+            },
+        },
+        "preview_cursor_move_up" => match arg {
+            "" => Ok(Box::new(self::PreviewCursorMoveUp::new(1))),
+            arg => match arg.parse::<usize>() {
+                Ok(s) => Ok(Box::new(self::PreviewCursorMoveUp::new(s))),
+                Err(e) => Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    e.to_string(),
+                )),
+            },
+        },
         "quit" => Ok(Box::new(self::Quit::new())),
+        "reload_all" => Ok(Box::new(self::ReloadAll::new())),
         "reload_dir_list" => Ok(Box::new(self::ReloadDirList::new())),
         "rename" => match arg {
             "" => Err(JoshutoError::new(
@@ -160,28 +687,113 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                 format!("rename_file: Expected 1, got 0"),
             )),
             arg => {
-                let path: PathBuf = PathBuf::from(arg);
+                let path: PathBuf = PathBuf::from(join_args(command, arg)?);
                 Ok(Box::new(self::RenameFile::new(path)))
             }
         },
-        "rename_append" => Ok(Box::new(self::RenameFileAppend::new())),
-        "rename_prepend" => Ok(Box::new(self::RenameFilePrepend::new())),
+        "rename_clean" => match arg {
+            "" => Ok(Box::new(self::RenameClean::new(false))),
+            "--dry-run" => Ok(Box::new(self::RenameClean::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
+        "rename_append" => match arg {
+            "" => Ok(Box::new(self::RenameFileAppend::new(None))),
+            arg => Ok(Box::new(self::RenameFileAppend::new(Some(join_args(
+                command, arg,
+            )?)))),
+        },
+        "rename_prepend" => match arg {
+            "" => Ok(Box::new(self::RenameFilePrepend::new(None))),
+            arg => Ok(Box::new(self::RenameFilePrepend::new(Some(join_args(
+                command, arg,
+            )?)))),
+        },
         "search" => match arg {
             "" => Err(JoshutoError::new(
                 JoshutoErrorKind::IOInvalidData,
                 format!("{}: Expected 1, got 0", command),
             )),
-            arg => Ok(Box::new(self::Search::new(arg))),
+            arg => Ok(Box::new(self::Search::new(
+                join_args(command, arg)?.as_str(),
+            ))),
         },
         "search_next" => Ok(Box::new(self::SearchNext::new())),
         "search_prev" => Ok(Box::new(self::SearchPrev::new())),
+        "repeat" => Ok(Box::new(self::Repeat::new())),
+        "reveal" => match arg {
+            "" => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: Expected a path", command),
+            )),
+            arg => {
+                let path = join_args(command, arg)?;
+                Ok(Box::new(self::Reveal::new(PathBuf::from(path))))
+            }
+        },
+        "scroll_pane_down" => {
+            let (pane, movement) = pane_scroll_args(command, arg)?;
+            Ok(Box::new(self::ScrollPaneDown::new(pane, movement)))
+        }
+        "scroll_pane_up" => {
+            let (pane, movement) = pane_scroll_args(command, arg)?;
+            Ok(Box::new(self::ScrollPaneUp::new(pane, movement)))
+        }
+        "select_by_regex" => {
+            let mut pattern = None;
+            let mut toggle = false;
+            let mut unselect = false;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "--toggle" => toggle = true,
+                    "--unselect" => unselect = true,
+                    arg if pattern.is_none() => pattern = Some(arg),
+                    arg => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unknown option {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            match pattern {
+                Some(p) => Ok(Box::new(self::SelectByRegex::new(p, toggle, unselect)?)),
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Expected 1, got 0", command),
+                )),
+            }
+        }
         "select_files" => {
             let mut toggle = false;
             let mut all = false;
-            for arg in arg.split_whitespace() {
+            let mut advance = true;
+            let mut pattern = None;
+            let args = split_args(command, arg)?;
+            let mut args = args.iter().map(|s| s.as_str());
+            while let Some(arg) = args.next() {
                 match arg {
                     "--toggle" => toggle = true,
                     "--all" => all = true,
+                    "--no-advance" => advance = false,
+                    "--pattern" => match args.next().map(Glob::new) {
+                        Some(Ok(glob)) => pattern = Some(glob.compile_matcher()),
+                        Some(Err(e)) => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::ParseError,
+                                format!("{}: {}", command, e),
+                            ));
+                        }
+                        None => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::IOInvalidData,
+                                format!("{}: --pattern expects a glob", command),
+                            ));
+                        }
+                    },
                     _ => {
                         return Err(JoshutoError::new(
                             JoshutoErrorKind::IOInvalidData,
@@ -190,37 +802,297 @@ pub fn parse_command(s: &str) -> JoshutoResult<Box<dyn JoshutoCommand>> {
                     }
                 }
             }
-            Ok(Box::new(self::SelectFiles::new(toggle, all)))
+            Ok(Box::new(self::SelectFiles::new(
+                toggle, all, advance, pattern,
+            )))
+        }
+        "select_clear" => Ok(Box::new(self::SelectClear::new())),
+        "select_same_extension" => {
+            let mut toggle = false;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "--toggle" => toggle = true,
+                    arg => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unknown option {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            Ok(Box::new(self::SelectSameExtension::new(toggle)))
+        }
+        "select_toggle_hidden" => Ok(Box::new(self::SelectToggleHidden::new())),
+        "select_type" => {
+            let mut entry_type = None;
+            let mut toggle = false;
+            let mut unselect = false;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "--toggle" => toggle = true,
+                    "--unselect" => unselect = true,
+                    arg => match SelectEntryType::parse(arg) {
+                        Some(t) if entry_type.is_none() => entry_type = Some(t),
+                        _ => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::IOInvalidData,
+                                format!("{}: unknown option {}", command, arg),
+                            ));
+                        }
+                    },
+                }
+            }
+            match entry_type {
+                Some(t) => Ok(Box::new(self::SelectType::new(t, toggle, unselect))),
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Expected one of dir, file, symlink, exec", command),
+                )),
+            }
         }
         "set_mode" => Ok(Box::new(self::SetMode::new())),
+        "set_target_tab" => Ok(Box::new(self::SetTargetTab::new())),
+        "set_view_columns" => {
+            let mut left = None;
+            let mut right = None;
+            let args = split_args(command, arg)?;
+            let mut args = args.iter().map(|s| s.as_str());
+            while let Some(arg) = args.next() {
+                match arg {
+                    "--left" => match args.next().and_then(LeftPaneMode::parse) {
+                        Some(mode) => left = Some(mode),
+                        None => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::IOInvalidData,
+                                format!("{}: --left expects parent or none", command),
+                            ))
+                        }
+                    },
+                    "--right" => match args.next().and_then(RightPaneMode::parse) {
+                        Some(mode) => right = Some(mode),
+                        None => {
+                            return Err(JoshutoError::new(
+                                JoshutoErrorKind::IOInvalidData,
+                                format!(
+                                    "{}: --right expects auto, listing, preview or none",
+                                    command
+                                ),
+                            ))
+                        }
+                    },
+                    arg => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: Unknown option {}", command, arg),
+                        ))
+                    }
+                }
+            }
+            if left.is_none() && right.is_none() {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Expected --left or --right", command),
+                ));
+            }
+            Ok(Box::new(self::SetViewColumns::new(left, right)))
+        }
         "shell" => Ok(Box::new(self::ShellCommand::new(arg.to_owned()))),
         "sort" => match arg {
             "reverse" => Ok(Box::new(self::SortReverse::new())),
-            arg => match SortType::parse(arg) {
-                Some(s) => Ok(Box::new(self::Sort::new(s))),
-                None => Err(JoshutoError::new(
-                    JoshutoErrorKind::IOInvalidData,
-                    format!("sort: Unknown option {}", arg),
-                )),
-            },
+            "--forget" => Ok(Box::new(self::SortDirectory::new(None))),
+            arg => {
+                let mut sort_method = None;
+                let mut reverse = false;
+                let mut here = false;
+                for arg in split_args(command, arg)?.iter().map(|s| s.as_str()) {
+                    match arg {
+                        "--here" => here = true,
+                        "--reverse" => reverse = true,
+                        arg => match SortType::parse(arg) {
+                            Some(s) if sort_method.is_none() => sort_method = Some(s),
+                            _ => {
+                                return Err(JoshutoError::new(
+                                    JoshutoErrorKind::IOInvalidData,
+                                    format!("sort: Unknown option {}", arg),
+                                ))
+                            }
+                        },
+                    }
+                }
+                match sort_method {
+                    Some(s) if here => Ok(Box::new(self::SortDirectory::new(Some((s, reverse))))),
+                    Some(s) => Ok(Box::new(self::Sort::new(s, reverse))),
+                    None => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!("{}: Expected a sort method", command),
+                    )),
+                }
+            }
         },
+        "split" => match arg {
+            "" => Ok(Box::new(self::Split::new(false))),
+            "--horizontal" => Ok(Box::new(self::Split::new(true))),
+            arg => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: unknown option {}", command, arg),
+            )),
+        },
+        #[cfg(unix)]
+        "sync" => Ok(Box::new(self::SyncFilesystems::new())),
         "tab_switch" => match arg {
             "" => Err(JoshutoError::new(
                 JoshutoErrorKind::IOInvalidData,
                 format!("{}: {}", command, "No option provided"),
             )),
+            arg => {
+                /* N is relative to the current tab, --index N counts from the first */
+                let (arg, relative) = match arg.strip_prefix("--index") {
+                    Some(rest) => (rest.trim(), false),
+                    None => (arg, true),
+                };
+                match arg.parse::<i32>() {
+                    Ok(0) if !relative => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!(
+                            "{}: Tabs are numbered from 1, or from -1 at the end",
+                            command
+                        ),
+                    )),
+                    Ok(s) => Ok(Box::new(self::TabSwitch::new(s, relative))),
+                    Err(e) => Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!("{}: {}", command, e.to_string()),
+                    )),
+                }
+            }
+        },
+        "tab_switch_next" => Ok(Box::new(self::TabSwitchNext::new())),
+        "tab_switch_prev" => Ok(Box::new(self::TabSwitchPrev::new())),
+        "toggle_extension_column" => Ok(Box::new(self::ToggleExtensionColumn::new())),
+        "toggle_follow_newest" => Ok(Box::new(self::ToggleFollowNewest::new())),
+        "toggle_hex_preview" => Ok(Box::new(self::ToggleHexPreview::new())),
+        "toggle_hidden" => Ok(Box::new(self::ToggleHiddenFiles::new())),
+        "toggle_line_numbers" => match arg {
+            "" => Ok(Box::new(self::ToggleLineNumbers::new(None))),
+            arg => match LineNumberMode::parse(arg) {
+                Some(LineNumberMode::None) | None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Unknown option {}", command, arg),
+                )),
+                mode => Ok(Box::new(self::ToggleLineNumbers::new(mode))),
+            },
+        },
+        #[cfg(unix)]
+        "touch_time" => {
+            let time = match arg {
+                "" => None,
+                arg if arg.starts_with("--time ") => {
+                    Some(join_args(command, &arg["--time ".len()..])?)
+                }
+                arg => {
+                    return Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!("{}: unknown option {}", command, arg),
+                    ));
+                }
+            };
+            match self::TouchTime::new(time) {
+                Some(touch_time) => Ok(Box::new(touch_time)),
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    format!(
+                        "{}: invalid time, expected @<seconds> or YYYY-MM-DD [HH:MM[:SS]]",
+                        command
+                    ),
+                )),
+            }
+        }
+        "unflatten" => Ok(Box::new(self::Unflatten::new())),
+        "yank_contents" => Ok(Box::new(self::YankContents::new())),
+        "yank_path_to_tab" => match arg {
+            "" => Ok(Box::new(self::YankPathToTab::new(1))),
             arg => match arg.parse::<i32>() {
-                Ok(s) => Ok(Box::new(self::TabSwitch::new(s))),
+                Ok(s) => Ok(Box::new(self::YankPathToTab::new(s))),
                 Err(e) => Err(JoshutoError::new(
                     JoshutoErrorKind::IOInvalidData,
                     format!("{}: {}", command, e.to_string()),
                 )),
+            },
+        },
+        "yank_to_register" => {
+            let mut name = None;
+            let mut operation = FileOp::Copy;
+            let args = split_args(command, arg)?;
+            for arg in args.iter().map(|s| s.as_str()) {
+                match arg {
+                    "--cut" => operation = FileOp::Cut,
+                    arg if name.is_none() && !arg.starts_with('-') => name = Some(arg),
+                    _ => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: unknown option {}", command, arg),
+                        ));
+                    }
+                }
+            }
+            match name {
+                Some(name) => Ok(Box::new(self::YankToRegister::new(
+                    name.to_string(),
+                    operation,
+                ))),
+                None => Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: Expected a register name", command),
+                )),
             }
         }
-        "toggle_hidden" => Ok(Box::new(self::ToggleHiddenFiles::new())),
-        inp => Err(JoshutoError::new(
-            JoshutoErrorKind::UnknownCommand,
-            format!("Unknown command: {}", inp),
-        )),
+        inp => match self::CustomCommand::from_name(inp) {
+            Some(custom_command) => Ok(Box::new(custom_command?)),
+            None => Err(JoshutoError::new(
+                JoshutoErrorKind::UnknownCommand,
+                format!("Unknown command: {}", inp),
+            )),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(s: &str) -> Box<dyn JoshutoCommand> {
+        match parse_command(s) {
+            Ok(cmd) => cmd,
+            Err(e) => panic!("{}: {}", s, e.to_string()),
+        }
+    }
+
+    #[test]
+    fn display_parses_back_to_the_same_command() {
+        let commands = [
+            "cd /tmp/with\\ space",
+            "cd \"quoted dir/it's\"",
+            "mkdir \"a  b\"",
+            "rename back\\\\slash.txt",
+            "reveal /tmp/x",
+            "search foo\\ bar",
+            "select_files --toggle --pattern '*.rs'",
+            "delete_pattern '*.o' --hidden",
+            "paste_files --dry-run --to '/tmp/x y'",
+            "sort mtime --reverse",
+            "sort --here size --reverse",
+            "sort --forget",
+            "tab_switch --index -1",
+            "tab_switch 2",
+            "new_tab --here",
+            "mark_files --all",
+            "chown -R 0:0",
+        ];
+        for s in commands.iter() {
+            let displayed = parse(s).to_string();
+            assert_eq!(parse(&displayed).to_string(), displayed, "{}", s);
+        }
     }
 }
@@ -1,9 +1,10 @@
 use std::path;
 
-use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::commands::{
+    check_writable, escape_arg, ChangeDirectory, JoshutoCommand, JoshutoRunnable, ReloadDirList,
+};
 use crate::context::JoshutoContext;
 use crate::error::JoshutoResult;
-use crate::history::DirectoryHistory;
 use crate::ui::TuiBackend;
 use crate::util::load_child::LoadChild;
 
@@ -21,24 +22,76 @@ impl NewDirectory {
     }
 }
 
-impl JoshutoCommand for NewDirectory {}
+impl JoshutoCommand for NewDirectory {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for NewDirectory {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(Self::command())
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            escape_arg(&self.path.to_string_lossy())
+        )
     }
 }
 
 impl JoshutoRunnable for NewDirectory {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        /* absolute paths may well point somewhere writable */
+        if self.path.is_relative() {
+            check_writable(Self::command(), context)?;
+        }
         std::fs::create_dir_all(&self.path)?;
 
-        let options = &context.config_t.sort_option;
         let curr_path = context.tabs[context.curr_tab_index].curr_path.clone();
-        for tab in context.tabs.iter_mut() {
-            tab.history.reload(&curr_path, options)?;
-        }
+        ReloadDirList::reload_path(&curr_path, context)?;
+
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+// mkdir, then cd into the new directory unless creating it failed
+#[derive(Clone, Debug)]
+pub struct NewDirectoryEnter {
+    path: path::PathBuf,
+}
+
+impl NewDirectoryEnter {
+    pub fn new(path: path::PathBuf) -> Self {
+        NewDirectoryEnter { path }
+    }
+    pub const fn command() -> &'static str {
+        "mkdir_and_enter"
+    }
+}
+
+impl JoshutoCommand for NewDirectoryEnter {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for NewDirectoryEnter {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            escape_arg(&self.path.to_string_lossy())
+        )
+    }
+}
 
+impl JoshutoRunnable for NewDirectoryEnter {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let path = context.curr_tab_ref().curr_path.join(&self.path);
+        NewDirectory::new(self.path.clone()).execute(context, backend)?;
+        ChangeDirectory::change_directories(path.as_path(), context)?;
         LoadChild::load_child(context)?;
         Ok(())
     }
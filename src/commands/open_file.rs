@@ -1,16 +1,154 @@
-use std::path::Path;
+use std::env;
+use std::fs;
+use std::path;
+use std::process;
 
-use crate::commands::{ChangeDirectory, JoshutoCommand, JoshutoRunnable};
+use termion::event::Key;
+
+use crate::commands::{
+    cursor_move, escape_arg, ChangeDirectory, JoshutoCommand, JoshutoRunnable, NewTab,
+    ReloadDirList,
+};
 use crate::config::mimetype::JoshutoMimetypeEntry;
+use crate::config::{OpenArchiveMode, OpenDirMode, OpenExecutableMode, OpenSymlinkDirMode};
 use crate::context::JoshutoContext;
 use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
-use crate::fs::JoshutoDirEntry;
-use crate::ui::widgets::{TuiMenu, TuiTextField};
+use crate::fs::{FileType, JoshutoDirEntry};
+use crate::ui::widgets::{TuiMenu, TuiPrompt, TuiTextField};
 use crate::ui::TuiBackend;
 use crate::util::load_child::LoadChild;
+use crate::util::open_with_history::JoshutoOpenWithHistory;
+use crate::util::unix;
 
 use crate::MIMETYPE_T;
 
+// file name suffixes of archives and the programs extracting them
+const ARCHIVE_EXTRACTORS: [(&str, &str, &[&str]); 12] = [
+    (".tar", "tar", &["-xf"]),
+    (".tar.gz", "tar", &["-xf"]),
+    (".tgz", "tar", &["-xf"]),
+    (".tar.bz2", "tar", &["-xf"]),
+    (".tbz2", "tar", &["-xf"]),
+    (".tar.xz", "tar", &["-xf"]),
+    (".txz", "tar", &["-xf"]),
+    (".tar.zst", "tar", &["-xf"]),
+    (".zip", "unzip", &[]),
+    (".7z", "7z", &["x"]),
+    (".rar", "unrar", &["x"]),
+    (".jar", "unzip", &[]),
+];
+
+/*
+ * runs "$1" for run_shell, then waits for enter so what it printed stays
+ * on screen, exiting the way it did
+ */
+const RUN_SHELL_SCRIPT: &str =
+    r#""$1"; s=$?; printf '\nexited with %d, press enter to continue' "$s"; read _; exit "$s""#;
+
+// regular files, or symlinks to them, with an executable bit set
+fn is_executable_file(path: &path::Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    fs::metadata(path)
+        .map(|m| m.is_file() && unix::is_executable(m.mode()))
+        .unwrap_or(false)
+}
+
+fn archive_extractor(path: &path::Path) -> Option<JoshutoMimetypeEntry> {
+    let file_name = path.file_name()?.to_str()?.to_lowercase();
+    ARCHIVE_EXTRACTORS
+        .iter()
+        .find(|(suffix, _, _)| file_name.ends_with(suffix))
+        .map(|(_, command, args)| {
+            let mut entry = JoshutoMimetypeEntry::new(String::from(*command));
+            entry.args(args.iter().map(|s| s.to_string()));
+            entry
+        })
+}
+
+// whether every one of entries lists program as a way to open it
+fn opens_all(program: &JoshutoMimetypeEntry, entries: &[&JoshutoDirEntry]) -> bool {
+    entries.iter().all(|entry| {
+        OpenFile::get_options(entry)
+            .iter()
+            .any(|option| option.same_program(program))
+    })
+}
+
+/*
+ * runs program with all of paths at once when together is set,
+ * otherwise once for each path. timeout is the open_timeout config
+ */
+fn launch(
+    backend: &mut TuiBackend,
+    program: &JoshutoMimetypeEntry,
+    paths: &[&str],
+    together: bool,
+    timeout: u64,
+) -> std::io::Result<()> {
+    let run = || {
+        if together {
+            program.execute_with(paths, timeout)
+        } else {
+            paths
+                .iter()
+                .try_for_each(|path| program.execute_with(&[path], timeout))
+        }
+    };
+    if program.get_fork() {
+        run()
+    } else {
+        backend.terminal_drop();
+        let res = run();
+        backend.terminal_restore()?;
+        res
+    }
+}
+
+/*
+ * tries each fallback in order until one of them starts, returning the one
+ * that was used. words like $EDITOR are expanded, a fallback that refers
+ * to an unset variable is skipped
+ */
+fn open_fallback(
+    backend: &mut TuiBackend,
+    fallbacks: &[String],
+    paths: &[&str],
+    timeout: u64,
+) -> Option<String> {
+    for fallback in fallbacks {
+        let words: Option<Vec<String>> = fallback
+            .split_whitespace()
+            .map(|word| {
+                if word.starts_with('$') {
+                    env::var(&word[1..]).ok().filter(|s| !s.is_empty())
+                } else {
+                    Some(word.to_string())
+                }
+            })
+            .collect();
+        let words = match words {
+            Some(words) if !words.is_empty() => words,
+            _ => continue,
+        };
+        let mut program = JoshutoMimetypeEntry::new(words[0].clone());
+        program.args(words[1..].iter());
+        if launch(backend, &program, paths, true, timeout).is_ok() {
+            return Some(words.join(" "));
+        }
+    }
+    None
+}
+
+// how open_file deals with the entry under the cursor, as configured
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum OpenAction {
+    Open,
+    OpenWith,
+    Extract,
+    Run,
+}
+
 #[derive(Clone, Debug)]
 pub struct OpenFile;
 
@@ -34,7 +172,7 @@ impl OpenFile {
         }
         #[cfg(feature = "file_mimetype")]
         {
-            if let Some(mimetype) = entry.metadata.mimetype.as_ref() {
+            if let Some(mimetype) = entry.metadata().and_then(|m| m.mimetype.as_ref()) {
                 let mime_entries = MIMETYPE_T.get_entries_for_mimetype(mimetype.as_str());
                 mimetype_options.extend(mime_entries);
             }
@@ -42,50 +180,242 @@ impl OpenFile {
         mimetype_options
     }
 
-    fn open(context: &mut JoshutoContext, backend: &mut TuiBackend) -> std::io::Result<()> {
+    fn extract(
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+        paths: Vec<path::PathBuf>,
+    ) -> JoshutoResult<()> {
+        if context.readonly {
+            context.message_queue.push_back(format!(
+                "{}: Extracting is disabled in read-only mode",
+                Self::command()
+            ));
+            return Ok(());
+        }
+        let ch = {
+            let prompt_str = format!("Extract {} archives here? (Y/n)", paths.len());
+            let mut prompt = TuiPrompt::new(&prompt_str);
+            prompt.get_key(backend, &context)
+        };
+        if ch != Key::Char('y') && ch != Key::Char('\n') {
+            return Ok(());
+        }
+
+        backend.terminal_drop();
+        let res: std::io::Result<()> = paths.iter().try_for_each(|path| {
+            match archive_extractor(path.as_path()) {
+                /* extracting can rightly take a long time, so it never times out */
+                Some(extractor) => extractor.execute_with(&[path], 0),
+                None => Ok(()),
+            }
+        });
+        backend.terminal_restore()?;
+        res?;
+
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        LoadChild::load_child(context)?;
+        let msg = format!("Extracted {} archives", paths.len());
+        context.message_queue.push_back(msg);
+        Ok(())
+    }
+
+    /*
+     * runs the executable at path from the current directory once confirmed,
+     * posting how it exited
+     */
+    fn run(
+        context: &mut JoshutoContext,
+        backend: &mut TuiBackend,
+        path: path::PathBuf,
+    ) -> JoshutoResult<()> {
+        let name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let ch = {
+            let prompt_str = format!("Run {}? (y/N)", name);
+            let mut prompt = TuiPrompt::new(&prompt_str);
+            prompt.get_key(backend, &context)
+        };
+        if ch != Key::Char('y') {
+            return Ok(());
+        }
+
+        let mut command = match context.config_t.open_executable {
+            OpenExecutableMode::RunShell => {
+                let mut command = process::Command::new("sh");
+                command.arg("-c").arg(RUN_SHELL_SCRIPT).arg("sh").arg(&path);
+                command
+            }
+            _ => process::Command::new(&path),
+        };
+        command.current_dir(&context.curr_tab_ref().curr_path);
+
+        backend.terminal_drop();
+        let res = command.status();
+        backend.terminal_restore()?;
+
+        match res {
+            Ok(status) => {
+                context
+                    .message_queue
+                    .push_back(format!("{}: {}", name, status));
+                Ok(())
+            }
+            Err(e) => Err(JoshutoError::new(
+                JoshutoErrorKind::from(e.kind()),
+                format!("{}: {}: {}", Self::command(), name, e),
+            )),
+        }
+    }
+
+    pub fn open(context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
         let mut dirpath = None;
         let mut selected_entries = None;
+        let mut follow = false;
+        let mut action = OpenAction::Open;
 
         {
+            let config = &context.config_t;
             let curr_tab = context.curr_tab_ref();
             match curr_tab.curr_list_ref() {
                 None => return Ok(()),
                 Some(curr_list) => match curr_list.get_curr_ref() {
                     Some(entry) if entry.file_path().is_dir() => {
-                        let path = entry.file_path().clone();
-                        dirpath = Some(path);
+                        let is_symlink = entry.file_type().is_symlink();
+                        match config.open_symlink_dir {
+                            OpenSymlinkDirMode::OpenWith if is_symlink => {
+                                selected_entries = Some(vec![entry]);
+                                action = OpenAction::OpenWith;
+                            }
+                            mode => {
+                                follow = is_symlink && mode == OpenSymlinkDirMode::Follow;
+                                dirpath = Some(entry.file_path().clone());
+                            }
+                        }
                     }
                     Some(entry) => {
                         let vec: Vec<&JoshutoDirEntry> = curr_list.selected_entries().collect();
-                        if vec.is_empty() {
-                            selected_entries = Some(vec![entry]);
-                        } else {
-                            selected_entries = Some(vec);
+                        let vec = if vec.is_empty() { vec![entry] } else { vec };
+                        if archive_extractor(vec[0].file_path()).is_some() {
+                            action = match config.open_archive {
+                                OpenArchiveMode::Open => OpenAction::Open,
+                                OpenArchiveMode::Extract => OpenAction::Extract,
+                                OpenArchiveMode::OpenWith => OpenAction::OpenWith,
+                            };
+                        } else if vec.len() == 1
+                            && config.open_executable != OpenExecutableMode::Open
+                            && is_executable_file(vec[0].file_path())
+                        {
+                            action = OpenAction::Run;
                         }
+                        selected_entries = Some(vec);
                     }
                     None => return Ok(()),
                 },
             }
         }
 
+        let missing = match (dirpath.as_ref(), selected_entries.as_ref()) {
+            (Some(path), _) => ReloadDirList::find_missing(&[path]),
+            (None, Some(entries)) => {
+                let paths: Vec<&path::PathBuf> = entries.iter().map(|e| e.file_path()).collect();
+                ReloadDirList::find_missing(&paths)
+            }
+            (None, None) => None,
+        };
+        if let Some(path) = missing {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+        /* programs would hang reading a fifo, or a device that never ends */
+        if let Some(entries) = selected_entries.as_ref() {
+            let special = entries
+                .iter()
+                .find_map(|e| FileType::special_target(e.file_path()).map(|t| (e.file_name(), t)));
+            if let Some((name, file_type)) = special {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!(
+                        "{}: Cannot open {}, it is a {}",
+                        Self::command(),
+                        name,
+                        file_type.special_name().unwrap_or_default()
+                    ),
+                ));
+            }
+        }
+
         if let Some(path) = dirpath {
-            ChangeDirectory::cd(path.as_path(), context)?;
-            LoadChild::load_child(context)?;
+            let path = if follow {
+                fs::canonicalize(path.as_path())?
+            } else {
+                path
+            };
+            match context.config_t.open_directory {
+                OpenDirMode::NewTab => NewTab::new_tab_at(path, context)?,
+                /* a followed target can be anywhere, so its ancestors need loading too */
+                OpenDirMode::Enter if follow => {
+                    ChangeDirectory::change_directories(path.as_path(), context)?;
+                    LoadChild::load_child(context)?;
+                }
+                OpenDirMode::Enter => {
+                    ChangeDirectory::cd(path.as_path(), context)?;
+                    LoadChild::load_child(context)?;
+                }
+            }
         } else if let Some(entries) = selected_entries {
-            let options = Self::get_options(entries[0]);
+            if action == OpenAction::Extract {
+                let paths: Vec<path::PathBuf> = entries
+                    .iter()
+                    .map(|e| e.file_path().clone())
+                    .filter(|p| archive_extractor(p.as_path()).is_some())
+                    .collect();
+                return Self::extract(context, backend, paths);
+            }
+            if action == OpenAction::Run {
+                let path = entries[0].file_path().clone();
+                return Self::run(context, backend, path);
+            }
+            /* a program set by the user takes every file at once, like it always did */
+            let configured = context.config_t.open_file_program.as_ref().map(|s| {
+                let mut args_iter = s.split_whitespace();
+                let mut entry =
+                    JoshutoMimetypeEntry::new(args_iter.next().unwrap_or("").to_string());
+                entry.args(args_iter).multi_file(true);
+                entry
+            });
+            let options = match configured.as_ref() {
+                _ if action == OpenAction::OpenWith => Vec::new(),
+                Some(entry) => vec![entry],
+                None => Self::get_options(entries[0]),
+            };
+            /* open_with asks for the program no matter what */
+            let fallbacks: &[String] = match action {
+                OpenAction::OpenWith => &[],
+                _ => &context.config_t.open_fallback,
+            };
             let entry_paths: Vec<&str> = entries.iter().map(|e| e.file_name()).collect();
+            let timeout = context.config_t.open_timeout;
             if !options.is_empty() {
-                let res = if options[0].get_fork() {
-                    options[0].execute_with(entry_paths.as_slice())
-                } else {
-                    backend.terminal_drop();
-                    let res = options[0].execute_with(entry_paths.as_slice());
-                    backend.terminal_restore()?;
-                    res
-                };
-                return res;
+                let together = options[0].get_multi_file()
+                    && (configured.is_some() || opens_all(options[0], &entries));
+                launch(
+                    backend,
+                    options[0],
+                    entry_paths.as_slice(),
+                    together,
+                    timeout,
+                )?;
+            } else if let Some(used) =
+                open_fallback(backend, fallbacks, entry_paths.as_slice(), timeout)
+            {
+                let msg = format!("Opened with fallback {}", used);
+                context.message_queue.push_back(msg);
             } else {
-                OpenFileWith::open_with(context, backend, &entries)?;
+                let ext = JoshutoOpenWithHistory::extension(entries[0].file_path());
+                let command = OpenFileWith::open_with(context, backend, &entries)?;
+                OpenFileWith::remember(context, ext, command);
             }
         }
         Ok(())
@@ -107,26 +437,81 @@ impl JoshutoRunnable for OpenFile {
     }
 }
 
+// an argument of open_file_with standing for the files being opened
+const FILES_PLACEHOLDER: &str = "{}";
+
+/*
+ * opens the selected files with program, or with one chosen
+ * interactively when no program is given
+ */
 #[derive(Clone, Debug)]
-pub struct OpenFileWith;
+pub struct OpenFileWith {
+    program: Vec<String>,
+}
 
 impl OpenFileWith {
-    pub fn new() -> Self {
-        OpenFileWith
+    pub fn new(program: Vec<String>) -> Self {
+        OpenFileWith { program }
     }
     pub const fn command() -> &'static str {
         "open_file_with"
     }
 
+    /* every {} among the arguments is replaced by the files, otherwise they are appended */
+    fn open_with_program(
+        backend: &mut TuiBackend,
+        program: &[String],
+        entries: &[&JoshutoDirEntry],
+        timeout: u64,
+    ) -> std::io::Result<()> {
+        let entry_paths: Vec<&str> = entries.iter().map(|e| e.file_name()).collect();
+        let mut mimetype_entry = JoshutoMimetypeEntry::new(program[0].clone());
+        let args = &program[1..];
+        if args.iter().any(|arg| arg == FILES_PLACEHOLDER) {
+            for arg in args {
+                if arg == FILES_PLACEHOLDER {
+                    mimetype_entry.args(entry_paths.iter().copied());
+                } else {
+                    mimetype_entry.arg(arg.as_str());
+                }
+            }
+            launch(backend, &mimetype_entry, &[], true, timeout)
+        } else {
+            mimetype_entry.args(args.iter().cloned());
+            launch(
+                backend,
+                &mimetype_entry,
+                entry_paths.as_slice(),
+                true,
+                timeout,
+            )
+        }
+    }
+
+    fn remember(context: &mut JoshutoContext, ext: Option<String>, command: Option<String>) {
+        if let (Some(ext), Some(command)) = (ext, command) {
+            context.open_with_history.remember(ext, command);
+        }
+    }
+
+    /*
+     * prompts for a program to open entries with, prefilled with the one
+     * last used for their extension. returns the program that was used
+     */
     pub fn open_with(
         context: &JoshutoContext,
         backend: &mut TuiBackend,
         entries: &[&JoshutoDirEntry],
-    ) -> std::io::Result<()> {
+    ) -> std::io::Result<Option<String>> {
         const PROMPT: &str = "open_with ";
 
         let mimetype_options: Vec<&JoshutoMimetypeEntry> = OpenFile::get_options(&entries[0]);
 
+        let last_used = JoshutoOpenWithHistory::extension(entries[0].file_path())
+            .and_then(|ext| context.open_with_history.get(ext.as_str()))
+            .unwrap_or("");
+        let prefix = format!("{}{}", PROMPT, last_used);
+
         let user_input: Option<String> = {
             let menu_options: Vec<String> = mimetype_options
                 .iter()
@@ -138,7 +523,7 @@ impl OpenFileWith {
 
             let mut textfield = TuiTextField::default()
                 .prompt(":")
-                .prefix(PROMPT)
+                .prefix(prefix.as_str())
                 .menu(menu_widget);
             textfield.get_input(backend, &context)
         };
@@ -154,15 +539,19 @@ impl OpenFileWith {
                         "option does not exist".to_string(),
                     )),
                     Ok(n) => {
-                        let mimetype_entry = &mimetype_options[n];
-                        if mimetype_entry.get_fork() {
-                            mimetype_entry.execute_with(entry_paths.as_slice())
-                        } else {
-                            backend.terminal_drop();
-                            let res = mimetype_entry.execute_with(entry_paths.as_slice());
-                            backend.terminal_restore()?;
-                            res
-                        }
+                        let mimetype_entry = mimetype_options[n];
+                        let together =
+                            mimetype_entry.get_multi_file() && opens_all(mimetype_entry, entries);
+                        launch(
+                            backend,
+                            mimetype_entry,
+                            entry_paths.as_slice(),
+                            together,
+                            context.config_t.open_timeout,
+                        )?;
+                        let mut command = vec![mimetype_entry.get_command().to_string()];
+                        command.extend(mimetype_entry.get_args().iter().cloned());
+                        Ok(Some(command.join(" ")))
                     }
                     Err(_) => {
                         let mut args_iter = user_input.split_whitespace();
@@ -171,16 +560,20 @@ impl OpenFileWith {
                                 backend.terminal_drop();
                                 let res = JoshutoMimetypeEntry::new(String::from(cmd))
                                     .args(args_iter)
-                                    .execute_with(entry_paths.as_slice());
+                                    .execute_with(
+                                        entry_paths.as_slice(),
+                                        context.config_t.open_timeout,
+                                    );
                                 backend.terminal_restore()?;
-                                res
+                                res?;
+                                Ok(Some(user_input.trim().to_string()))
                             }
-                            None => Ok(()),
+                            None => Ok(None),
                         }
                     }
                 }
             }
-            _ => Ok(()),
+            _ => Ok(None),
         }
     }
 }
@@ -189,7 +582,11 @@ impl JoshutoCommand for OpenFileWith {}
 
 impl std::fmt::Display for OpenFileWith {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(Self::command())
+        f.write_str(Self::command())?;
+        for arg in self.program.iter() {
+            write!(f, " {}", escape_arg(arg))?;
+        }
+        Ok(())
     }
 }
 
@@ -219,7 +616,105 @@ impl JoshutoRunnable for OpenFileWith {
                 String::from("No files selected"),
             ));
         }
-        Self::open_with(context, backend, &selected_entries)?;
+        let paths: Vec<&path::PathBuf> = selected_entries.iter().map(|e| e.file_path()).collect();
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+        if !self.program.is_empty() {
+            let timeout = context.config_t.open_timeout;
+            Self::open_with_program(backend, &self.program, &selected_entries, timeout)?;
+            return Ok(());
+        }
+        let ext = JoshutoOpenWithHistory::extension(selected_entries[0].file_path());
+        let command = Self::open_with(context, backend, &selected_entries)?;
+        Self::remember(context, ext, command);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClearOpenWith {
+    ext: Option<String>,
+}
+
+impl ClearOpenWith {
+    pub fn new(ext: Option<String>) -> Self {
+        ClearOpenWith { ext }
+    }
+    pub const fn command() -> &'static str {
+        "clear_open_with"
+    }
+}
+
+impl JoshutoCommand for ClearOpenWith {}
+
+impl std::fmt::Display for ClearOpenWith {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.ext.as_ref() {
+            Some(ext) => write!(f, "{} {}", Self::command(), ext),
+            None => f.write_str(Self::command()),
+        }
+    }
+}
+
+impl JoshutoRunnable for ClearOpenWith {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let msg = match self.ext.as_ref() {
+            Some(ext) => {
+                let ext = ext.trim_start_matches('.').to_lowercase();
+                if context.open_with_history.forget(ext.as_str()) {
+                    format!("Forgot the program used for .{} files", ext)
+                } else {
+                    format!("No program remembered for .{} files", ext)
+                }
+            }
+            None => {
+                context.open_with_history.clear();
+                String::from("Forgot all programs used with open_with")
+            }
+        };
+        context.message_queue.push_back(msg);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpenIndex {
+    index: usize,
+}
+
+impl OpenIndex {
+    pub fn new(index: usize) -> Self {
+        OpenIndex { index }
+    }
+    pub const fn command() -> &'static str {
+        "open_index"
+    }
+}
+
+impl JoshutoCommand for OpenIndex {}
+
+impl std::fmt::Display for OpenIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.index)
+    }
+}
+
+impl JoshutoRunnable for OpenIndex {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let len = match context.curr_tab_ref().curr_list_ref() {
+            Some(curr_list) => curr_list.contents.len(),
+            None => 0,
+        };
+        /* indices are 1-based, as shown by the line numbers */
+        if self.index == 0 || self.index > len {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: index {} out of range", Self::command(), self.index),
+            ));
+        }
+        cursor_move::cursor_move(self.index - 1, context);
+        OpenFile::open(context, backend)?;
         Ok(())
     }
 }
@@ -0,0 +1,92 @@
+use std::path;
+use std::process;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+
+// tried in order when no gui_file_manager is configured
+const GUI_FILE_MANAGERS: [&str; 6] = [
+    "nautilus", "dolphin", "thunar", "nemo", "pcmanfm", "xdg-open",
+];
+
+#[derive(Clone, Debug)]
+pub struct OpenInGui;
+
+impl OpenInGui {
+    pub fn new() -> Self {
+        OpenInGui
+    }
+    pub const fn command() -> &'static str {
+        "open_in_gui"
+    }
+
+    fn in_path(program: &str) -> bool {
+        match std::env::var_os("PATH") {
+            Some(paths) => std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()),
+            None => false,
+        }
+    }
+
+    fn detect() -> Option<String> {
+        GUI_FILE_MANAGERS
+            .iter()
+            .find(|s| Self::in_path(s))
+            .map(|s| s.to_string())
+    }
+
+    // started detached, so it outlives us and stays out of the terminal
+    fn spawn(program: &str, dir: &path::Path) -> std::io::Result<()> {
+        let mut words = program.split_whitespace();
+        let mut command = match words.next() {
+            Some(s) => process::Command::new(s),
+            None => return Ok(()),
+        };
+        command
+            .args(words)
+            .arg(dir)
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null());
+        command.spawn()?;
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for OpenInGui {}
+
+impl std::fmt::Display for OpenInGui {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for OpenInGui {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let program = match context.config_t.gui_file_manager.clone() {
+            Some(s) => s,
+            None => match Self::detect() {
+                Some(s) => s,
+                None => {
+                    return Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!(
+                            "{}: No file manager found ({})",
+                            Self::command(),
+                            GUI_FILE_MANAGERS.join(", ")
+                        ),
+                    ))
+                }
+            },
+        };
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        if let Err(e) = Self::spawn(program.as_str(), curr_path.as_path()) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOOther,
+                format!("{}: {}: {}", Self::command(), program, e),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use std::env;
+use std::path;
+use std::process;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::fs::FileType;
+use crate::ui::TuiBackend;
+
+// used when $PAGER is unset or empty
+const DEFAULT_PAGER: &str = "less";
+
+#[derive(Clone, Debug)]
+pub struct Page;
+
+impl Page {
+    pub fn new() -> Self {
+        Page
+    }
+    pub const fn command() -> &'static str {
+        "page"
+    }
+
+    // $PAGER may carry its own arguments, e.g. "less -R"
+    fn pager() -> String {
+        env::var("PAGER")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_PAGER.to_string())
+    }
+
+    pub fn page(pager: &str, path: &path::Path) -> std::io::Result<()> {
+        let mut words = pager.split_whitespace();
+        let program = words.next().unwrap_or(DEFAULT_PAGER);
+
+        let mut command = process::Command::new(program);
+        command.args(words).arg(path);
+        command.spawn()?.wait()?;
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for Page {}
+
+impl std::fmt::Display for Page {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for Page {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let path = match context
+            .curr_tab_ref()
+            .curr_list_ref()
+            .and_then(|s| s.get_curr_ref())
+        {
+            Some(entry) => entry.file_path().clone(),
+            None => return Ok(()),
+        };
+        if let Some(path) = ReloadDirList::find_missing(&[path.as_path()]) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+        if path.is_dir() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: Cannot page a directory", Self::command()),
+            ));
+        }
+        if let Some(file_type) = FileType::special_target(path.as_path()) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot page a {}",
+                    Self::command(),
+                    file_type.special_name().unwrap_or_default()
+                ),
+            ));
+        }
+
+        let pager = Self::pager();
+        backend.terminal_drop();
+        let res = Self::page(pager.as_str(), path.as_path());
+        backend.terminal_restore()?;
+
+        res.map_err(|e| {
+            JoshutoError::new(
+                JoshutoErrorKind::from(e.kind()),
+                format!("{}: {}: {}", Self::command(), pager, e),
+            )
+        })
+    }
+}
@@ -20,6 +20,7 @@ impl ParentDirectory {
             return Ok(());
         }
         std::env::set_current_dir(&curr_tab.curr_path)?;
+        context.frecency.visit(&curr_tab.curr_path);
         Ok(())
     }
 }
@@ -1,6 +1,9 @@
+use termion::event::Key;
+
 use crate::commands::{JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
 use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::widgets::TuiPrompt;
 use crate::ui::TuiBackend;
 
 #[derive(Clone, Debug)]
@@ -14,16 +17,47 @@ impl Quit {
         "quit"
     }
 
-    pub fn quit(context: &mut JoshutoContext) -> JoshutoResult<()> {
-        if context.worker_busy {
-            Err(JoshutoError::new(
-                JoshutoErrorKind::IOOther,
-                String::from("operations running in background, use force_quit to quit"),
+    /*
+     * with confirm_quit set, asks first when more than one tab is open,
+     * and offers to interrupt file operations that are still running.
+     * those stop wherever they are, leaving the file being written incomplete
+     * and dropping the ones still queued
+     */
+    pub fn quit(context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if !context.config_t.confirm_quit {
+            if context.worker_busy {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOOther,
+                    String::from("operations running in background, use force_quit to quit"),
+                ));
+            }
+            context.exit = true;
+            return Ok(());
+        }
+
+        let prompt_str = if context.worker_busy {
+            let queued = match context.worker_queue.len() {
+                0 => String::new(),
+                n => format!(" ({} more queued)", n),
+            };
+            Some(format!(
+                "A file operation is still running{}, interrupt it and quit? (y/N)",
+                queued
             ))
+        } else if context.tabs.len() > 1 {
+            Some(format!("Quit with {} tabs open? (y/N)", context.tabs.len()))
         } else {
-            context.exit = true;
-            Ok(())
+            None
+        };
+        if let Some(prompt_str) = prompt_str {
+            let mut prompt = TuiPrompt::new(&prompt_str);
+            if prompt.get_key(backend, &context) != Key::Char('y') {
+                return Ok(());
+            }
+            context.worker_queue.clear();
         }
+        context.exit = true;
+        Ok(())
     }
 }
 
@@ -36,8 +70,8 @@ impl std::fmt::Display for Quit {
 }
 
 impl JoshutoRunnable for Quit {
-    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
-        Self::quit(context)
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        Self::quit(context, backend)
     }
 }
 
@@ -1,7 +1,14 @@
+use std::fs;
+use std::path;
+use std::thread;
+
 use crate::commands::{JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::fs::JoshutoDirList;
+use crate::history::DirectoryHistory;
 use crate::ui::TuiBackend;
+use crate::util::event::Event;
 use crate::util::load_child::LoadChild;
 
 #[derive(Clone, Debug)]
@@ -54,6 +61,44 @@ impl ReloadDirList {
 
         Ok(())
     }
+
+    /*
+     * reloads path in every tab that has it listed, so tabs showing the
+     * same directory stay consistent once files in it change. cursors and
+     * selections carry over
+     */
+    pub fn reload_path(path: &path::Path, context: &mut JoshutoContext) -> std::io::Result<()> {
+        let sort_option = &context.config_t.sort_option;
+        for tab in context.tabs.iter_mut() {
+            tab.history.reload(path, sort_option)?;
+        }
+        Ok(())
+    }
+
+    // the first of paths that no longer exists on disk, broken symlinks still count
+    pub fn find_missing<P: AsRef<path::Path>>(paths: &[P]) -> Option<path::PathBuf> {
+        paths
+            .iter()
+            .find(|p| fs::symlink_metadata(p.as_ref()).is_err())
+            .map(|p| p.as_ref().to_path_buf())
+    }
+
+    /*
+     * for entries removed since the directory was listed, reloads the
+     * current tab so they disappear and returns an error explaining why
+     * the command did nothing
+     */
+    pub fn stale_entry(path: &path::Path, context: &mut JoshutoContext) -> JoshutoError {
+        let _ = Self::reload(context.curr_tab_index, context);
+        let _ = LoadChild::load_child(context);
+        JoshutoError::new(
+            JoshutoErrorKind::IONotFound,
+            format!(
+                "{} no longer exists, reloaded directory",
+                path.to_string_lossy()
+            ),
+        )
+    }
 }
 
 impl JoshutoCommand for ReloadDirList {}
@@ -71,3 +116,120 @@ impl JoshutoRunnable for ReloadDirList {
         Ok(())
     }
 }
+
+#[derive(Clone, Debug)]
+pub struct ReloadAll;
+
+impl ReloadAll {
+    pub fn new() -> Self {
+        ReloadAll
+    }
+    pub const fn command() -> &'static str {
+        "reload_all"
+    }
+}
+
+impl JoshutoCommand for ReloadAll {}
+
+impl std::fmt::Display for ReloadAll {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for ReloadAll {
+    /*
+     * directories are read on separate threads, every tab showing one of
+     * them is updated once the event with its contents arrives
+     */
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let mut paths: Vec<(path::PathBuf, Option<usize>)> = context
+            .tabs
+            .iter()
+            .map(|t| {
+                let flatten_depth = t.curr_list_ref().and_then(|s| s.flatten_depth);
+                (t.curr_path.clone(), flatten_depth)
+            })
+            .collect();
+        paths.sort();
+        paths.dedup();
+
+        for (path, flatten_depth) in paths {
+            let event_tx = context.events.event_tx.clone();
+            let sort_option = context.config_t.sort_option.clone();
+            thread::spawn(move || {
+                let contents = JoshutoDirList::read(path.as_path(), flatten_depth, &sort_option);
+                let _ = event_tx.send(Event::DirReload(path, flatten_depth, contents));
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::DeleteFiles;
+    use crate::config::JoshutoConfig;
+    use crate::tab::JoshutoTab;
+    use crate::util::test_dir::TempDir;
+
+    #[test]
+    fn deleting_refreshes_every_tab_in_the_directory() {
+        let dir = TempDir::new();
+        for name in &["a", "b", "c"] {
+            dir.touch(name);
+        }
+
+        let mut context = JoshutoContext::without_state(JoshutoConfig::default());
+        let options = context.config_t.sort_option.clone();
+        for _ in 0..2 {
+            let tab = JoshutoTab::new(dir.path().to_path_buf(), &options).unwrap();
+            context.push_tab(tab);
+        }
+        /* the other tab has its cursor on c and c selected */
+        {
+            let list = context.tabs[1].curr_list_mut().unwrap();
+            list.index = Some(2);
+            list.contents[2].set_selected(true);
+        }
+
+        context.curr_tab_index = 0;
+        let deleted = dir.path().join("a");
+        DeleteFiles::remove_files(&[&deleted]);
+        ReloadDirList::reload_path(dir.path(), &mut context).unwrap();
+
+        for tab in context.tabs.iter() {
+            let list = tab.curr_list_ref().unwrap();
+            let names: Vec<&str> = list.contents.iter().map(|e| e.file_name()).collect();
+            assert_eq!(names, vec!["b", "c"]);
+        }
+        let list = context.tabs[1].curr_list_ref().unwrap();
+        assert_eq!(list.get_curr_ref().map(|e| e.file_name()), Some("c"));
+        let selected: Vec<&str> = list.selected_entries().map(|e| e.file_name()).collect();
+        assert_eq!(selected, vec!["c"]);
+    }
+
+    #[test]
+    fn find_missing_reports_deleted_paths() {
+        let dir = TempDir::new();
+        let kept = dir.touch("kept");
+        let deleted = dir.touch("deleted");
+        assert_eq!(ReloadDirList::find_missing(&[&kept, &deleted]), None);
+
+        fs::remove_file(&deleted).unwrap();
+        assert_eq!(
+            ReloadDirList::find_missing(&[&kept, &deleted]),
+            Some(deleted)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn find_missing_keeps_broken_symlinks() {
+        let dir = TempDir::new();
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink("nowhere", &link).unwrap();
+        assert_eq!(ReloadDirList::find_missing(&[&link]), None);
+    }
+}
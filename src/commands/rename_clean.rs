@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::path;
+
+use crate::commands::{check_writable, JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct RenameClean {
+    dry_run: bool,
+}
+
+impl RenameClean {
+    pub fn new(dry_run: bool) -> Self {
+        RenameClean { dry_run }
+    }
+    pub const fn command() -> &'static str {
+        "rename_clean"
+    }
+
+    /*
+     * replaces whitespace with the separator, strips everything that is not
+     * alphanumeric or one of '.', '-', '_' and collapses repeated punctuation
+     */
+    pub fn clean_file_name(file_name: &str, separator: &str) -> String {
+        let mut cleaned = String::with_capacity(file_name.len());
+        for ch in file_name.chars() {
+            if ch.is_whitespace() {
+                cleaned.push_str(separator);
+            } else if ch.is_alphanumeric() || ch == '.' || ch == '-' || ch == '_' {
+                cleaned.push(ch);
+            }
+        }
+
+        let mut collapsed = String::with_capacity(cleaned.len());
+        let mut prev: Option<char> = None;
+        for ch in cleaned.chars() {
+            let is_punct = !ch.is_alphanumeric();
+            if is_punct && prev == Some(ch) {
+                continue;
+            }
+            collapsed.push(ch);
+            prev = Some(ch);
+        }
+
+        collapsed
+            .trim_matches(|c: char| c == '-' || c == '_' || separator.contains(c))
+            .to_string()
+    }
+
+    fn wait_for_enter() -> std::io::Result<()> {
+        print!("Press ENTER to continue...");
+        std::io::stdout().flush()?;
+        let mut user_input = String::new();
+        std::io::stdin().read_line(&mut user_input)?;
+        Ok(())
+    }
+
+    pub fn rename_clean(&self, context: &mut JoshutoContext) -> JoshutoResult<()> {
+        let separator = context.config_t.rename_clean_separator.clone();
+        let curr_tab = &context.tabs[context.curr_tab_index];
+        let paths = match curr_tab.curr_list_ref() {
+            Some(s) => s.get_selected_paths(),
+            None => Vec::new(),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                String::from("No files selected"),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+
+        let mut renames: Vec<(&path::PathBuf, path::PathBuf)> = Vec::new();
+        for path in paths.iter() {
+            let file_name = match path.file_name().and_then(|s| s.to_str()) {
+                Some(s) => s,
+                None => continue,
+            };
+            let cleaned = Self::clean_file_name(file_name, separator.as_str());
+            if cleaned.is_empty() {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!("{}: nothing left of {}", Self::command(), file_name),
+                ));
+            }
+            if cleaned != file_name {
+                renames.push((path, path.with_file_name(cleaned)));
+            }
+        }
+        if renames.is_empty() {
+            context
+                .message_queue
+                .push_back(String::from("File names are already clean"));
+            return Ok(());
+        }
+
+        /*
+         * sanitizing can map different names onto the same one. a name
+         * that is taken is only free if its file was renamed away earlier
+         * in the batch, renames happen in order
+         */
+        let mut renamed_away: HashSet<&path::PathBuf> = HashSet::new();
+        let mut targets: HashSet<&path::PathBuf> = HashSet::new();
+        let mut collisions: Vec<(&path::PathBuf, &path::PathBuf)> = Vec::new();
+        for (p, q) in renames.iter() {
+            let exists = q.exists() && !renamed_away.contains(q);
+            if exists || !targets.insert(q) {
+                collisions.push((*p, q));
+            }
+            renamed_away.insert(*p);
+        }
+
+        if !collisions.is_empty() {
+            println!("Aborting, the following names collide:");
+            for (p, q) in collisions.iter() {
+                println!("{:?} -> {:?}", p, q);
+            }
+            Self::wait_for_enter()?;
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOAlreadyExists,
+                format!("{}: {} name collisions", Self::command(), collisions.len()),
+            ));
+        }
+
+        for (p, q) in renames.iter() {
+            println!("{:?} -> {:?}", p, q);
+        }
+        if self.dry_run {
+            println!("Dry run, no files were renamed");
+            Self::wait_for_enter()?;
+            return Ok(());
+        }
+        print!("Continue with rename? (Y/n): ");
+        std::io::stdout().flush()?;
+
+        let mut user_input = String::with_capacity(4);
+        std::io::stdin().read_line(&mut user_input)?;
+        let user_input = user_input.trim().to_lowercase();
+        if user_input == "n" || user_input == "no" {
+            return Ok(());
+        }
+
+        for (p, q) in renames.iter() {
+            std::fs::rename(p, q)?;
+        }
+        let msg = format!("Renamed {} files", renames.len());
+        context.message_queue.push_back(msg);
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for RenameClean {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for RenameClean {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.dry_run {
+            f.write_str(" --dry-run")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for RenameClean {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        check_writable(Self::command(), context)?;
+        backend.terminal_drop();
+        let res = self.rename_clean(context);
+        backend.terminal_restore()?;
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        res
+    }
+}
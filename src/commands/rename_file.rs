@@ -1,11 +1,96 @@
 use std::path;
 
-use crate::commands::{CommandLine, JoshutoCommand, JoshutoRunnable};
+use termion::event::Key;
+
+use crate::commands::{self, CommandLine, JoshutoCommand, JoshutoRunnable, ReloadDirList};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::io::IOResults;
+use crate::ui::widgets::TuiPrompt;
 use crate::ui::TuiBackend;
 use crate::util::load_child::LoadChild;
 
+/*
+ * renames every selected entry to what rename gives for its name,
+ * after listing the changes and getting an explicit y.
+ * nothing is renamed if two entries would end up with the same name
+ * or a new name is already taken
+ */
+fn rename_selected<F>(
+    command: &str,
+    context: &mut JoshutoContext,
+    backend: &mut TuiBackend,
+    rename: F,
+) -> JoshutoResult<()>
+where
+    F: Fn(&str) -> String,
+{
+    commands::check_writable(command, context)?;
+    let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+        Some(list) => list.get_selected_paths().into_iter().cloned().collect(),
+        None => return Ok(()),
+    };
+    if let Some(path) = ReloadDirList::find_missing(&paths) {
+        return Err(ReloadDirList::stale_entry(path.as_path(), context));
+    }
+
+    let mut renames: Vec<(path::PathBuf, path::PathBuf)> = Vec::with_capacity(paths.len());
+    for path in paths {
+        let file_name = match path.file_name() {
+            Some(s) => s.to_string_lossy().to_string(),
+            None => continue,
+        };
+        let new_path = path.with_file_name(rename(file_name.as_str()));
+        if new_path.exists() || renames.iter().any(|(_, q)| *q == new_path) {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!(
+                    "{}: Cannot rename {}, {} already exists",
+                    command,
+                    file_name,
+                    new_path.file_name().unwrap_or_default().to_string_lossy()
+                ),
+            ));
+        }
+        renames.push((path, new_path));
+    }
+    if renames.is_empty() {
+        return Ok(());
+    }
+
+    let ch = {
+        let lines: Vec<String> = renames
+            .iter()
+            .map(|(p, q)| {
+                format!(
+                    "  {} -> {}",
+                    p.file_name().unwrap_or_default().to_string_lossy(),
+                    q.file_name().unwrap_or_default().to_string_lossy()
+                )
+            })
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(|s| s.as_str()).collect();
+        let prompt_str = format!("Rename {} entries? (y/N)", renames.len());
+        let mut prompt = TuiPrompt::new(&prompt_str).menu(&lines);
+        prompt.get_key(backend, &context)
+    };
+    if ch != Key::Char('y') {
+        return Ok(());
+    }
+
+    let mut results = IOResults::new("rename");
+    for (p, q) in renames.iter() {
+        results.push(p, std::fs::rename(p, q));
+    }
+
+    let curr_path = context.tabs[context.curr_tab_index].curr_path.clone();
+    ReloadDirList::reload_path(&curr_path, context)?;
+    LoadChild::load_child(context)?;
+    let msg = format!("Renamed {} entries", renames.len());
+    commands::report_results(&results, msg, context, backend);
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct RenameFile {
     path: path::PathBuf,
@@ -39,16 +124,26 @@ impl RenameFile {
     }
 }
 
-impl JoshutoCommand for RenameFile {}
+impl JoshutoCommand for RenameFile {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for RenameFile {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", Self::command())
+        write!(
+            f,
+            "{} {}",
+            Self::command(),
+            commands::escape_arg(&self.path.to_string_lossy())
+        )
     }
 }
 
 impl JoshutoRunnable for RenameFile {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        commands::check_writable(Self::command(), context)?;
         let mut path: Option<path::PathBuf> = None;
 
         if let Some(curr_list) = context.curr_tab_ref().curr_list_ref() {
@@ -58,6 +153,9 @@ impl JoshutoRunnable for RenameFile {
         }
 
         if let Some(path) = path {
+            if ReloadDirList::find_missing(&[&path]).is_some() {
+                return Err(ReloadDirList::stale_entry(path.as_path(), context));
+            }
             self.rename_file(&path, context)?;
         }
         LoadChild::load_child(context)?;
@@ -66,11 +164,13 @@ impl JoshutoRunnable for RenameFile {
 }
 
 #[derive(Clone, Debug)]
-pub struct RenameFileAppend;
+pub struct RenameFileAppend {
+    text: Option<String>,
+}
 
 impl RenameFileAppend {
-    pub fn new() -> Self {
-        RenameFileAppend {}
+    pub fn new(text: Option<String>) -> Self {
+        RenameFileAppend { text }
     }
     pub const fn command() -> &'static str {
         "rename_append"
@@ -85,10 +185,10 @@ impl RenameFileAppend {
         let prefix;
         let suffix;
         if let Some(ext) = file_name.rfind('.') {
-            prefix = format!("rename {}", &file_name[0..ext]);
-            suffix = String::from(&file_name[ext..]);
+            prefix = format!("rename {}", commands::escape_arg(&file_name[0..ext]));
+            suffix = commands::escape_arg(&file_name[ext..]);
         } else {
-            prefix = format!("rename {}", file_name);
+            prefix = format!("rename {}", commands::escape_arg(file_name));
             suffix = String::new();
         }
 
@@ -97,16 +197,34 @@ impl RenameFileAppend {
     }
 }
 
-impl JoshutoCommand for RenameFileAppend {}
+impl JoshutoCommand for RenameFileAppend {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for RenameFileAppend {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", Self::command())
+        match self.text.as_ref() {
+            Some(s) => write!(f, "{} {}", Self::command(), commands::escape_arg(s)),
+            None => write!(f, "{}", Self::command()),
+        }
     }
 }
 
 impl JoshutoRunnable for RenameFileAppend {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if let Some(text) = self.text.as_ref() {
+            /* goes before the extension, a leading dot doesn't start one */
+            return rename_selected(Self::command(), context, backend, |name| {
+                match name.rfind('.') {
+                    Some(ext) if ext > 0 => format!("{}{}{}", &name[..ext], text, &name[ext..]),
+                    _ => format!("{}{}", name, text),
+                }
+            });
+        }
+
+        commands::check_writable(Self::command(), context)?;
         let mut file_name: Option<String> = None;
 
         if let Some(curr_list) = context.curr_tab_ref().curr_list_ref() {
@@ -122,11 +240,13 @@ impl JoshutoRunnable for RenameFileAppend {
 }
 
 #[derive(Clone, Debug)]
-pub struct RenameFilePrepend;
+pub struct RenameFilePrepend {
+    text: Option<String>,
+}
 
 impl RenameFilePrepend {
-    pub fn new() -> Self {
-        RenameFilePrepend {}
+    pub fn new(text: Option<String>) -> Self {
+        RenameFilePrepend { text }
     }
     pub const fn command() -> &'static str {
         "rename_prepend"
@@ -139,23 +259,37 @@ impl RenameFilePrepend {
         file_name: String,
     ) -> JoshutoResult<()> {
         let prefix = String::from("rename ");
-        let suffix = file_name;
+        let suffix = commands::escape_arg(file_name.as_str());
 
         let command = CommandLine::new(prefix, suffix);
         command.readline(context, backend)
     }
 }
 
-impl JoshutoCommand for RenameFilePrepend {}
+impl JoshutoCommand for RenameFilePrepend {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for RenameFilePrepend {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", Self::command())
+        match self.text.as_ref() {
+            Some(s) => write!(f, "{} {}", Self::command(), commands::escape_arg(s)),
+            None => write!(f, "{}", Self::command()),
+        }
     }
 }
 
 impl JoshutoRunnable for RenameFilePrepend {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        if let Some(text) = self.text.as_ref() {
+            return rename_selected(Self::command(), context, backend, |name| {
+                format!("{}{}", text, name)
+            });
+        }
+
+        commands::check_writable(Self::command(), context)?;
         let mut file_name: Option<String> = None;
 
         if let Some(curr_list) = context.curr_tab_ref().curr_list_ref() {
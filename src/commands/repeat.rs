@@ -0,0 +1,45 @@
+use crate::commands::{run_command, JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct Repeat;
+
+impl Repeat {
+    pub fn new() -> Self {
+        Repeat
+    }
+    pub const fn command() -> &'static str {
+        "repeat"
+    }
+}
+
+impl JoshutoCommand for Repeat {
+    fn is_repeatable(&self) -> bool {
+        false
+    }
+}
+
+impl std::fmt::Display for Repeat {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for Repeat {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        /* commands read the cursor and selection when run, so this acts on the current state */
+        match context.last_command.take() {
+            Some(command) => {
+                let res = run_command(command.as_ref(), context, backend);
+                context.last_command = Some(command);
+                res
+            }
+            None => Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: No command to repeat", Self::command()),
+            )),
+        }
+    }
+}
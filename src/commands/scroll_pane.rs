@@ -0,0 +1,85 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::tab::SidePane;
+use crate::ui::TuiBackend;
+
+/*
+ * scrolls the parent or child listing to peek further along it,
+ * leaving every cursor where it is
+ */
+#[derive(Clone, Debug)]
+pub struct ScrollPaneDown {
+    pane: SidePane,
+    movement: usize,
+}
+
+impl ScrollPaneDown {
+    pub fn new(pane: SidePane, movement: usize) -> Self {
+        Self { pane, movement }
+    }
+    pub const fn command() -> &'static str {
+        "scroll_pane_down"
+    }
+}
+
+impl JoshutoCommand for ScrollPaneDown {}
+
+impl std::fmt::Display for ScrollPaneDown {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            Self::command(),
+            self.pane.name(),
+            self.movement
+        )
+    }
+}
+
+impl JoshutoRunnable for ScrollPaneDown {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        context
+            .curr_tab_mut()
+            .scroll_pane(self.pane, self.movement as isize);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ScrollPaneUp {
+    pane: SidePane,
+    movement: usize,
+}
+
+impl ScrollPaneUp {
+    pub fn new(pane: SidePane, movement: usize) -> Self {
+        Self { pane, movement }
+    }
+    pub const fn command() -> &'static str {
+        "scroll_pane_up"
+    }
+}
+
+impl JoshutoCommand for ScrollPaneUp {}
+
+impl std::fmt::Display for ScrollPaneUp {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} {}",
+            Self::command(),
+            self.pane.name(),
+            self.movement
+        )
+    }
+}
+
+impl JoshutoRunnable for ScrollPaneUp {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        context
+            .curr_tab_mut()
+            .scroll_pane(self.pane, -(self.movement as isize));
+        Ok(())
+    }
+}
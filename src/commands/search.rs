@@ -1,7 +1,7 @@
 use lazy_static::lazy_static;
 use std::sync::Mutex;
 
-use crate::commands::{cursor_move, JoshutoCommand, JoshutoRunnable};
+use crate::commands::{cursor_move, escape_arg, JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
 use crate::error::JoshutoResult;
 use crate::tab::JoshutoTab;
@@ -11,6 +11,19 @@ lazy_static! {
     static ref SEARCH_PATTERN: Mutex<Option<String>> = Mutex::new(None);
 }
 
+// smart case: only match case sensitively if the pattern has an uppercase letter
+pub fn case_sensitive(pattern: &str) -> bool {
+    pattern.chars().any(|c| c.is_uppercase())
+}
+
+fn name_matches(file_name: &str, pattern: &str) -> bool {
+    if case_sensitive(pattern) {
+        file_name.contains(pattern)
+    } else {
+        file_name.to_lowercase().contains(pattern)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Search {
     pattern: String,
@@ -19,7 +32,7 @@ pub struct Search {
 impl Search {
     pub fn new(pattern: &str) -> Self {
         Search {
-            pattern: pattern.to_lowercase(),
+            pattern: pattern.to_string(),
         }
     }
     pub const fn command() -> &'static str {
@@ -31,10 +44,8 @@ impl Search {
         let offset = curr_list.index? + 1;
         let contents_len = curr_list.contents.len();
         for i in 0..contents_len {
-            let file_name_lower = curr_list.contents[(offset + i) % contents_len]
-                .file_name()
-                .to_lowercase();
-            if file_name_lower.contains(pattern) {
+            let file_name = curr_list.contents[(offset + i) % contents_len].file_name();
+            if name_matches(file_name, pattern) {
                 return Some((offset + i) % contents_len);
             }
         }
@@ -46,10 +57,8 @@ impl Search {
         let offset = curr_list.index?;
         let contents_len = curr_list.contents.len();
         for i in (0..contents_len).rev() {
-            let file_name_lower = curr_list.contents[(offset + i) % contents_len]
-                .file_name()
-                .to_lowercase();
-            if file_name_lower.contains(pattern) {
+            let file_name = curr_list.contents[(offset + i) % contents_len].file_name();
+            if name_matches(file_name, pattern) {
                 return Some((offset + i) % contents_len);
             }
         }
@@ -61,7 +70,7 @@ impl JoshutoCommand for Search {}
 
 impl std::fmt::Display for Search {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} {}", Self::command(), self.pattern)
+        write!(f, "{} {}", Self::command(), escape_arg(&self.pattern))
     }
 }
 
@@ -150,3 +159,15 @@ impl JoshutoRunnable for SearchPrev {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn uppercase_makes_the_search_case_sensitive() {
+        assert!(name_matches("README.md", "readme"));
+        assert!(name_matches("README.md", "README"));
+        assert!(!name_matches("readme.md", "README"));
+    }
+}
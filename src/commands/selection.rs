@@ -1,17 +1,30 @@
-use crate::commands::{CursorMoveDown, JoshutoCommand, JoshutoRunnable};
+use globset::GlobMatcher;
+use regex::{Regex, RegexBuilder};
+
+use crate::commands::{escape_arg, search, CursorMoveDown, JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::fs::{FileType, JoshutoDirEntry};
 use crate::ui::TuiBackend;
+use crate::util::unix;
 
 #[derive(Debug, Clone)]
 pub struct SelectFiles {
     toggle: bool,
     all: bool,
+    advance: bool,
+    // acts on every entry whose name matches, as if --all was given
+    pattern: Option<GlobMatcher>,
 }
 
 impl SelectFiles {
-    pub fn new(toggle: bool, all: bool) -> Self {
-        SelectFiles { toggle, all }
+    pub fn new(toggle: bool, all: bool, advance: bool, pattern: Option<GlobMatcher>) -> Self {
+        SelectFiles {
+            toggle,
+            all,
+            advance,
+            pattern,
+        }
     }
     pub const fn command() -> &'static str {
         "select_files"
@@ -29,6 +42,12 @@ impl std::fmt::Display for SelectFiles {
         if self.all {
             f.write_str(" --all").unwrap();
         }
+        if !self.advance {
+            f.write_str(" --no-advance").unwrap();
+        }
+        if let Some(pattern) = self.pattern.as_ref() {
+            write!(f, " --pattern {}", escape_arg(pattern.glob().glob())).unwrap();
+        }
         f.write_str("")
     }
 }
@@ -36,13 +55,24 @@ impl std::fmt::Display for SelectFiles {
 impl JoshutoRunnable for SelectFiles {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
         let curr_tab = &mut context.tabs[context.curr_tab_index];
-        if self.toggle {
+        if let Some(pattern) = self.pattern.as_ref() {
+            if let Some(curr_list) = curr_tab.curr_list_mut() {
+                for curr in curr_list.contents.iter_mut() {
+                    if pattern.is_match(curr.file_name()) {
+                        let selected = !self.toggle || !curr.is_selected();
+                        curr.set_selected(selected);
+                    }
+                }
+            }
+        } else if self.toggle {
             if !self.all {
                 let curr_list = curr_tab.curr_list_mut();
                 if let Some(curr_list) = curr_list {
                     if let Some(s) = curr_list.get_curr_mut() {
                         s.set_selected(!s.is_selected());
-                        CursorMoveDown::new(1).execute(context, backend)?;
+                        if self.advance {
+                            CursorMoveDown::new(1).execute(context, backend)?;
+                        }
                     }
                 }
             } else {
@@ -58,7 +88,9 @@ impl JoshutoRunnable for SelectFiles {
             if let Some(curr_list) = curr_list {
                 if let Some(s) = curr_list.get_curr_mut() {
                     s.set_selected(!s.is_selected());
-                    CursorMoveDown::new(1).execute(context, backend)?;
+                    if self.advance {
+                        CursorMoveDown::new(1).execute(context, backend)?;
+                    }
                 }
             }
         } else {
@@ -72,3 +104,344 @@ impl JoshutoRunnable for SelectFiles {
         Ok(())
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SelectEntryType {
+    Dir,
+    File,
+    Symlink,
+    Exec,
+}
+
+impl SelectEntryType {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "dir" => Some(SelectEntryType::Dir),
+            "file" => Some(SelectEntryType::File),
+            "symlink" => Some(SelectEntryType::Symlink),
+            "exec" => Some(SelectEntryType::Exec),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        match *self {
+            SelectEntryType::Dir => "dir",
+            SelectEntryType::File => "file",
+            SelectEntryType::Symlink => "symlink",
+            SelectEntryType::Exec => "exec",
+        }
+    }
+
+    fn matches(&self, entry: &JoshutoDirEntry) -> bool {
+        match (*self, entry.file_type()) {
+            (SelectEntryType::Dir, FileType::Directory) => true,
+            (SelectEntryType::File, FileType::File) => true,
+            (SelectEntryType::Symlink, FileType::Symlink(_)) => true,
+            (SelectEntryType::Exec, FileType::File) => entry
+                .metadata()
+                .map(|m| unix::is_executable(m.mode))
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectType {
+    entry_type: SelectEntryType,
+    toggle: bool,
+    unselect: bool,
+}
+
+impl SelectType {
+    pub fn new(entry_type: SelectEntryType, toggle: bool, unselect: bool) -> Self {
+        SelectType {
+            entry_type,
+            toggle,
+            unselect,
+        }
+    }
+    pub const fn command() -> &'static str {
+        "select_type"
+    }
+}
+
+impl JoshutoCommand for SelectType {}
+
+impl std::fmt::Display for SelectType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.entry_type.as_str())?;
+        if self.toggle {
+            f.write_str(" --toggle")?;
+        }
+        if self.unselect {
+            f.write_str(" --unselect")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for SelectType {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            /* telling executables apart needs the permission bits */
+            if self.entry_type == SelectEntryType::Exec {
+                curr_list.load_metadata(0..curr_list.contents.len());
+            }
+            for curr in curr_list.contents.iter_mut() {
+                if !self.entry_type.matches(curr) {
+                    continue;
+                }
+                let selected = if self.toggle {
+                    !curr.is_selected()
+                } else {
+                    !self.unselect
+                };
+                curr.set_selected(selected);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectClear;
+
+impl SelectClear {
+    pub fn new() -> Self {
+        SelectClear
+    }
+    pub const fn command() -> &'static str {
+        "select_clear"
+    }
+}
+
+impl JoshutoCommand for SelectClear {}
+
+impl std::fmt::Display for SelectClear {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for SelectClear {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            for curr in curr_list.contents.iter_mut() {
+                curr.set_selected(false);
+            }
+        }
+        Ok(())
+    }
+}
+
+// flips the marked flag of the entry under the cursor, or of every entry,
+// without touching the selection
+#[derive(Debug, Clone)]
+pub struct MarkFiles {
+    all: bool,
+}
+
+impl MarkFiles {
+    pub fn new(all: bool) -> Self {
+        MarkFiles { all }
+    }
+    pub const fn command() -> &'static str {
+        "mark_files"
+    }
+}
+
+impl JoshutoCommand for MarkFiles {}
+
+impl std::fmt::Display for MarkFiles {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.all {
+            f.write_str(" --all")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for MarkFiles {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            if self.all {
+                for curr in curr_list.contents.iter_mut() {
+                    curr.set_marked(!curr.is_marked());
+                }
+            } else if let Some(s) = curr_list.get_curr_mut() {
+                s.set_marked(!s.is_marked());
+                CursorMoveDown::new(1).execute(context, backend)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// flips the selection of hidden entries only, leaving the rest as they are
+#[derive(Debug, Clone)]
+pub struct SelectToggleHidden;
+
+impl SelectToggleHidden {
+    pub fn new() -> Self {
+        SelectToggleHidden
+    }
+    pub const fn command() -> &'static str {
+        "select_toggle_hidden"
+    }
+}
+
+impl JoshutoCommand for SelectToggleHidden {}
+
+impl std::fmt::Display for SelectToggleHidden {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for SelectToggleHidden {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            for curr in curr_list.contents.iter_mut() {
+                if curr.file_name().starts_with('.') {
+                    curr.set_selected(!curr.is_selected());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectSameExtension {
+    toggle: bool,
+}
+
+impl SelectSameExtension {
+    pub fn new(toggle: bool) -> Self {
+        SelectSameExtension { toggle }
+    }
+    pub const fn command() -> &'static str {
+        "select_same_extension"
+    }
+
+    // compared ignoring case, so a.jpg and b.JPG count as the same kind
+    fn extension(entry: &JoshutoDirEntry) -> Option<String> {
+        entry
+            .file_path()
+            .extension()
+            .map(|s| s.to_string_lossy().to_lowercase())
+    }
+}
+
+impl JoshutoCommand for SelectSameExtension {}
+
+impl std::fmt::Display for SelectSameExtension {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.toggle {
+            f.write_str(" --toggle")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for SelectSameExtension {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            let extension = match curr_list.get_curr_ref() {
+                Some(curr) => match Self::extension(curr) {
+                    Some(s) => s,
+                    None => {
+                        return Err(JoshutoError::new(
+                            JoshutoErrorKind::IOInvalidData,
+                            format!("{}: {} has no extension", Self::command(), curr.file_name()),
+                        ))
+                    }
+                },
+                None => return Ok(()),
+            };
+            for curr in curr_list.contents.iter_mut() {
+                if Self::extension(curr).as_ref() != Some(&extension) {
+                    continue;
+                }
+                let selected = !self.toggle || !curr.is_selected();
+                curr.set_selected(selected);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SelectByRegex {
+    pattern: String,
+    regex: Regex,
+    toggle: bool,
+    unselect: bool,
+}
+
+impl SelectByRegex {
+    pub fn new(pattern: &str, toggle: bool, unselect: bool) -> JoshutoResult<Self> {
+        /* the same smart case as search */
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(!search::case_sensitive(pattern))
+            .build()
+            .map_err(|e| {
+                JoshutoError::new(
+                    JoshutoErrorKind::ParseError,
+                    format!("{}: {}", Self::command(), e),
+                )
+            })?;
+        Ok(SelectByRegex {
+            pattern: pattern.to_string(),
+            regex,
+            toggle,
+            unselect,
+        })
+    }
+    pub const fn command() -> &'static str {
+        "select_by_regex"
+    }
+}
+
+impl JoshutoCommand for SelectByRegex {}
+
+impl std::fmt::Display for SelectByRegex {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), escape_arg(&self.pattern))?;
+        if self.toggle {
+            f.write_str(" --toggle")?;
+        }
+        if self.unselect {
+            f.write_str(" --unselect")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for SelectByRegex {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            for curr in curr_list.contents.iter_mut() {
+                if !self.regex.is_match(curr.file_name()) {
+                    continue;
+                }
+                let selected = if self.toggle {
+                    !curr.is_selected()
+                } else {
+                    !self.unselect
+                };
+                curr.set_selected(selected);
+            }
+        }
+        Ok(())
+    }
+}
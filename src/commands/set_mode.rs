@@ -40,7 +40,11 @@ impl SetMode {
     }
 }
 
-impl JoshutoCommand for SetMode {}
+impl JoshutoCommand for SetMode {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for SetMode {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
@@ -54,13 +58,20 @@ impl JoshutoRunnable for SetMode {
 
         const PREFIX: &str = "set_mode ";
 
+        let entry = context.tabs[context.curr_tab_index]
+            .curr_list_mut()
+            .and_then(|x| x.get_curr_mut());
+        if let Some(entry) = entry {
+            entry.load_metadata()?;
+        }
+
         let entry = context.tabs[context.curr_tab_index]
             .curr_list_ref()
             .and_then(|x| x.get_curr_ref());
 
-        let user_input = match entry {
-            Some(entry) => {
-                let mode = entry.metadata.permissions.mode();
+        let user_input = match entry.and_then(|e| e.metadata()) {
+            Some(metadata) => {
+                let mode = metadata.permissions.mode();
                 let mode_string = unix::stringify_mode(mode);
                 let mut textfield = TuiTextField::default()
                     .prompt(":")
@@ -82,7 +93,9 @@ impl JoshutoRunnable for SetMode {
                     .unwrap();
 
                 unix::set_mode(entry.file_path().as_path(), mode);
-                entry.metadata.permissions.set_mode(mode);
+                if let Some(metadata) = entry.metadata_mut() {
+                    metadata.permissions.set_mode(mode);
+                }
                 CursorMoveDown::new(1).execute(context, backend)?;
             }
         }
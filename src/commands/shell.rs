@@ -5,6 +5,7 @@ use crate::context::JoshutoContext;
 use crate::error::JoshutoResult;
 use crate::ui::widgets::TuiTextField;
 use crate::ui::TuiBackend;
+use crate::util::process::wait_timeout;
 
 #[derive(Clone, Debug)]
 pub struct ShellCommand {
@@ -16,27 +17,35 @@ impl ShellCommand {
         Self { command }
     }
     pub const fn command() -> &'static str {
-        "console"
+        "shell"
     }
 
-    pub fn shell_command(command: &str) -> std::io::Result<()> {
+    // without a timeout the command is left running, otherwise it is waited for
+    pub fn shell_command(command: &str, timeout: u64) -> std::io::Result<()> {
         let mut command = process::Command::new("sh").arg("-c").arg(command).spawn()?;
+        if timeout > 0 {
+            wait_timeout(&mut command, timeout)?;
+        }
         Ok(())
     }
 }
 
-impl JoshutoCommand for ShellCommand {}
+impl JoshutoCommand for ShellCommand {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
 
 impl std::fmt::Display for ShellCommand {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}: sh -c '{}'", Self::command(), self.command)
+        write!(f, "{} {}", Self::command(), self.command)
     }
 }
 
 impl JoshutoRunnable for ShellCommand {
     fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
         backend.terminal_drop();
-        let res = Self::shell_command(self.command.as_str());
+        let res = Self::shell_command(self.command.as_str(), context.config_t.shell_timeout);
         backend.terminal_restore()?;
         res?;
         Ok(())
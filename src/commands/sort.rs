@@ -6,15 +6,40 @@ use crate::ui::TuiBackend;
 
 use crate::util::load_child::LoadChild;
 use crate::util::sort::SortType;
+use crate::util::sort_overrides;
+
+// sorting by creation time quietly falls back to mtime where there is none
+fn warn_missing_created(sort_method: SortType, context: &mut JoshutoContext) {
+    if let SortType::Created = sort_method {
+        let missing = match context.curr_tab_ref().curr_list_ref() {
+            Some(list) => list
+                .contents
+                .iter()
+                .any(|e| e.metadata().map(|m| m.created.is_none()).unwrap_or(false)),
+            None => false,
+        };
+        if missing {
+            context.message_queue.push_back(
+                "Creation time isn't available for every entry here, using mtime for those"
+                    .to_string(),
+            );
+        }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Sort {
     sort_method: SortType,
+    // sort in reverse, otherwise in the usual direction
+    reverse: bool,
 }
 
 impl Sort {
-    pub fn new(sort_method: SortType) -> Self {
-        Self { sort_method }
+    pub fn new(sort_method: SortType, reverse: bool) -> Self {
+        Self {
+            sort_method,
+            reverse,
+        }
     }
     pub const fn command() -> &'static str {
         "sort"
@@ -25,18 +50,24 @@ impl JoshutoCommand for Sort {}
 
 impl std::fmt::Display for Sort {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} {}", Self::command(), self.sort_method.as_str())
+        write!(f, "{} {}", Self::command(), self.sort_method.as_str())?;
+        if self.reverse {
+            f.write_str(" --reverse")?;
+        }
+        Ok(())
     }
 }
 
 impl JoshutoRunnable for Sort {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
         context.config_t.sort_option.sort_method = self.sort_method;
+        context.config_t.sort_option.reverse = self.reverse;
         for tab in context.tabs.iter_mut() {
             tab.history.depreciate_all_entries();
         }
         ReloadDirList::soft_reload(context.curr_tab_index, context)?;
         LoadChild::load_child(context)?;
+        warn_missing_created(self.sort_method, context);
         Ok(())
     }
 }
@@ -73,3 +104,62 @@ impl JoshutoRunnable for SortReverse {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct SortDirectory {
+    // with whether to sort in reverse, None to forget the directory's sort method
+    sort_method: Option<(SortType, bool)>,
+}
+
+impl SortDirectory {
+    pub fn new(sort_method: Option<(SortType, bool)>) -> Self {
+        Self { sort_method }
+    }
+    pub const fn command() -> &'static str {
+        "sort"
+    }
+}
+
+impl JoshutoCommand for SortDirectory {}
+
+impl std::fmt::Display for SortDirectory {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.sort_method {
+            Some((s, false)) => write!(f, "{} --here {}", Self::command(), s.as_str()),
+            Some((s, true)) => write!(f, "{} --here {} --reverse", Self::command(), s.as_str()),
+            None => write!(f, "{} --forget", Self::command()),
+        }
+    }
+}
+
+impl JoshutoRunnable for SortDirectory {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        sort_overrides::set(curr_path.as_path(), self.sort_method)?;
+
+        for tab in context.tabs.iter_mut() {
+            tab.history.depreciate_entry(curr_path.as_path());
+        }
+        ReloadDirList::soft_reload(context.curr_tab_index, context)?;
+        LoadChild::load_child(context)?;
+
+        let msg = match self.sort_method {
+            Some((s, false)) => {
+                format!("Sorting {} by {}", curr_path.to_string_lossy(), s.as_str())
+            }
+            Some((s, true)) => format!(
+                "Sorting {} by {}, reversed",
+                curr_path.to_string_lossy(),
+                s.as_str()
+            ),
+            None => format!(
+                "Sorting {} like everything else",
+                curr_path.to_string_lossy()
+            ),
+        };
+        context.message_queue.push_back(msg);
+        if let Some((s, _)) = self.sort_method {
+            warn_missing_created(s, context);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,121 @@
+use std::path;
+use std::process;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::config::SplitBackend;
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+
+#[derive(Clone, Debug)]
+pub struct Split {
+    horizontal: bool,
+}
+
+impl Split {
+    pub fn new(horizontal: bool) -> Self {
+        Split { horizontal }
+    }
+    pub const fn command() -> &'static str {
+        "split"
+    }
+
+    // the multiplexer we are running inside of, going by what each one exports
+    fn detect() -> Option<SplitBackend> {
+        if std::env::var_os("TMUX").is_some() {
+            Some(SplitBackend::Tmux)
+        } else if std::env::var_os("KITTY_WINDOW_ID").is_some() {
+            Some(SplitBackend::Kitty)
+        } else if std::env::var_os("WEZTERM_PANE").is_some() {
+            Some(SplitBackend::Wezterm)
+        } else {
+            None
+        }
+    }
+
+    fn split_command(
+        backend: SplitBackend,
+        dir: &path::Path,
+        horizontal: bool,
+    ) -> Option<process::Command> {
+        let mut command = match backend {
+            SplitBackend::Tmux => {
+                let mut command = process::Command::new("tmux");
+                command.arg("split-window");
+                if horizontal {
+                    command.arg("-h");
+                }
+                command.arg("-c").arg(dir);
+                command
+            }
+            SplitBackend::Kitty => {
+                let mut command = process::Command::new("kitty");
+                command.args(&["@", "launch", "--type", "window"]);
+                command.arg("--location");
+                command.arg(if horizontal { "vsplit" } else { "hsplit" });
+                command.arg("--cwd").arg(dir);
+                command
+            }
+            SplitBackend::Wezterm => {
+                let mut command = process::Command::new("wezterm");
+                command.args(&["cli", "split-pane"]);
+                if horizontal {
+                    command.arg("--horizontal");
+                }
+                command.arg("--cwd").arg(dir);
+                command
+            }
+            SplitBackend::Auto => return None,
+        };
+        command
+            .stdin(process::Stdio::null())
+            .stdout(process::Stdio::null())
+            .stderr(process::Stdio::null());
+        Some(command)
+    }
+}
+
+impl JoshutoCommand for Split {}
+
+impl std::fmt::Display for Split {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if self.horizontal {
+            f.write_str(" --horizontal")?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for Split {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let backend = match context.config_t.split_backend {
+            SplitBackend::Auto => Self::detect(),
+            backend => Some(backend),
+        };
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let mut command = match backend
+            .and_then(|b| Self::split_command(b, curr_path.as_path(), self.horizontal))
+        {
+            Some(command) => command,
+            None => {
+                return Err(JoshutoError::new(
+                    JoshutoErrorKind::IOInvalidData,
+                    format!(
+                        "{}: No supported multiplexer detected (tmux, kitty, wezterm)",
+                        Self::command()
+                    ),
+                ))
+            }
+        };
+
+        let status = command.status()?;
+        if !status.success() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOOther,
+                format!("{}: {:?} exited with {}", Self::command(), command, status),
+            ));
+        }
+        Ok(())
+    }
+}
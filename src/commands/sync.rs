@@ -0,0 +1,48 @@
+use std::thread;
+use std::time;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+use crate::util::event::Event;
+use crate::util::unix;
+
+/*
+ * flushes pending writes to disk, so removable media can be unmounted
+ * safely. this can take a while, so it runs on a separate thread
+ */
+#[derive(Clone, Debug)]
+pub struct SyncFilesystems;
+
+impl SyncFilesystems {
+    pub fn new() -> Self {
+        SyncFilesystems
+    }
+    pub const fn command() -> &'static str {
+        "sync"
+    }
+}
+
+impl JoshutoCommand for SyncFilesystems {}
+
+impl std::fmt::Display for SyncFilesystems {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for SyncFilesystems {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let event_tx = context.events.event_tx.clone();
+        thread::spawn(move || {
+            let start = time::Instant::now();
+            unix::sync();
+            let _ = event_tx.send(Event::Synced(start.elapsed()));
+        });
+        context
+            .message_queue
+            .push_back("Flushing pending writes...".to_string());
+        Ok(())
+    }
+}
@@ -10,23 +10,42 @@ use crate::util::load_child::LoadChild;
 use crate::HOME_DIR;
 
 #[derive(Clone, Debug)]
-pub struct NewTab;
+pub struct NewTab {
+    here: bool,
+}
 
 impl NewTab {
-    pub fn new() -> Self {
-        NewTab
+    pub fn new(here: bool) -> Self {
+        NewTab { here }
     }
     pub const fn command() -> &'static str {
         "new_tab"
     }
 
-    pub fn new_tab(context: &mut JoshutoContext) -> JoshutoResult<()> {
-        /* start the new tab in $HOME or root */
-        let curr_path = match HOME_DIR.as_ref() {
-            Some(s) => s.clone(),
-            None => path::PathBuf::from("/"),
+    /* the directory under the cursor, or the current directory for regular files */
+    fn cursor_path(context: &JoshutoContext) -> path::PathBuf {
+        let curr_tab = context.curr_tab_ref();
+        match curr_tab.curr_list_ref().and_then(|s| s.get_curr_ref()) {
+            Some(entry) if entry.file_path().is_dir() => entry.file_path().clone(),
+            _ => curr_tab.curr_path.clone(),
+        }
+    }
+
+    pub fn new_tab(context: &mut JoshutoContext, here: bool) -> JoshutoResult<()> {
+        let curr_path = if here {
+            Self::cursor_path(context)
+        } else {
+            /* start the new tab in $HOME or root */
+            match HOME_DIR.as_ref() {
+                Some(s) => s.clone(),
+                None => path::PathBuf::from("/"),
+            }
         };
 
+        Self::new_tab_at(curr_path, context)
+    }
+
+    pub fn new_tab_at(curr_path: path::PathBuf, context: &mut JoshutoContext) -> JoshutoResult<()> {
         let tab = JoshutoTab::new(curr_path, &context.config_t.sort_option)?;
         context.tabs.push(tab);
         context.curr_tab_index = context.tabs.len() - 1;
@@ -40,13 +59,120 @@ impl JoshutoCommand for NewTab {}
 
 impl std::fmt::Display for NewTab {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        f.write_str(Self::command())
+        f.write_str(Self::command())?;
+        if self.here {
+            f.write_str(" --here")?;
+        }
+        Ok(())
     }
 }
 
 impl JoshutoRunnable for NewTab {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
-        Self::new_tab(context)
+        Self::new_tab(context, self.here)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DuplicateTab;
+
+impl DuplicateTab {
+    pub fn new() -> Self {
+        DuplicateTab
+    }
+    pub const fn command() -> &'static str {
+        "duplicate_tab"
+    }
+
+    pub fn duplicate_tab(context: &mut JoshutoContext) -> JoshutoResult<()> {
+        /* start the new tab in the same directory and at the same entry */
+        let curr_tab = context.curr_tab_ref();
+        let curr_path = curr_tab.curr_path.clone();
+        let index = curr_tab.curr_list_ref().and_then(|s| s.index);
+
+        let mut tab = JoshutoTab::new(curr_path, &context.config_t.sort_option)?;
+        if let Some(curr_list) = tab.curr_list_mut() {
+            if let Some(i) = index {
+                if i < curr_list.contents.len() {
+                    curr_list.index = Some(i);
+                }
+            }
+        }
+        context.tabs.push(tab);
+        context.curr_tab_index = context.tabs.len() - 1;
+        TabSwitch::tab_switch(context.curr_tab_index, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for DuplicateTab {}
+
+impl std::fmt::Display for DuplicateTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for DuplicateTab {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        Self::duplicate_tab(context)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct OpenParentInTab;
+
+impl OpenParentInTab {
+    pub fn new() -> Self {
+        OpenParentInTab
+    }
+    pub const fn command() -> &'static str {
+        "open_parent_in_tab"
+    }
+
+    pub fn open_parent_in_tab(context: &mut JoshutoContext) -> JoshutoResult<()> {
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        let parent = match curr_path.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => {
+                context
+                    .message_queue
+                    .push_back(format!("{}: Already at the root", Self::command()));
+                return Ok(());
+            }
+        };
+
+        /* the new tab starts on the directory we came from */
+        let mut tab = JoshutoTab::new(parent, &context.config_t.sort_option)?;
+        if let Some(curr_list) = tab.curr_list_mut() {
+            let index = curr_list
+                .contents
+                .iter()
+                .position(|e| e.file_path() == &curr_path);
+            if index.is_some() {
+                curr_list.index = index;
+            }
+        }
+        context.tabs.push(tab);
+        context.curr_tab_index = context.tabs.len() - 1;
+        TabSwitch::tab_switch(context.curr_tab_index, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
+
+impl JoshutoCommand for OpenParentInTab {}
+
+impl std::fmt::Display for OpenParentInTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for OpenParentInTab {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        Self::open_parent_in_tab(context)
     }
 }
 
@@ -61,12 +187,18 @@ impl CloseTab {
         "close_tab"
     }
 
-    pub fn close_tab(context: &mut JoshutoContext) -> JoshutoResult<()> {
+    pub fn close_tab(context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
         if context.tabs.len() <= 1 {
-            return Quit::quit(context);
+            return Quit::quit(context, backend);
         }
 
         let _ = context.tabs.remove(context.curr_tab_index);
+        /* the target moves down with the tabs after the closed one */
+        context.target_tab = match context.target_tab {
+            Some(i) if i == context.curr_tab_index => None,
+            Some(i) if i > context.curr_tab_index => Some(i - 1),
+            target => target,
+        };
         if context.curr_tab_index > 0 {
             context.curr_tab_index -= 1;
         }
@@ -84,7 +216,45 @@ impl std::fmt::Display for CloseTab {
 }
 
 impl JoshutoRunnable for CloseTab {
+    fn execute(&self, context: &mut JoshutoContext, backend: &mut TuiBackend) -> JoshutoResult<()> {
+        Self::close_tab(context, backend)
+    }
+}
+
+/*
+ * marks the current tab as the one paste_to_target pastes into,
+ * running it again on the target tab unmarks it
+ */
+#[derive(Clone, Debug)]
+pub struct SetTargetTab;
+
+impl SetTargetTab {
+    pub fn new() -> Self {
+        SetTargetTab
+    }
+    pub const fn command() -> &'static str {
+        "set_target_tab"
+    }
+}
+
+impl JoshutoCommand for SetTargetTab {}
+
+impl std::fmt::Display for SetTargetTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for SetTargetTab {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
-        Self::close_tab(context)
+        let msg = if context.target_tab == Some(context.curr_tab_index) {
+            context.target_tab = None;
+            "Target tab unset".to_string()
+        } else {
+            context.target_tab = Some(context.curr_tab_index);
+            format!("Tab {} is the target", context.curr_tab_index + 1)
+        };
+        context.message_queue.push_back(msg);
+        Ok(())
     }
 }
@@ -2,17 +2,23 @@ use std::env;
 
 use crate::commands::{JoshutoCommand, JoshutoRunnable};
 use crate::context::JoshutoContext;
-use crate::error::JoshutoResult;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
 use crate::ui::TuiBackend;
 
+/*
+ * switches to tab number index, counting from 1, or from -1 at the end.
+ * when relative it goes index tabs away from the current one instead,
+ * and where that is past either end depends on tab_switch_wrap
+ */
 #[derive(Clone, Debug)]
 pub struct TabSwitch {
-    movement: i32,
+    index: i32,
+    relative: bool,
 }
 
 impl TabSwitch {
-    pub fn new(movement: i32) -> Self {
-        TabSwitch { movement }
+    pub fn new(index: i32, relative: bool) -> Self {
+        TabSwitch { index, relative }
     }
     pub const fn command() -> &'static str {
         "tab_switch"
@@ -31,22 +37,161 @@ impl JoshutoCommand for TabSwitch {}
 
 impl std::fmt::Display for TabSwitch {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{} {}", Self::command(), self.movement)
+        if self.relative {
+            write!(f, "{} {}", Self::command(), self.index)
+        } else {
+            write!(f, "{} --index {}", Self::command(), self.index)
+        }
     }
 }
 
 impl JoshutoRunnable for TabSwitch {
     fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
-        let mut new_index = context.curr_tab_index as i32 + self.movement;
         let tab_len = context.tabs.len() as i32;
-        while new_index < 0 {
-            new_index += tab_len;
+        let new_index = if self.relative {
+            let new_index = context.curr_tab_index as i32 + self.index;
+            if context.config_t.tab_switch_wrap {
+                new_index.rem_euclid(tab_len)
+            } else {
+                new_index.max(0).min(tab_len - 1)
+            }
+        } else {
+            match absolute_index(self.index, tab_len) {
+                Some(new_index) => new_index,
+                None => {
+                    return Err(JoshutoError::new(
+                        JoshutoErrorKind::IOInvalidData,
+                        format!("{}: There are only {} tabs", Self::command(), tab_len),
+                    ))
+                }
+            }
+        };
+        Self::tab_switch(new_index as usize, context)?;
+        Ok(())
+    }
+}
+
+// where tab number index is, counting from 1 or from -1 at the end
+fn absolute_index(index: i32, tab_len: i32) -> Option<i32> {
+    let index = if index < 0 {
+        tab_len + index
+    } else {
+        index - 1
+    };
+    if index >= 0 && index < tab_len {
+        Some(index)
+    } else {
+        None
+    }
+}
+
+// the tab movement away from the current one, wrapping around at the ends
+fn cycle(context: &JoshutoContext, movement: i32) -> usize {
+    let tab_len = context.tabs.len() as i32;
+    (context.curr_tab_index as i32 + movement).rem_euclid(tab_len) as usize
+}
+
+#[derive(Clone, Debug)]
+pub struct TabSwitchNext;
+
+impl TabSwitchNext {
+    pub fn new() -> Self {
+        TabSwitchNext
+    }
+    pub const fn command() -> &'static str {
+        "tab_switch_next"
+    }
+}
+
+impl JoshutoCommand for TabSwitchNext {}
+
+impl std::fmt::Display for TabSwitchNext {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for TabSwitchNext {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let new_index = cycle(context, 1);
+        TabSwitch::tab_switch(new_index, context)?;
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TabSwitchPrev;
+
+impl TabSwitchPrev {
+    pub fn new() -> Self {
+        TabSwitchPrev
+    }
+    pub const fn command() -> &'static str {
+        "tab_switch_prev"
+    }
+}
+
+impl JoshutoCommand for TabSwitchPrev {}
+
+impl std::fmt::Display for TabSwitchPrev {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for TabSwitchPrev {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let new_index = cycle(context, -1);
+        TabSwitch::tab_switch(new_index, context)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::ReloadDirList;
+    use crate::config::JoshutoConfig;
+    use crate::tab::JoshutoTab;
+    use crate::util::test_dir::TempDir;
+
+    #[test]
+    fn switching_back_keeps_selection_and_cursor() {
+        let dir_a = TempDir::new();
+        let dir_b = TempDir::new();
+        for name in &["a", "b", "c"] {
+            dir_a.touch(name);
         }
-        while new_index >= tab_len {
-            new_index -= tab_len;
+
+        let mut context = JoshutoContext::without_state(JoshutoConfig::default());
+        let options = context.config_t.sort_option.clone();
+        context.push_tab(JoshutoTab::new(dir_a.path().to_path_buf(), &options).unwrap());
+        context.push_tab(JoshutoTab::new(dir_b.path().to_path_buf(), &options).unwrap());
+
+        TabSwitch::tab_switch(0, &mut context).unwrap();
+        {
+            let list = context.curr_tab_mut().curr_list_mut().unwrap();
+            list.index = Some(2);
+            list.contents[1].set_selected(true);
         }
-        let new_index = new_index as usize;
-        Self::tab_switch(new_index, context)?;
-        Ok(())
+        TabSwitch::tab_switch(1, &mut context).unwrap();
+        TabSwitch::tab_switch(0, &mut context).unwrap();
+        /* re-reading the directory must not lose them either */
+        ReloadDirList::reload(0, &mut context).unwrap();
+
+        let list = context.curr_tab_ref().curr_list_ref().unwrap();
+        assert_eq!(list.index, Some(2));
+        let selected: Vec<&str> = list.selected_entries().map(|e| e.file_name()).collect();
+        assert_eq!(selected, vec!["b"]);
+    }
+
+    #[test]
+    fn negative_indices_count_from_the_end() {
+        assert_eq!(absolute_index(1, 3), Some(0));
+        assert_eq!(absolute_index(3, 3), Some(2));
+        assert_eq!(absolute_index(-1, 3), Some(2));
+        assert_eq!(absolute_index(-3, 3), Some(0));
+        assert_eq!(absolute_index(4, 3), None);
+        assert_eq!(absolute_index(-4, 3), None);
     }
 }
@@ -0,0 +1,101 @@
+use std::path;
+use std::time;
+
+use filetime::FileTime;
+
+use crate::commands::{escape_arg, JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+use crate::util::load_child::LoadChild;
+
+/*
+ * parses @<seconds since epoch> or a date in the format the footer shows
+ * (YYYY-MM-DD, optionally followed by HH:MM or HH:MM:SS), in UTC like the footer
+ */
+pub fn parse_timestamp(s: &str) -> Option<time::SystemTime> {
+    const DATETIME_FORMATS: [&str; 2] = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"];
+
+    if s.starts_with('@') {
+        let secs = s[1..].parse::<u64>().ok()?;
+        return Some(time::UNIX_EPOCH + time::Duration::from_secs(secs));
+    }
+    let naive = DATETIME_FORMATS
+        .iter()
+        .find_map(|f| chrono::NaiveDateTime::parse_from_str(s, f).ok())
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .ok()
+                .map(|d| d.and_hms(0, 0, 0))
+        })?;
+    let secs = naive.timestamp();
+    if secs < 0 {
+        return None;
+    }
+    Some(time::UNIX_EPOCH + time::Duration::from_secs(secs as u64))
+}
+
+#[derive(Clone, Debug)]
+pub struct TouchTime {
+    time: Option<String>,
+    mtime: Option<time::SystemTime>,
+}
+
+impl TouchTime {
+    pub fn new(time: Option<String>) -> Option<Self> {
+        let mtime = match time.as_ref() {
+            Some(s) => Some(parse_timestamp(s)?),
+            None => None,
+        };
+        Some(TouchTime { time, mtime })
+    }
+    pub const fn command() -> &'static str {
+        "touch_time"
+    }
+}
+
+impl JoshutoCommand for TouchTime {
+    fn modifies_files(&self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Display for TouchTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if let Some(time) = self.time.as_ref() {
+            write!(f, " --time {}", escape_arg(time))?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for TouchTime {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let paths: Vec<path::PathBuf> = match context.curr_tab_ref().curr_list_ref() {
+            Some(s) => s.get_selected_paths().into_iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        if paths.is_empty() {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IONotFound,
+                String::from("No files selected"),
+            ));
+        }
+        if let Some(path) = ReloadDirList::find_missing(&paths) {
+            return Err(ReloadDirList::stale_entry(path.as_path(), context));
+        }
+
+        let mtime = self.mtime.unwrap_or_else(time::SystemTime::now);
+        for path in paths.iter() {
+            /* the access time is left alone */
+            filetime::set_file_mtime(path.as_path(), FileTime::from_system_time(mtime))?;
+        }
+
+        /* entries are compared by mtime when sorting, so resort the listing */
+        let curr_path = context.curr_tab_ref().curr_path.clone();
+        ReloadDirList::reload_path(&curr_path, context)?;
+        LoadChild::load_child(context)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::config::{LeftPaneMode, RightPaneMode};
+use crate::context::JoshutoContext;
+use crate::error::JoshutoResult;
+use crate::ui::TuiBackend;
+
+// changes what the left and right panes show, leaving out either keeps it as is
+#[derive(Clone, Debug)]
+pub struct SetViewColumns {
+    left: Option<LeftPaneMode>,
+    right: Option<RightPaneMode>,
+}
+
+impl SetViewColumns {
+    pub fn new(left: Option<LeftPaneMode>, right: Option<RightPaneMode>) -> Self {
+        SetViewColumns { left, right }
+    }
+    pub const fn command() -> &'static str {
+        "set_view_columns"
+    }
+}
+
+impl JoshutoCommand for SetViewColumns {}
+
+impl std::fmt::Display for SetViewColumns {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())?;
+        if let Some(left) = self.left {
+            write!(f, " --left {}", left.as_str())?;
+        }
+        if let Some(right) = self.right {
+            write!(f, " --right {}", right.as_str())?;
+        }
+        Ok(())
+    }
+}
+
+impl JoshutoRunnable for SetViewColumns {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        if let Some(left) = self.left {
+            context.config_t.left_pane = left;
+        }
+        if let Some(right) = self.right {
+            context.config_t.right_pane = right;
+        }
+        Ok(())
+    }
+}
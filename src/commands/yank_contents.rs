@@ -0,0 +1,92 @@
+use std::fs;
+
+use crate::commands::{JoshutoCommand, JoshutoRunnable, ReloadDirList};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::fs::FileType;
+use crate::ui::TuiBackend;
+use crate::util::clipboard;
+
+#[derive(Clone, Debug)]
+pub struct YankContents;
+
+impl YankContents {
+    pub fn new() -> Self {
+        YankContents
+    }
+    pub const fn command() -> &'static str {
+        "yank_contents"
+    }
+
+    fn refuse(msg: String) -> JoshutoError {
+        JoshutoError::new(
+            JoshutoErrorKind::IOInvalidData,
+            format!("{}: {}", Self::command(), msg),
+        )
+    }
+}
+
+impl JoshutoCommand for YankContents {}
+
+impl std::fmt::Display for YankContents {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str(Self::command())
+    }
+}
+
+impl JoshutoRunnable for YankContents {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let path = match context.curr_tab_ref().curr_list_ref() {
+            Some(curr_list) => match curr_list.get_curr_ref() {
+                Some(entry) => entry.file_path().clone(),
+                None => return Ok(()),
+            },
+            None => return Ok(()),
+        };
+        if let Some(missing) = ReloadDirList::find_missing(&[path.as_path()]) {
+            return Err(ReloadDirList::stale_entry(missing.as_path(), context));
+        }
+        if path.is_dir() {
+            return Err(Self::refuse(
+                "Cannot yank contents of a directory".to_string(),
+            ));
+        }
+        if let Some(file_type) = FileType::special_target(path.as_path()) {
+            return Err(Self::refuse(format!(
+                "Cannot yank contents of a {}",
+                file_type.special_name().unwrap_or_default()
+            )));
+        }
+
+        let max_size = context.config_t.max_preview_size;
+        if fs::metadata(path.as_path())?.len() > max_size {
+            return Err(Self::refuse(format!(
+                "File is larger than {} bytes",
+                max_size
+            )));
+        }
+
+        /* anything with NUL bytes or invalid utf-8 is treated as binary */
+        let bytes = fs::read(path.as_path())?;
+        if bytes.contains(&0) {
+            return Err(Self::refuse("Refusing to yank a binary file".to_string()));
+        }
+        let contents = match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(_) => return Err(Self::refuse("Refusing to yank a binary file".to_string())),
+        };
+
+        clipboard::set_contents(contents.as_str())?;
+
+        let file_name = path
+            .file_name()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        context.message_queue.push_back(format!(
+            "Copied {} lines of {} to clipboard",
+            contents.lines().count(),
+            file_name
+        ));
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use crate::commands::{JoshutoCommand, JoshutoRunnable};
+use crate::context::JoshutoContext;
+use crate::error::{JoshutoError, JoshutoErrorKind, JoshutoResult};
+use crate::ui::TuiBackend;
+use crate::util::clipboard;
+use crate::util::relative_path::relative_path;
+
+#[derive(Clone, Debug)]
+pub struct YankPathToTab {
+    movement: i32,
+}
+
+impl YankPathToTab {
+    pub fn new(movement: i32) -> Self {
+        YankPathToTab { movement }
+    }
+    pub const fn command() -> &'static str {
+        "yank_path_to_tab"
+    }
+}
+
+impl JoshutoCommand for YankPathToTab {}
+
+impl std::fmt::Display for YankPathToTab {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{} {}", Self::command(), self.movement)
+    }
+}
+
+impl JoshutoRunnable for YankPathToTab {
+    fn execute(&self, context: &mut JoshutoContext, _: &mut TuiBackend) -> JoshutoResult<()> {
+        let tab_len = context.tabs.len() as i32;
+        if tab_len < 2 {
+            return Err(JoshutoError::new(
+                JoshutoErrorKind::IOInvalidData,
+                format!("{}: No other tab open", Self::command()),
+            ));
+        }
+        /* counted from the current tab, wrapping around like tab_switch */
+        let other_index = (context.curr_tab_index as i32 + self.movement).rem_euclid(tab_len);
+        let from = &context.curr_tab_ref().curr_path;
+        let to = &context.tabs[other_index as usize].curr_path;
+
+        let path = relative_path(from, to).unwrap_or_else(|| to.clone());
+        let path = path.to_string_lossy().into_owned();
+        clipboard::set_contents(path.as_str())?;
+        context
+            .message_queue
+            .push_back(format!("Copied {} to clipboard", path));
+        Ok(())
+    }
+}
@@ -1,5 +1,8 @@
+use std::collections::HashMap;
+
 use serde_derive::Deserialize;
 
+use super::icons::{Icons, IconsRawOption};
 use super::{parse_to_config_file, ConfigStructure, Flattenable};
 use crate::util::sort;
 
@@ -14,9 +17,286 @@ const fn default_scroll_offset() -> usize {
 const fn default_max_preview_size() -> u64 {
     2 * 1024 * 1024 // 2 MB
 }
+fn default_diff_program() -> String {
+    "diff".to_string()
+}
+
+fn default_rename_clean_separator() -> String {
+    String::from("_")
+}
 const fn default_column_ratio() -> (usize, usize, usize) {
     (1, 3, 4)
 }
+const fn default_listing_column_gap() -> usize {
+    1
+}
+
+// arguments that open a file at a position, for editors whose syntax is known
+fn default_editor_positions() -> HashMap<String, Vec<String>> {
+    let line = ["+{line}", "{file}"];
+    let goto = ["--goto", "{file}:{line}:{column}"];
+    let positions: [(&str, &[&str]); 9] = [
+        ("vi", &line),
+        ("vim", &line),
+        ("nvim", &line),
+        ("nano", &line),
+        ("emacs", &line),
+        ("kak", &line),
+        ("code", &goto),
+        ("codium", &goto),
+        ("hx", &["{file}:{line}:{column}"]),
+    ];
+    positions
+        .iter()
+        .map(|(editor, args)| {
+            let args = args.iter().map(|s| s.to_string()).collect();
+            (editor.to_string(), args)
+        })
+        .collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DirPreviewMode {
+    Listing,
+    Summary,
+}
+
+impl DirPreviewMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "listing" => Some(DirPreviewMode::Listing),
+            "summary" => Some(DirPreviewMode::Summary),
+            _ => None,
+        }
+    }
+}
+
+// what open_file does with directories
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenDirMode {
+    Enter,
+    NewTab,
+}
+
+impl OpenDirMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "enter" => Some(OpenDirMode::Enter),
+            "new_tab" => Some(OpenDirMode::NewTab),
+            _ => None,
+        }
+    }
+}
+
+// what open_file does with a symlink pointing to a directory
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenSymlinkDirMode {
+    // enter the link, keeping it in the path
+    Enter,
+    // enter the directory the link resolves to
+    Follow,
+    OpenWith,
+}
+
+impl OpenSymlinkDirMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "enter" => Some(OpenSymlinkDirMode::Enter),
+            "follow" => Some(OpenSymlinkDirMode::Follow),
+            "open_with" => Some(OpenSymlinkDirMode::OpenWith),
+            _ => None,
+        }
+    }
+}
+
+// what open_file does with executable files
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenExecutableMode {
+    // open like any other file, using the mimetype config
+    Open,
+    // ask, then run it in the terminal
+    Run,
+    // ask, then run it through sh and wait for enter so its output can be read
+    RunShell,
+}
+
+impl OpenExecutableMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(OpenExecutableMode::Open),
+            "run" => Some(OpenExecutableMode::Run),
+            "run_shell" => Some(OpenExecutableMode::RunShell),
+            _ => None,
+        }
+    }
+}
+
+// where to go once deleting leaves the current directory empty
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EmptyDirMode {
+    // keep showing the empty directory
+    Stay,
+    // change to the parent, with the cursor on the emptied directory
+    Parent,
+}
+
+impl EmptyDirMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "stay" => Some(EmptyDirMode::Stay),
+            "parent" => Some(EmptyDirMode::Parent),
+            _ => None,
+        }
+    }
+}
+
+// what open_file does with archives
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OpenArchiveMode {
+    // open like any other file, using the mimetype config
+    Open,
+    // ask to extract into the current directory
+    Extract,
+    OpenWith,
+}
+
+impl OpenArchiveMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "open" => Some(OpenArchiveMode::Open),
+            "extract" => Some(OpenArchiveMode::Extract),
+            "open_with" => Some(OpenArchiveMode::OpenWith),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineNumberMode {
+    None,
+    Absolute,
+    Relative,
+}
+
+impl LineNumberMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "none" => Some(LineNumberMode::None),
+            "absolute" => Some(LineNumberMode::Absolute),
+            "relative" => Some(LineNumberMode::Relative),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        match *self {
+            LineNumberMode::None => "none",
+            LineNumberMode::Absolute => "absolute",
+            LineNumberMode::Relative => "relative",
+        }
+    }
+}
+
+// what the left pane shows
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LeftPaneMode {
+    Parent,
+    None,
+}
+
+impl LeftPaneMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "parent" => Some(LeftPaneMode::Parent),
+            "none" => Some(LeftPaneMode::None),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        match *self {
+            LeftPaneMode::Parent => "parent",
+            LeftPaneMode::None => "none",
+        }
+    }
+}
+
+// what the right pane shows: auto is listings of directories and previews of files
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RightPaneMode {
+    Auto,
+    Listing,
+    Preview,
+    None,
+}
+
+impl RightPaneMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(RightPaneMode::Auto),
+            "listing" => Some(RightPaneMode::Listing),
+            "preview" => Some(RightPaneMode::Preview),
+            "none" => Some(RightPaneMode::None),
+            _ => None,
+        }
+    }
+    pub fn as_str(&self) -> &str {
+        match *self {
+            RightPaneMode::Auto => "auto",
+            RightPaneMode::Listing => "listing",
+            RightPaneMode::Preview => "preview",
+            RightPaneMode::None => "none",
+        }
+    }
+    pub fn shows_listing(&self) -> bool {
+        match *self {
+            RightPaneMode::Auto | RightPaneMode::Listing => true,
+            RightPaneMode::Preview | RightPaneMode::None => false,
+        }
+    }
+    pub fn shows_preview(&self) -> bool {
+        match *self {
+            RightPaneMode::Auto | RightPaneMode::Preview => true,
+            RightPaneMode::Listing | RightPaneMode::None => false,
+        }
+    }
+}
+
+// which part of a name too long for its column is cut out
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NameTruncation {
+    End,
+    Middle,
+}
+
+impl NameTruncation {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "end" => Some(NameTruncation::End),
+            "middle" => Some(NameTruncation::Middle),
+            _ => None,
+        }
+    }
+}
+
+// the terminal multiplexer the split command opens panes with
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SplitBackend {
+    Auto,
+    Tmux,
+    Kitty,
+    Wezterm,
+}
+
+impl SplitBackend {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "auto" => Some(SplitBackend::Auto),
+            "tmux" => Some(SplitBackend::Tmux),
+            "kitty" => Some(SplitBackend::Kitty),
+            "wezterm" => Some(SplitBackend::Wezterm),
+            _ => None,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Deserialize)]
 struct SortRawOption {
@@ -28,6 +308,8 @@ struct SortRawOption {
     case_sensitive: bool,
     #[serde(default)]
     reverse: bool,
+    #[serde(default)]
+    respect_gitignore: bool,
 }
 
 impl SortRawOption {
@@ -37,6 +319,7 @@ impl SortRawOption {
             directories_first: self.directories_first,
             case_sensitive: self.case_sensitive,
             reverse: self.reverse,
+            respect_gitignore: self.respect_gitignore,
             sort_method,
         }
     }
@@ -49,6 +332,7 @@ impl std::default::Default for SortRawOption {
             directories_first: default_true(),
             case_sensitive: bool::default(),
             reverse: bool::default(),
+            respect_gitignore: bool::default(),
         }
     }
 }
@@ -65,7 +349,60 @@ pub struct JoshutoRawConfig {
     xdg_open: bool,
     #[serde(default = "default_max_preview_size")]
     max_preview_size: u64,
+    #[serde(default)]
+    image_preview: bool,
+    #[serde(default)]
+    hex_preview: bool,
+    dir_preview: Option<String>,
+    #[serde(default)]
+    dir_preview_recursive_size: bool,
+    #[serde(default = "default_rename_clean_separator")]
+    rename_clean_separator: String,
+    #[serde(default = "default_true")]
+    paste_precount: bool,
+    #[serde(default)]
+    sync_after_io: bool,
+    #[serde(default = "default_diff_program")]
+    diff_program: String,
+    #[serde(default)]
+    extension_column: bool,
+    #[serde(default)]
+    show_icons: bool,
+    name_truncation: Option<String>,
+    max_name_width: Option<usize>,
+    #[serde(default)]
+    icons: IconsRawOption,
+    #[serde(default)]
+    follow_newest: bool,
+    #[serde(default)]
+    auto_refresh_interval: u64,
+    #[serde(default)]
+    shell_timeout: u64,
+    #[serde(default)]
+    open_timeout: u64,
+    #[serde(default = "default_true")]
+    tab_switch_wrap: bool,
+    #[serde(default)]
+    confirm_quit: bool,
+    split_backend: Option<String>,
+    gui_file_manager: Option<String>,
+    #[serde(default)]
+    editor_positions: HashMap<String, Vec<String>>,
+    line_numbers: Option<String>,
+    open_directory: Option<String>,
+    open_file_program: Option<String>,
+    #[serde(default)]
+    open_fallback: Vec<String>,
+    open_symlink_dir: Option<String>,
+    open_archive: Option<String>,
+    open_executable: Option<String>,
+    empty_after_delete: Option<String>,
     column_ratio: Option<[usize; 3]>,
+    listing_padding: Option<[usize; 2]>,
+    #[serde(default = "default_listing_column_gap")]
+    listing_column_gap: usize,
+    left_pane: Option<String>,
+    right_pane: Option<String>,
     sort_method: Option<String>,
     #[serde(default)]
     sort_option: SortRawOption,
@@ -77,6 +414,10 @@ impl Flattenable<JoshutoConfig> for JoshutoRawConfig {
             Some(s) => (s[0], s[1], s[2]),
             _ => default_column_ratio(),
         };
+        let listing_padding = match self.listing_padding {
+            Some(s) => (s[0], s[1]),
+            None => (0, 0),
+        };
 
         let sort_method = match self.sort_method {
             Some(s) => match sort::SortType::parse(s.as_str()) {
@@ -87,13 +428,113 @@ impl Flattenable<JoshutoConfig> for JoshutoRawConfig {
         };
         let sort_option = self.sort_option.into_sort_option(sort_method);
 
+        let line_numbers = match self.line_numbers {
+            Some(s) => LineNumberMode::parse(s.as_str()).unwrap_or(LineNumberMode::None),
+            None => LineNumberMode::None,
+        };
+
+        let split_backend = match self.split_backend {
+            Some(s) => SplitBackend::parse(s.as_str()).unwrap_or(SplitBackend::Auto),
+            None => SplitBackend::Auto,
+        };
+
+        let open_directory = match self.open_directory {
+            Some(s) => OpenDirMode::parse(s.as_str()).unwrap_or(OpenDirMode::Enter),
+            None => OpenDirMode::Enter,
+        };
+
+        /* an empty program means using the mimetype associations */
+        let open_file_program = self.open_file_program.filter(|s| !s.trim().is_empty());
+        let gui_file_manager = self.gui_file_manager.filter(|s| !s.trim().is_empty());
+
+        /* configured editors are added to the known ones, or replace them */
+        let mut editor_positions = default_editor_positions();
+        editor_positions.extend(self.editor_positions);
+
+        let open_symlink_dir = match self.open_symlink_dir {
+            Some(s) => OpenSymlinkDirMode::parse(s.as_str()).unwrap_or(OpenSymlinkDirMode::Enter),
+            None => OpenSymlinkDirMode::Enter,
+        };
+
+        let open_archive = match self.open_archive {
+            Some(s) => OpenArchiveMode::parse(s.as_str()).unwrap_or(OpenArchiveMode::Open),
+            None => OpenArchiveMode::Open,
+        };
+
+        let open_executable = match self.open_executable {
+            Some(s) => OpenExecutableMode::parse(s.as_str()).unwrap_or(OpenExecutableMode::Open),
+            None => OpenExecutableMode::Open,
+        };
+
+        let empty_after_delete = match self.empty_after_delete {
+            Some(s) => EmptyDirMode::parse(s.as_str()).unwrap_or(EmptyDirMode::Stay),
+            None => EmptyDirMode::Stay,
+        };
+
+        let left_pane = match self.left_pane {
+            Some(s) => LeftPaneMode::parse(s.as_str()).unwrap_or(LeftPaneMode::Parent),
+            None => LeftPaneMode::Parent,
+        };
+        /* show_preview = false predates right_pane and still hides it */
+        let right_pane = match self.right_pane {
+            Some(s) => RightPaneMode::parse(s.as_str()).unwrap_or(RightPaneMode::Auto),
+            None if !self.show_preview => RightPaneMode::None,
+            None => RightPaneMode::Auto,
+        };
+
+        let name_truncation = match self.name_truncation {
+            Some(s) => NameTruncation::parse(s.as_str()).unwrap_or(NameTruncation::End),
+            None => NameTruncation::End,
+        };
+        /* 0 means no limit, same as leaving it out */
+        let max_name_width = self.max_name_width.filter(|w| *w > 0);
+
+        let dir_preview = match self.dir_preview {
+            Some(s) => DirPreviewMode::parse(s.as_str()).unwrap_or(DirPreviewMode::Listing),
+            None => DirPreviewMode::Listing,
+        };
+
         JoshutoConfig {
             scroll_offset: self.scroll_offset,
             tilde_in_titlebar: self.tilde_in_titlebar,
             show_preview: self.show_preview,
             xdg_open: self.xdg_open,
             max_preview_size: self.max_preview_size,
+            image_preview: self.image_preview,
+            hex_preview: self.hex_preview,
+            dir_preview,
+            dir_preview_recursive_size: self.dir_preview_recursive_size,
+            rename_clean_separator: self.rename_clean_separator,
+            paste_precount: self.paste_precount,
+            sync_after_io: self.sync_after_io,
+            diff_program: self.diff_program,
+            extension_column: self.extension_column,
+            show_icons: self.show_icons,
+            name_truncation,
+            max_name_width,
+            icons: self.icons.into_icons(),
+            follow_newest: self.follow_newest,
+            auto_refresh_interval: self.auto_refresh_interval,
+            shell_timeout: self.shell_timeout,
+            open_timeout: self.open_timeout,
+            tab_switch_wrap: self.tab_switch_wrap,
+            confirm_quit: self.confirm_quit,
+            split_backend,
+            gui_file_manager,
+            editor_positions,
+            line_numbers,
+            open_directory,
+            open_file_program,
+            open_fallback: self.open_fallback,
+            open_symlink_dir,
+            open_archive,
+            open_executable,
+            empty_after_delete,
             column_ratio,
+            listing_padding,
+            listing_column_gap: self.listing_column_gap,
+            left_pane,
+            right_pane,
             sort_option,
         }
     }
@@ -106,8 +547,58 @@ pub struct JoshutoConfig {
     pub show_preview: bool,
     pub xdg_open: bool,
     pub max_preview_size: u64,
+    pub image_preview: bool,
+    pub hex_preview: bool,
+    pub dir_preview: DirPreviewMode,
+    pub dir_preview_recursive_size: bool,
+    pub rename_clean_separator: String,
+    pub paste_precount: bool,
+    // whether pasted files are flushed to disk before the paste counts as done
+    pub sync_after_io: bool,
+    pub diff_program: String,
+    pub extension_column: bool,
+    // nerd font icons before entry names, off since the font may be missing
+    pub show_icons: bool,
+    pub name_truncation: NameTruncation,
+    // names are cut to this many columns even when there is room for more
+    pub max_name_width: Option<usize>,
+    pub icons: Icons,
+    pub follow_newest: bool,
+    // seconds between reloads of the current directory, 0 turns it off
+    pub auto_refresh_interval: u64,
+    // seconds shell commands may run before being killed, 0 for no limit
+    pub shell_timeout: u64,
+    // seconds programs opening files may run before being killed, 0 for no limit
+    pub open_timeout: u64,
+    // whether tab_switch past the last tab goes back to the first, or stops there
+    pub tab_switch_wrap: bool,
+    // whether quit asks first with several tabs open or file operations running
+    pub confirm_quit: bool,
+    pub split_backend: SplitBackend,
+    // run by open_in_gui with the current directory, detected if unset
+    pub gui_file_manager: Option<String>,
+    /*
+     * arguments that open {file} at {line} and {column}, by the name of the
+     * editor program. editors missing here are just given the file
+     */
+    pub editor_positions: HashMap<String, Vec<String>>,
+    pub line_numbers: LineNumberMode,
+    pub open_directory: OpenDirMode,
+    pub open_file_program: Option<String>,
+    // programs tried in order for files without an association
+    pub open_fallback: Vec<String>,
+    pub open_symlink_dir: OpenSymlinkDirMode,
+    pub open_archive: OpenArchiveMode,
+    pub open_executable: OpenExecutableMode,
+    pub empty_after_delete: EmptyDirMode,
     pub sort_option: sort::SortOption,
     pub column_ratio: (usize, usize, usize),
+    // blank columns to the left and right of every entry in the listings
+    pub listing_padding: (usize, usize),
+    // blank columns between the line number, name, extension and size of an entry
+    pub listing_column_gap: usize,
+    pub left_pane: LeftPaneMode,
+    pub right_pane: RightPaneMode,
 }
 
 impl ConfigStructure for JoshutoConfig {
@@ -127,8 +618,42 @@ impl std::default::Default for JoshutoConfig {
             show_preview: default_true(),
             xdg_open: false,
             max_preview_size: default_max_preview_size(),
+            image_preview: false,
+            hex_preview: false,
+            dir_preview: DirPreviewMode::Listing,
+            dir_preview_recursive_size: false,
+            rename_clean_separator: default_rename_clean_separator(),
+            paste_precount: true,
+            sync_after_io: false,
+            diff_program: default_diff_program(),
+            extension_column: false,
+            show_icons: false,
+            name_truncation: NameTruncation::End,
+            max_name_width: None,
+            icons: Icons::default(),
+            follow_newest: false,
+            auto_refresh_interval: 0,
+            shell_timeout: 0,
+            open_timeout: 0,
+            tab_switch_wrap: true,
+            confirm_quit: false,
+            split_backend: SplitBackend::Auto,
+            gui_file_manager: None,
+            editor_positions: default_editor_positions(),
+            line_numbers: LineNumberMode::None,
+            open_directory: OpenDirMode::Enter,
+            open_file_program: None,
+            open_fallback: Vec::new(),
+            open_symlink_dir: OpenSymlinkDirMode::Enter,
+            open_archive: OpenArchiveMode::Open,
+            open_executable: OpenExecutableMode::Open,
+            empty_after_delete: EmptyDirMode::Stay,
             sort_option,
             column_ratio: default_column_ratio(),
+            listing_padding: (0, 0),
+            listing_column_gap: default_listing_column_gap(),
+            left_pane: LeftPaneMode::Parent,
+            right_pane: RightPaneMode::Auto,
         }
     }
 }
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use serde_derive::Deserialize;
+
+use crate::fs::{FileType, JoshutoDirEntry};
+
+// nerd font glyphs shown when nothing more specific is configured
+const DIRECTORY_ICON: &str = "\u{f115}";
+const FILE_ICON: &str = "\u{f016}";
+const SYMLINK_ICON: &str = "\u{f0c1}";
+
+const EXT_ICONS: &[(&str, &str)] = &[
+    ("7z", "\u{f410}"),
+    ("avi", "\u{f03d}"),
+    ("bmp", "\u{f1c5}"),
+    ("bz2", "\u{f410}"),
+    ("c", "\u{e61e}"),
+    ("cpp", "\u{e61d}"),
+    ("css", "\u{e749}"),
+    ("deb", "\u{e77d}"),
+    ("doc", "\u{f1c2}"),
+    ("docx", "\u{f1c2}"),
+    ("flac", "\u{f001}"),
+    ("gif", "\u{f1c5}"),
+    ("go", "\u{e626}"),
+    ("gz", "\u{f410}"),
+    ("h", "\u{f0fd}"),
+    ("html", "\u{f13b}"),
+    ("iso", "\u{e271}"),
+    ("java", "\u{e256}"),
+    ("jpeg", "\u{f1c5}"),
+    ("jpg", "\u{f1c5}"),
+    ("js", "\u{e74e}"),
+    ("json", "\u{e60b}"),
+    ("lock", "\u{f023}"),
+    ("lua", "\u{e620}"),
+    ("md", "\u{f48a}"),
+    ("mkv", "\u{f03d}"),
+    ("mov", "\u{f03d}"),
+    ("mp3", "\u{f001}"),
+    ("mp4", "\u{f03d}"),
+    ("ogg", "\u{f001}"),
+    ("pdf", "\u{f1c1}"),
+    ("png", "\u{f1c5}"),
+    ("ppt", "\u{f1c4}"),
+    ("pptx", "\u{f1c4}"),
+    ("py", "\u{e606}"),
+    ("rar", "\u{f410}"),
+    ("rb", "\u{e21e}"),
+    ("rs", "\u{e7a8}"),
+    ("sh", "\u{f489}"),
+    ("svg", "\u{f1c5}"),
+    ("tar", "\u{f410}"),
+    ("toml", "\u{e615}"),
+    ("ts", "\u{e628}"),
+    ("txt", "\u{f15c}"),
+    ("vim", "\u{e62b}"),
+    ("wav", "\u{f001}"),
+    ("webm", "\u{f03d}"),
+    ("webp", "\u{f1c5}"),
+    ("xls", "\u{f1c3}"),
+    ("xlsx", "\u{f1c3}"),
+    ("xz", "\u{f410}"),
+    ("yaml", "\u{f481}"),
+    ("yml", "\u{f481}"),
+    ("zip", "\u{f410}"),
+];
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct IconsRawOption {
+    directory: Option<String>,
+    file: Option<String>,
+    symlink: Option<String>,
+    #[serde(default)]
+    ext: HashMap<String, String>,
+}
+
+impl IconsRawOption {
+    // configured icons are added to the built-in ones, or replace them
+    pub fn into_icons(self) -> Icons {
+        let mut icons = Icons::default();
+        if let Some(s) = self.directory {
+            icons.directory = s;
+        }
+        if let Some(s) = self.file {
+            icons.file = s;
+        }
+        if let Some(s) = self.symlink {
+            icons.symlink = s;
+        }
+        icons.ext.extend(
+            self.ext
+                .into_iter()
+                .map(|(ext, icon)| (ext.to_lowercase(), icon)),
+        );
+        icons
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Icons {
+    pub directory: String,
+    pub file: String,
+    pub symlink: String,
+    // by lowercase extension, for files
+    pub ext: HashMap<String, String>,
+}
+
+impl Icons {
+    pub fn get(&self, entry: &JoshutoDirEntry) -> &str {
+        match entry.file_type() {
+            FileType::Directory => self.directory.as_str(),
+            FileType::Symlink(_) => self.symlink.as_str(),
+            FileType::File => entry
+                .file_path()
+                .extension()
+                .and_then(|s| self.ext.get(&s.to_string_lossy().to_lowercase()))
+                .unwrap_or(&self.file)
+                .as_str(),
+            _ => self.file.as_str(),
+        }
+    }
+}
+
+impl std::default::Default for Icons {
+    fn default() -> Self {
+        Self {
+            directory: DIRECTORY_ICON.to_string(),
+            file: FILE_ICON.to_string(),
+            symlink: SYMLINK_ICON.to_string(),
+            ext: EXT_ICONS
+                .iter()
+                .map(|(ext, icon)| (ext.to_string(), icon.to_string()))
+                .collect(),
+        }
+    }
+}
@@ -7,7 +7,7 @@ use termion::event::Key;
 use super::{parse_to_config_file, ConfigStructure, Flattenable};
 use crate::commands::{self, CommandKeybind, JoshutoCommand};
 use crate::io::Options;
-use crate::util::key_mapping::str_to_key;
+use crate::util::key_mapping::{key_to_str, str_to_key};
 use crate::KEYMAP_FILE;
 
 #[derive(Debug)]
@@ -34,6 +34,30 @@ impl JoshutoCommandMapping {
         }
     }
 
+    /*
+     * every key sequence bound to a command, written out like in keymap.toml
+     * but separated by spaces, along with the command it runs
+     */
+    pub fn bindings(&self) -> Vec<(String, String)> {
+        let mut bindings = Vec::new();
+        self.collect_bindings(&mut Vec::new(), &mut bindings);
+        bindings.sort();
+        bindings
+    }
+
+    fn collect_bindings(&self, prefix: &mut Vec<String>, bindings: &mut Vec<(String, String)>) {
+        for (key, keybind) in self.map.iter() {
+            prefix.push(key_to_str(key));
+            match keybind {
+                CommandKeybind::SimpleKeybind(command) => {
+                    bindings.push((prefix.join(" "), command.to_string()))
+                }
+                CommandKeybind::CompositeKeybind(m) => m.collect_bindings(prefix, bindings),
+            }
+            prefix.pop();
+        }
+    }
+
     pub fn default_res(&mut self) -> Result<(), String> {
         let mut m = self;
 
@@ -87,17 +111,17 @@ impl JoshutoCommandMapping {
         let keys = [Key::Char('l')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::NewTab::new());
+        let cmd = Box::new(commands::NewTab::new(false));
         let keys = [Key::Char('T')];
         insert_keycommand(&mut m, cmd, &keys)?;
-        let cmd = Box::new(commands::NewTab::new());
+        let cmd = Box::new(commands::NewTab::new(false));
         let keys = [Key::Ctrl('t')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::NewTab::new());
+        let cmd = Box::new(commands::NewTab::new(false));
         let keys = [Key::Char('W')];
         insert_keycommand(&mut m, cmd, &keys)?;
-        let cmd = Box::new(commands::NewTab::new());
+        let cmd = Box::new(commands::NewTab::new(false));
         let keys = [Key::Ctrl('w')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
@@ -116,15 +140,15 @@ impl JoshutoCommandMapping {
         let keys = [Key::Char('z'), Key::Char('h')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::TabSwitch::new(1));
+        let cmd = Box::new(commands::TabSwitchNext::new());
         let keys = [Key::Char('\t')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::TabSwitch::new(-1));
+        let cmd = Box::new(commands::TabSwitchPrev::new());
         let keys = [Key::BackTab];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::OpenFileWith::new());
+        let cmd = Box::new(commands::OpenFileWith::new(Vec::new()));
         let keys = [Key::Char('r')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
@@ -136,23 +160,23 @@ impl JoshutoCommandMapping {
         let keys = [Key::Char('y'), Key::Char('y')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::PasteFiles::new(Options::default()));
+        let cmd = Box::new(commands::PasteFiles::new(Options::default(), false));
         let keys = [Key::Char('p'), Key::Char('p')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::DeleteFiles::new());
+        let cmd = Box::new(commands::DeleteFiles::new(false));
         let keys = [Key::Delete];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::DeleteFiles::new());
+        let cmd = Box::new(commands::DeleteFiles::new(false));
         let keys = [Key::Char('D'), Key::Char('d')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::RenameFileAppend::new());
+        let cmd = Box::new(commands::RenameFileAppend::new(None));
         let keys = [Key::Char('a')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::RenameFilePrepend::new());
+        let cmd = Box::new(commands::RenameFilePrepend::new(None));
         let keys = [Key::Char('A')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
@@ -171,7 +195,7 @@ impl JoshutoCommandMapping {
         let keys = [Key::Char('N')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
-        let cmd = Box::new(commands::BulkRename::new());
+        let cmd = Box::new(commands::BulkRename::new(false));
         let keys = [Key::Char('b'), Key::Char('b')];
         insert_keycommand(&mut m, cmd, &keys)?;
 
@@ -226,14 +250,32 @@ struct JoshutoMapCommand {
     pub keys: Vec<String>,
 }
 
+#[derive(Debug, Deserialize)]
+struct JoshutoCustomCommand {
+    pub name: String,
+    pub commands: Vec<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct JoshutoRawCommandMapping {
+    #[serde(default)]
+    custom_command: Vec<JoshutoCustomCommand>,
     #[serde(default)]
     mapcommand: Vec<JoshutoMapCommand>,
 }
 
 impl Flattenable<JoshutoCommandMapping> for JoshutoRawCommandMapping {
     fn flatten(self) -> JoshutoCommandMapping {
+        // custom commands have to be known before keys can be mapped to them
+        let custom_commands = self
+            .custom_command
+            .into_iter()
+            .map(|c| (c.name, c.commands))
+            .collect();
+        for e in commands::CustomCommand::register_all(custom_commands) {
+            eprintln!("{}", e);
+        }
+
         let mut keymaps = JoshutoCommandMapping::new();
         for m in self.mapcommand {
             match commands::parse_command(m.command.as_str()) {
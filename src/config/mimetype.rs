@@ -6,6 +6,7 @@ use std::path::Path;
 use std::process;
 
 use super::{parse_config_file, ConfigStructure};
+use crate::util::process::wait_timeout;
 use crate::MIMETYPE_FILE;
 
 #[derive(Debug, Deserialize)]
@@ -20,6 +21,9 @@ pub struct JoshutoMimetypeEntry {
     _silent: bool,
     #[serde(default, rename = "confirm_exit")]
     _confirm_exit: bool,
+    // takes several files in one invocation
+    #[serde(default, rename = "multi_file")]
+    _multi_file: bool,
 }
 
 impl JoshutoMimetypeEntry {
@@ -30,6 +34,7 @@ impl JoshutoMimetypeEntry {
             _fork: false,
             _silent: false,
             _confirm_exit: false,
+            _multi_file: false,
         }
     }
 
@@ -62,6 +67,11 @@ impl JoshutoMimetypeEntry {
         self
     }
 
+    pub fn multi_file(&mut self, multi_file: bool) -> &mut Self {
+        self._multi_file = multi_file;
+        self
+    }
+
     pub fn get_command(&self) -> &str {
         self._command.as_str()
     }
@@ -82,7 +92,17 @@ impl JoshutoMimetypeEntry {
         self._confirm_exit
     }
 
-    pub fn execute_with<I, S>(&self, paths: I) -> std::io::Result<()>
+    pub fn get_multi_file(&self) -> bool {
+        self._multi_file
+    }
+
+    // whether other runs the same program with the same arguments
+    pub fn same_program(&self, other: &JoshutoMimetypeEntry) -> bool {
+        self.get_command() == other.get_command() && self.get_args() == other.get_args()
+    }
+
+    // programs that don't fork are killed after timeout seconds, unless it is 0
+    pub fn execute_with<I, S>(&self, paths: I, timeout: u64) -> std::io::Result<()>
       where
           I: IntoIterator<Item = S>,
           S: AsRef<std::ffi::OsStr>, {
@@ -99,7 +119,7 @@ impl JoshutoMimetypeEntry {
 
         let mut handle = command.spawn()?;
         if !self.get_fork() {
-            handle.wait()?;
+            wait_timeout(&mut handle, timeout)?;
             if self.get_confirm_exit() {
                 println!(" --- Press ENTER to continue --- ");
                 std::io::stdin().bytes().next();
@@ -117,6 +137,7 @@ impl std::default::Default for JoshutoMimetypeEntry {
             _fork: false,
             _silent: false,
             _confirm_exit: false,
+            _multi_file: false,
         }
     }
 }
@@ -138,6 +159,9 @@ impl std::fmt::Display for JoshutoMimetypeEntry {
         if self.get_confirm_exit() {
             f.write_str("[confirm-exit]").unwrap();
         }
+        if self.get_multi_file() {
+            f.write_str("[multi-file]").unwrap();
+        }
         f.write_str("")
     }
 }
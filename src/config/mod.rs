@@ -1,10 +1,16 @@
 pub mod config;
+pub mod icons;
 pub mod keymap;
 pub mod mimetype;
 pub mod preview;
 pub mod theme;
 
-pub use self::config::JoshutoConfig;
+pub use self::config::{
+    DirPreviewMode, EmptyDirMode, JoshutoConfig, LeftPaneMode, LineNumberMode, NameTruncation,
+    OpenArchiveMode, OpenDirMode, OpenExecutableMode, OpenSymlinkDirMode, RightPaneMode,
+    SplitBackend,
+};
+pub use self::icons::Icons;
 pub use self::keymap::JoshutoCommandMapping;
 pub use self::mimetype::JoshutoMimetype;
 pub use self::preview::{JoshutoPreview, JoshutoPreviewEntry};
@@ -13,6 +13,30 @@ const fn default_prefix() -> Option<JoshutoPrefix> {
 const fn default_color() -> Color {
     Color::Reset
 }
+fn default_cursor() -> JoshutoStyleThemeRaw {
+    JoshutoStyleThemeRaw {
+        invert: true,
+        ..JoshutoStyleThemeRaw::default()
+    }
+}
+fn default_marked() -> JoshutoStyleThemeRaw {
+    JoshutoStyleThemeRaw {
+        underline: true,
+        ..JoshutoStyleThemeRaw::default()
+    }
+}
+fn default_tab() -> JoshutoStyleThemeRaw {
+    JoshutoStyleThemeRaw {
+        invert: true,
+        ..JoshutoStyleThemeRaw::default()
+    }
+}
+fn default_message() -> JoshutoStyleThemeRaw {
+    JoshutoStyleThemeRaw {
+        fg: String::from("yellow"),
+        ..JoshutoStyleThemeRaw::default()
+    }
+}
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct JoshutoPrefix {
@@ -86,7 +110,11 @@ impl JoshutoStyleThemeRaw {
             "light_blue" => Color::LightBlue,
             "light_magenta" => Color::LightMagenta,
             "light_cyan" => Color::LightCyan,
-            _ => Color::Reset,
+            "" | "reset" => Color::Reset,
+            s => {
+                eprintln!("Error parsing {}: unknown color {}", THEME_FILE, s);
+                Color::Reset
+            }
         }
     }
 }
@@ -110,6 +138,10 @@ pub struct JoshutoRawTheme {
     pub regular: JoshutoStyleThemeRaw,
     #[serde(default)]
     pub selection: JoshutoStyleThemeRaw,
+    #[serde(default = "default_cursor")]
+    pub cursor: JoshutoStyleThemeRaw,
+    #[serde(default = "default_marked")]
+    pub marked: JoshutoStyleThemeRaw,
     #[serde(default)]
     pub directory: JoshutoStyleThemeRaw,
     #[serde(default)]
@@ -119,6 +151,14 @@ pub struct JoshutoRawTheme {
     #[serde(default)]
     pub socket: JoshutoStyleThemeRaw,
     #[serde(default)]
+    pub fifo: JoshutoStyleThemeRaw,
+    #[serde(default)]
+    pub device: JoshutoStyleThemeRaw,
+    #[serde(default = "default_tab")]
+    pub tab: JoshutoStyleThemeRaw,
+    #[serde(default = "default_message")]
+    pub message: JoshutoStyleThemeRaw,
+    #[serde(default)]
     pub ext: HashMap<String, JoshutoStyleThemeRaw>,
 }
 
@@ -127,10 +167,16 @@ impl std::default::Default for JoshutoRawTheme {
         Self {
             regular: JoshutoStyleThemeRaw::default(),
             selection: JoshutoStyleThemeRaw::default(),
+            cursor: default_cursor(),
+            marked: default_marked(),
             directory: JoshutoStyleThemeRaw::default(),
             executable: JoshutoStyleThemeRaw::default(),
             link: JoshutoStyleThemeRaw::default(),
             socket: JoshutoStyleThemeRaw::default(),
+            fifo: JoshutoStyleThemeRaw::default(),
+            device: JoshutoStyleThemeRaw::default(),
+            tab: default_tab(),
+            message: default_message(),
             ext: HashMap::default(),
         }
     }
@@ -139,11 +185,17 @@ impl std::default::Default for JoshutoRawTheme {
 impl Flattenable<JoshutoTheme> for JoshutoRawTheme {
     fn flatten(self) -> JoshutoTheme {
         let selection = self.selection.to_style_theme();
+        let cursor = self.cursor.to_style_theme();
+        let marked = self.marked.to_style_theme();
         let executable = self.executable.to_style_theme();
         let regular = self.regular.to_style_theme();
         let directory = self.directory.to_style_theme();
         let link = self.link.to_style_theme();
         let socket = self.socket.to_style_theme();
+        let fifo = self.fifo.to_style_theme();
+        let device = self.device.to_style_theme();
+        let tab = self.tab.to_style_theme();
+        let message = self.message.to_style_theme();
         let ext: HashMap<String, JoshutoStyleTheme> = self
             .ext
             .iter()
@@ -155,11 +207,17 @@ impl Flattenable<JoshutoTheme> for JoshutoRawTheme {
 
         JoshutoTheme {
             selection,
+            cursor,
+            marked,
             executable,
             regular,
             directory,
             link,
             socket,
+            fifo,
+            device,
+            tab,
+            message,
             ext,
         }
     }
@@ -191,6 +249,25 @@ impl JoshutoStyleTheme {
         self.modifier.insert(modifier);
         self
     }
+
+    pub fn as_style(&self) -> Style {
+        Style::default()
+            .fg(self.fg)
+            .bg(self.bg)
+            .modifier(self.modifier)
+    }
+
+    // layers this theme on top of a style, unset colors are left untouched
+    pub fn apply(&self, style: Style) -> Style {
+        let mut style = style.modifier(style.modifier | self.modifier);
+        if self.fg != Color::Reset {
+            style = style.fg(self.fg);
+        }
+        if self.bg != Color::Reset {
+            style = style.bg(self.bg);
+        }
+        style
+    }
 }
 
 impl std::default::Default for JoshutoStyleTheme {
@@ -208,10 +285,16 @@ impl std::default::Default for JoshutoStyleTheme {
 pub struct JoshutoTheme {
     pub regular: JoshutoStyleTheme,
     pub selection: JoshutoStyleTheme,
+    pub cursor: JoshutoStyleTheme,
+    pub marked: JoshutoStyleTheme,
     pub directory: JoshutoStyleTheme,
     pub executable: JoshutoStyleTheme,
     pub link: JoshutoStyleTheme,
     pub socket: JoshutoStyleTheme,
+    pub fifo: JoshutoStyleTheme,
+    pub device: JoshutoStyleTheme,
+    pub tab: JoshutoStyleTheme,
+    pub message: JoshutoStyleTheme,
     pub ext: HashMap<String, JoshutoStyleTheme>,
 }
 
@@ -227,6 +310,8 @@ impl std::default::Default for JoshutoTheme {
         let selection = JoshutoStyleTheme::default()
             .set_fg(Color::LightYellow)
             .insert(Modifier::BOLD);
+        let cursor = JoshutoStyleTheme::default().insert(Modifier::REVERSED);
+        let marked = JoshutoStyleTheme::default().insert(Modifier::UNDERLINED);
         let executable = JoshutoStyleTheme::default()
             .set_fg(Color::LightGreen)
             .insert(Modifier::BOLD);
@@ -240,15 +325,29 @@ impl std::default::Default for JoshutoTheme {
         let socket = JoshutoStyleTheme::default()
             .set_fg(Color::LightMagenta)
             .insert(Modifier::BOLD);
+        let fifo = JoshutoStyleTheme::default()
+            .set_fg(Color::Yellow)
+            .insert(Modifier::BOLD);
+        let device = JoshutoStyleTheme::default()
+            .set_fg(Color::LightYellow)
+            .insert(Modifier::BOLD);
+        let tab = JoshutoStyleTheme::default().insert(Modifier::REVERSED);
+        let message = JoshutoStyleTheme::default().set_fg(Color::Yellow);
         let ext = HashMap::new();
 
         Self {
             selection,
+            cursor,
+            marked,
             executable,
             regular,
             directory,
             link,
             socket,
+            fifo,
+            device,
+            tab,
+            message,
             ext,
         }
     }
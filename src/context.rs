@@ -1,35 +1,102 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
+use crate::commands::{FileOp, GrepResults, JoshutoCommand};
 use crate::config;
 use crate::io::IOWorkerThread;
+use crate::preview::hex_preview::HexPreview;
+use crate::preview::image_preview::ImagePreviewCache;
+use crate::preview::symlink_info::SymlinkInfo;
 use crate::tab::JoshutoTab;
 use crate::util::event::Events;
+use crate::util::frecency::JoshutoFrecency;
+use crate::util::open_with_history::JoshutoOpenWithHistory;
 
 pub struct JoshutoContext {
     pub exit: bool,
+    // set by --readonly, commands changing files do nothing
+    pub readonly: bool,
     pub curr_tab_index: usize,
     pub tabs: Vec<JoshutoTab>,
+    // the tab paste_to_target pastes into
+    pub target_tab: Option<usize>,
     pub worker_queue: VecDeque<IOWorkerThread>,
     pub worker_busy: bool,
+    pub spinner_index: usize,
 
     pub worker_msg: Option<String>,
     pub message_queue: VecDeque<String>,
     pub events: Events,
+    pub frecency: JoshutoFrecency,
+    pub open_with_history: JoshutoOpenWithHistory,
+    pub image_previews: ImagePreviewCache,
+    pub hex_preview: Option<HexPreview>,
+    pub symlink_info: Option<SymlinkInfo>,
+    // directories being added up, and whether to post their total once done
+    pub size_pending: HashMap<PathBuf, bool>,
+    pub size_msg: Option<String>,
+    pub last_command: Option<Box<dyn JoshutoCommand>>,
+    // left by the grep thread for the main loop, where no prompt can swallow them
+    pub grep_results: Arc<Mutex<Option<GrepResults>>>,
+    // files kept by yank_to_register, by register name
+    pub registers: HashMap<String, (FileOp, Vec<PathBuf>)>,
+    // key sequences and the commands they run, listed by help
+    pub key_bindings: Vec<(String, String)>,
 
     pub config_t: config::JoshutoConfig,
 }
 
 impl JoshutoContext {
     pub fn new(config_t: config::JoshutoConfig) -> Self {
+        Self::with_state(
+            config_t,
+            Events::new(),
+            JoshutoFrecency::load(),
+            JoshutoOpenWithHistory::load(),
+        )
+    }
+
+    // without history from disk or a thread reading stdin, for tests
+    #[cfg(test)]
+    pub fn without_state(config_t: config::JoshutoConfig) -> Self {
+        Self::with_state(
+            config_t,
+            Events::without_input(),
+            JoshutoFrecency::new(),
+            JoshutoOpenWithHistory::new(),
+        )
+    }
+
+    fn with_state(
+        config_t: config::JoshutoConfig,
+        events: Events,
+        frecency: JoshutoFrecency,
+        open_with_history: JoshutoOpenWithHistory,
+    ) -> Self {
         Self {
             exit: false,
+            readonly: false,
             curr_tab_index: 0,
             tabs: Vec::new(),
+            target_tab: None,
             worker_queue: VecDeque::with_capacity(10),
             worker_busy: false,
+            spinner_index: 0,
             worker_msg: None,
             message_queue: VecDeque::with_capacity(4),
-            events: Events::new(),
+            events,
+            frecency,
+            open_with_history,
+            image_previews: ImagePreviewCache::new(),
+            hex_preview: None,
+            symlink_info: None,
+            size_pending: HashMap::new(),
+            size_msg: None,
+            last_command: None,
+            grep_results: Arc::new(Mutex::new(None)),
+            registers: HashMap::new(),
+            key_bindings: Vec::new(),
 
             config_t,
         }
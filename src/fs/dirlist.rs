@@ -1,7 +1,13 @@
-use std::{fs, path};
+use std::collections::{HashMap, HashSet};
+use std::{cmp, fs, ops, path, thread};
 
-use crate::fs::{JoshutoDirEntry, JoshutoMetadata};
+use crate::fs::{FileType, JoshutoDirEntry, JoshutoMetadata};
 use crate::util::sort::SortOption;
+use crate::util::unix;
+
+// directories smaller than this are not worth spreading over threads
+const PREFETCH_MIN_ENTRIES: usize = 256;
+const PREFETCH_THREADS: usize = 4;
 
 #[derive(Debug)]
 pub struct JoshutoDirList {
@@ -11,17 +17,21 @@ pub struct JoshutoDirList {
     order_outdated: bool,
     pub metadata: JoshutoMetadata,
     pub contents: Vec<JoshutoDirEntry>,
+    pub recursive_size: Option<u64>,
+    // checked whenever the directory is read, commands that write refuse otherwise
+    pub writable: bool,
+    // set while flattened: how many levels of subdirectories are listed with it
+    pub flatten_depth: Option<usize>,
 }
 
 impl JoshutoDirList {
     pub fn new(path: path::PathBuf, sort_option: &SortOption) -> std::io::Result<Self> {
-        let filter_func = sort_option.filter_func();
-        let mut contents = read_dir_list(path.as_path(), filter_func)?;
-        contents.sort_by(|f1, f2| sort_option.compare(f1, f2));
+        let contents = Self::read_contents(path.as_path(), sort_option)?;
 
         let index = if contents.is_empty() { None } else { Some(0) };
 
         let metadata = JoshutoMetadata::from(&path)?;
+        let writable = unix::is_writable(path.as_path());
 
         Ok(Self {
             index,
@@ -30,6 +40,9 @@ impl JoshutoDirList {
             order_outdated: false,
             metadata,
             contents,
+            recursive_size: None,
+            writable,
+            flatten_depth: None,
         })
     }
 
@@ -41,43 +54,184 @@ impl JoshutoDirList {
         self.content_outdated
     }
 
+    // whether the directory was modified on disk since it was last read
+    pub fn modified_on_disk(&self) -> bool {
+        match fs::metadata(self.path.as_path()).and_then(|m| m.modified()) {
+            Ok(modified) => modified != self.metadata.modified,
+            Err(_) => false,
+        }
+    }
+
+    pub fn select_newest(&mut self) {
+        self.load_metadata(0..self.contents.len());
+        self.index = self
+            .contents
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, e)| e.metadata().map(|m| m.modified))
+            .map(|(i, _)| i);
+    }
+
+    // reads the metadata of the entries in range that do not have it yet
+    pub fn load_metadata(&mut self, range: ops::Range<usize>) {
+        let end = cmp::min(range.end, self.contents.len());
+        let start = cmp::min(range.start, end);
+        for entry in self.contents[start..end].iter_mut() {
+            let _ = entry.load_metadata();
+        }
+    }
+
+    // reads the metadata of the entries that could be on screen around the cursor
+    pub fn load_metadata_around(&mut self, rows: usize) {
+        let index = self.index.unwrap_or(0);
+        self.load_metadata(index.saturating_sub(rows)..index + rows + 1);
+    }
+
     pub fn file_path(&self) -> &path::PathBuf {
         &self.path
     }
 
-    pub fn reload_contents(&mut self, sort_option: &SortOption) -> std::io::Result<()> {
-        let filter_func = sort_option.filter_func();
-        let mut contents = read_dir_list(&self.path, filter_func)?;
+    // reads and sorts the entries of a directory without updating any listing
+    pub fn read_contents(
+        path: &path::Path,
+        sort_option: &SortOption,
+    ) -> std::io::Result<Vec<JoshutoDirEntry>> {
+        let sort_option = sort_option.for_dir(path);
+        let filter_func = sort_option.filter_func(path);
+        let with_metadata = sort_option.sort_method.needs_metadata();
+        let mut contents = read_dir_list(path, filter_func, with_metadata)?;
         contents.sort_by(|f1, f2| sort_option.compare(f1, f2));
+        Ok(contents)
+    }
 
-        let contents_len = contents.len();
-        let index: Option<usize> = if contents_len == 0 {
+    /*
+     * reads and sorts the files below a directory, descending depth levels.
+     * entries are named by their path relative to it, directories are only
+     * listed once too deep to descend into and unreadable ones are skipped
+     */
+    pub fn read_flattened(
+        path: &path::Path,
+        depth: usize,
+        sort_option: &SortOption,
+    ) -> std::io::Result<Vec<JoshutoDirEntry>> {
+        fn flatten(
+            base: &path::Path,
+            dir: &path::Path,
+            depth: usize,
+            sort_option: &SortOption,
+            contents: &mut Vec<JoshutoDirEntry>,
+        ) -> std::io::Result<()> {
+            let filter_func = sort_option.filter_func(dir);
+            let with_metadata = sort_option.sort_method.needs_metadata();
+            for entry in read_dir_list(dir, filter_func, with_metadata)? {
+                match entry.file_type() {
+                    FileType::Directory if depth > 0 => {
+                        let _ = flatten(base, entry.file_path(), depth - 1, sort_option, contents);
+                    }
+                    _ => contents.push(entry.relative_to(base)),
+                }
+            }
+            Ok(())
+        }
+
+        let sort_option = sort_option.for_dir(path);
+        let mut contents = Vec::new();
+        flatten(path, path, depth, &sort_option, &mut contents)?;
+        contents.sort_by(|f1, f2| sort_option.compare(f1, f2));
+        Ok(contents)
+    }
+
+    // what reload_contents would read for path, flattened to depth if given
+    pub fn read(
+        path: &path::Path,
+        flatten_depth: Option<usize>,
+        sort_option: &SortOption,
+    ) -> std::io::Result<Vec<JoshutoDirEntry>> {
+        match flatten_depth {
+            Some(depth) => Self::read_flattened(path, depth, sort_option),
+            None => Self::read_contents(path, sort_option),
+        }
+    }
+
+    pub fn reload_contents(&mut self, sort_option: &SortOption) -> std::io::Result<()> {
+        let contents = Self::read(self.path.as_path(), self.flatten_depth, sort_option)?;
+        self.set_contents(contents)
+    }
+
+    /*
+     * replaces the entries, keeping the cursor on the same entry and
+     * carrying over selected and marked entries that still exist
+     */
+    pub fn set_contents(&mut self, mut contents: Vec<JoshutoDirEntry>) -> std::io::Result<()> {
+        {
+            let selected: HashSet<&str> = self
+                .contents
+                .iter()
+                .filter(|e| e.is_selected())
+                .map(|e| e.file_name())
+                .collect();
+            let marked: HashSet<&str> = self
+                .contents
+                .iter()
+                .filter(|e| e.is_marked())
+                .map(|e| e.file_name())
+                .collect();
+            for entry in contents.iter_mut() {
+                if selected.contains(entry.file_name()) {
+                    entry.set_selected(true);
+                }
+                if marked.contains(entry.file_name()) {
+                    entry.set_marked(true);
+                }
+            }
+        }
+
+        let index: Option<usize> = if contents.is_empty() {
             None
         } else {
             match self.index {
-                Some(i) if i >= contents_len => Some(contents_len - 1),
-                Some(i) => {
-                    let entry = &self.contents[i];
-                    contents
-                        .iter()
-                        .enumerate()
-                        .find(|(_, e)| e.file_name() == entry.file_name())
-                        .map(|(i, _)| i)
-                        .or(Some(i))
-                }
+                Some(i) => Some(self.carry_over_index(i, &contents)),
                 None => Some(0),
             }
         };
 
         let metadata = JoshutoMetadata::from(&self.path)?;
         self.metadata = metadata;
+        self.writable = unix::is_writable(self.path.as_path());
         self.contents = contents;
         self.index = index;
         self.content_outdated = false;
+        self.recursive_size = None;
 
         Ok(())
     }
 
+    /*
+     * where the cursor at index lands in contents: on the same entry if it is
+     * still listed (e.g. after hidden files were toggled), otherwise on the
+     * closest entry after it that is, or the closest one before it
+     */
+    fn carry_over_index(&self, index: usize, contents: &[JoshutoDirEntry]) -> usize {
+        let positions: HashMap<&str, usize> = contents
+            .iter()
+            .enumerate()
+            .map(|(i, e)| (e.file_name(), i))
+            .collect();
+        let index = cmp::min(index, self.contents.len());
+
+        self.contents[index..]
+            .iter()
+            .find_map(|e| positions.get(e.file_name()))
+            .or_else(|| {
+                self.contents[..index]
+                    .iter()
+                    .rev()
+                    .find_map(|e| positions.get(e.file_name()))
+            })
+            .copied()
+            .unwrap_or_else(|| cmp::min(index, contents.len() - 1))
+    }
+
     pub fn selected_entries(&self) -> impl Iterator<Item = &JoshutoDirEntry> {
         self.contents.iter().filter(|entry| entry.is_selected())
     }
@@ -119,13 +273,118 @@ impl JoshutoDirList {
     }
 }
 
-fn read_dir_list<F>(path: &path::Path, filter_func: F) -> std::io::Result<Vec<JoshutoDirEntry>>
+fn read_dir_list<F>(
+    path: &path::Path,
+    filter_func: F,
+    with_metadata: bool,
+) -> std::io::Result<Vec<JoshutoDirEntry>>
 where
     F: Fn(&Result<fs::DirEntry, std::io::Error>) -> bool,
 {
-    let results: Vec<JoshutoDirEntry> = fs::read_dir(path)?
+    let direntries: Vec<fs::DirEntry> = fs::read_dir(path)?
         .filter(filter_func)
-        .filter_map(|res| JoshutoDirEntry::from(&res.ok()?).ok())
+        .filter_map(|res| res.ok())
+        .collect();
+
+    if !with_metadata || direntries.len() < PREFETCH_MIN_ENTRIES {
+        return Ok(direntries
+            .iter()
+            .filter_map(|d| JoshutoDirEntry::from(d, with_metadata).ok())
+            .collect());
+    }
+    Ok(prefetch_entries(direntries))
+}
+
+/*
+ * stats the entries on a few threads, each taking a contiguous chunk;
+ * joining the chunks in order keeps the result in read_dir order
+ */
+fn prefetch_entries(direntries: Vec<fs::DirEntry>) -> Vec<JoshutoDirEntry> {
+    let chunk_size = (direntries.len() + PREFETCH_THREADS - 1) / PREFETCH_THREADS;
+
+    let mut chunks: Vec<Vec<fs::DirEntry>> = Vec::with_capacity(PREFETCH_THREADS);
+    let mut direntries = direntries.into_iter().peekable();
+    while direntries.peek().is_some() {
+        chunks.push(direntries.by_ref().take(chunk_size).collect());
+    }
+
+    let handles: Vec<thread::JoinHandle<Vec<JoshutoDirEntry>>> = chunks
+        .into_iter()
+        .map(|chunk| {
+            thread::spawn(move || {
+                chunk
+                    .iter()
+                    .filter_map(|d| JoshutoDirEntry::from(d, true).ok())
+                    .collect()
+            })
+        })
         .collect();
-    Ok(results)
+
+    handles
+        .into_iter()
+        .filter_map(|handle| handle.join().ok())
+        .flatten()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::test_dir::TempDir;
+
+    fn sort_option(show_hidden: bool) -> SortOption {
+        SortOption {
+            show_hidden,
+            ..SortOption::default()
+        }
+    }
+
+    fn dir_with_hidden() -> TempDir {
+        let dir = TempDir::new();
+        for name in &[".a", ".b", "c", "d", "e"] {
+            dir.touch(name);
+        }
+        dir
+    }
+
+    fn move_to(list: &mut JoshutoDirList, name: &str) {
+        list.index = list.contents.iter().position(|e| e.file_name() == name);
+        assert!(list.index.is_some());
+    }
+
+    fn curr_name(list: &JoshutoDirList) -> Option<&str> {
+        list.index.map(|i| list.contents[i].file_name())
+    }
+
+    #[test]
+    fn toggling_hidden_keeps_the_cursor_on_its_entry() {
+        let dir = dir_with_hidden();
+        let mut list = JoshutoDirList::new(dir.path().to_path_buf(), &sort_option(false)).unwrap();
+        assert_eq!(list.contents.len(), 3);
+        move_to(&mut list, "d");
+
+        list.reload_contents(&sort_option(true)).unwrap();
+        assert_eq!(list.contents.len(), 5);
+        assert_eq!(curr_name(&list), Some("d"));
+
+        list.reload_contents(&sort_option(false)).unwrap();
+        assert_eq!(list.contents.len(), 3);
+        assert_eq!(curr_name(&list), Some("d"));
+    }
+
+    #[test]
+    fn hiding_the_entry_under_the_cursor_moves_to_the_next_one_shown() {
+        let dir = dir_with_hidden();
+        let mut list = JoshutoDirList::new(dir.path().to_path_buf(), &sort_option(true)).unwrap();
+        move_to(&mut list, ".b");
+        let index = list.index.unwrap();
+        let expected = list.contents[index..]
+            .iter()
+            .map(|e| e.file_name().to_string())
+            .find(|name| !name.starts_with('.'))
+            .unwrap();
+
+        list.reload_contents(&sort_option(false)).unwrap();
+        assert_eq!(curr_name(&list), Some(expected.as_str()));
+    }
 }
@@ -2,7 +2,8 @@ use std::{fs, path};
 
 use tui::style::{Color, Modifier, Style};
 
-use crate::fs::JoshutoMetadata;
+use crate::config::JoshutoStyleTheme;
+use crate::fs::{FileType, JoshutoMetadata};
 
 use crate::util::unix;
 use crate::THEME_T;
@@ -11,13 +12,15 @@ use crate::THEME_T;
 pub struct JoshutoDirEntry {
     name: String,
     path: path::PathBuf,
-    pub metadata: JoshutoMetadata,
+    file_type: FileType,
+    // only read once needed, unless sorting asks for it up front
+    metadata: Option<JoshutoMetadata>,
     selected: bool,
     marked: bool,
 }
 
 impl JoshutoDirEntry {
-    pub fn from(direntry: &fs::DirEntry) -> std::io::Result<Self> {
+    pub fn from(direntry: &fs::DirEntry, load_metadata: bool) -> std::io::Result<Self> {
         let name = match direntry.file_name().into_string() {
             Ok(s) => s,
             Err(_) => {
@@ -29,34 +32,94 @@ impl JoshutoDirEntry {
         };
 
         let path = direntry.path();
-        let metadata = JoshutoMetadata::from(&path)?;
+        let metadata = if load_metadata {
+            Some(JoshutoMetadata::from(&path)?)
+        } else {
+            None
+        };
+
+        /* read_dir usually knows the file type without a stat */
+        let file_type = match metadata.as_ref() {
+            Some(m) => m.file_type.clone(),
+            None => {
+                let file_type = direntry.file_type()?;
+                if file_type.is_dir() {
+                    FileType::Directory
+                } else if file_type.is_symlink() {
+                    let link = fs::read_link(&path)
+                        .ok()
+                        .and_then(|p| p.to_str().map(String::from))
+                        .unwrap_or_default();
+                    FileType::Symlink(link)
+                } else if let Some(special) = FileType::from_special(&file_type) {
+                    special
+                } else {
+                    FileType::File
+                }
+            }
+        };
 
         Ok(Self {
             name,
             path,
+            file_type,
             metadata,
             selected: false,
             marked: false,
         })
     }
 
+    pub fn file_type(&self) -> &FileType {
+        &self.file_type
+    }
+
+    // whether this is a directory or a symlink to one
+    pub fn is_dir(&self) -> bool {
+        match self.file_type {
+            FileType::Directory => true,
+            FileType::Symlink(_) => self.path.is_dir(),
+            _ => false,
+        }
+    }
+
+    pub fn metadata(&self) -> Option<&JoshutoMetadata> {
+        self.metadata.as_ref()
+    }
+
+    pub fn metadata_mut(&mut self) -> Option<&mut JoshutoMetadata> {
+        self.metadata.as_mut()
+    }
+
+    pub fn load_metadata(&mut self) -> std::io::Result<()> {
+        if self.metadata.is_none() {
+            self.metadata = Some(JoshutoMetadata::from(&self.path)?);
+        }
+        Ok(())
+    }
+
     pub fn file_name(&self) -> &str {
         self.name.as_str()
     }
 
+    // names the entry by its path below base, as flattened listings show it
+    pub fn relative_to(mut self, base: &path::Path) -> Self {
+        if let Ok(p) = self.path.strip_prefix(base) {
+            self.name = p.to_string_lossy().into_owned();
+        }
+        self
+    }
+
     pub fn file_path(&self) -> &path::PathBuf {
         &self.path
     }
 
-    /*
-        pub fn is_marked(&self) -> bool {
-            self.marked
-        }
+    pub fn is_marked(&self) -> bool {
+        self.marked
+    }
 
-        pub fn set_marked(&mut self, marked: bool) {
-            self.marked = marked;
-        }
-    */
+    pub fn set_marked(&mut self, marked: bool) {
+        self.marked = marked;
+    }
 
     pub fn is_selected(&self) -> bool {
         self.selected
@@ -67,13 +130,14 @@ impl JoshutoDirEntry {
     }
 
     pub fn get_modifier(&self) -> Modifier {
-        let metadata = &self.metadata;
-        let filetype = &metadata.file_type;
+        let filetype = &self.file_type;
 
         if filetype.is_dir() {
             THEME_T.directory.modifier
         } else if filetype.is_symlink() {
             THEME_T.link.modifier
+        } else if let Some(theme) = special_theme(filetype) {
+            theme.modifier
         } else {
             match self.file_path().extension() {
                 None => Modifier::empty(),
@@ -88,38 +152,46 @@ impl JoshutoDirEntry {
         }
     }
 
+    // the style of the entry combined with its selected and marked states
     pub fn get_style(&self) -> Style {
-        let metadata = &self.metadata;
-        let filetype = &metadata.file_type;
-
+        let mut style = self.get_type_style();
+        if self.is_marked() {
+            style = THEME_T.marked.apply(style);
+        }
         if self.is_selected() {
-            Style::default()
-                .fg(THEME_T.selection.fg)
-                .bg(THEME_T.selection.bg)
-                .modifier(THEME_T.selection.modifier)
-        } else if filetype.is_dir() {
-            Style::default()
-                .fg(THEME_T.directory.fg)
-                .bg(THEME_T.directory.bg)
-                .modifier(THEME_T.directory.modifier)
+            style = THEME_T.selection.apply(style);
+        }
+        style
+    }
+
+    pub fn get_cursor_style(&self) -> Style {
+        THEME_T.cursor.apply(self.get_style())
+    }
+
+    fn get_type_style(&self) -> Style {
+        let filetype = &self.file_type;
+        let is_executable = self
+            .metadata
+            .as_ref()
+            .map(|m| unix::is_executable(m.mode))
+            .unwrap_or(false);
+
+        if filetype.is_dir() {
+            THEME_T.directory.as_style()
         } else if filetype.is_symlink() {
-            Style::default()
-                .fg(THEME_T.link.fg)
-                .bg(THEME_T.link.bg)
-                .modifier(THEME_T.link.modifier)
-        } else if unix::is_executable(metadata.mode) {
-            Style::default()
-                .fg(THEME_T.executable.fg)
-                .bg(THEME_T.executable.bg)
-                .modifier(THEME_T.executable.modifier)
+            THEME_T.link.as_style()
+        } else if let Some(theme) = special_theme(filetype) {
+            theme.as_style()
+        } else if is_executable {
+            THEME_T.executable.as_style()
         } else {
             match self.file_path().extension() {
-                None => Style::default(),
+                None => THEME_T.regular.as_style(),
                 Some(os_str) => match os_str.to_str() {
-                    None => Style::default(),
+                    None => THEME_T.regular.as_style(),
                     Some(s) => match THEME_T.ext.get(s) {
-                        None => Style::default(),
-                        Some(t) => Style::default().fg(t.fg).bg(t.bg).modifier(t.modifier),
+                        None => THEME_T.regular.as_style(),
+                        Some(t) => t.as_style(),
                     },
                 },
             }
@@ -127,6 +199,15 @@ impl JoshutoDirEntry {
     }
 }
 
+fn special_theme(file_type: &FileType) -> Option<&'static JoshutoStyleTheme> {
+    match file_type {
+        FileType::Fifo => Some(&THEME_T.fifo),
+        FileType::Socket => Some(&THEME_T.socket),
+        FileType::BlockDevice | FileType::CharDevice => Some(&THEME_T.device),
+        _ => None,
+    }
+}
+
 impl std::fmt::Display for JoshutoDirEntry {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
         write!(f, "{}", self.file_name())
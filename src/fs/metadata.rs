@@ -5,6 +5,10 @@ pub enum FileType {
     Directory,
     Symlink(String),
     File,
+    Fifo,
+    Socket,
+    BlockDevice,
+    CharDevice,
 }
 
 impl FileType {
@@ -28,12 +32,61 @@ impl FileType {
             _ => false,
         }
     }
+
+    /*
+     * fifos, sockets and devices are never read like regular files,
+     * opening a fifo blocks until something writes to it
+     */
+    pub fn is_special(&self) -> bool {
+        self.special_name().is_some()
+    }
+
+    pub fn special_name(&self) -> Option<&'static str> {
+        match *self {
+            Self::Fifo => Some("fifo"),
+            Self::Socket => Some("socket"),
+            Self::BlockDevice => Some("block device"),
+            Self::CharDevice => Some("character device"),
+            _ => None,
+        }
+    }
+
+    #[cfg(unix)]
+    pub fn from_special(file_type: &fs::FileType) -> Option<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        if file_type.is_fifo() {
+            Some(Self::Fifo)
+        } else if file_type.is_socket() {
+            Some(Self::Socket)
+        } else if file_type.is_block_device() {
+            Some(Self::BlockDevice)
+        } else if file_type.is_char_device() {
+            Some(Self::CharDevice)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn from_special(_: &fs::FileType) -> Option<Self> {
+        None
+    }
+
+    // what path is once symlinks are followed, if that is a special file
+    pub fn special_target(path: &path::Path) -> Option<Self> {
+        fs::metadata(path)
+            .ok()
+            .and_then(|m| Self::from_special(&m.file_type()))
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct JoshutoMetadata {
     pub len: u64,
     pub modified: time::SystemTime,
+    // not every platform or filesystem records when a file was created
+    pub created: Option<time::SystemTime>,
     pub permissions: fs::Permissions,
     pub file_type: FileType,
     pub mimetype: Option<String>,
@@ -54,6 +107,7 @@ impl JoshutoMetadata {
 
         let len = metadata.len();
         let modified = metadata.modified()?;
+        let created = metadata.created().ok();
         let permissions = metadata.permissions();
         let file_type = metadata.file_type();
 
@@ -68,6 +122,8 @@ impl JoshutoMetadata {
                 }
             }
             FileType::Symlink(link)
+        } else if let Some(special) = FileType::from_special(&file_type) {
+            special
         } else {
             FileType::File
         };
@@ -90,6 +146,7 @@ impl JoshutoMetadata {
         Ok(Self {
             len,
             modified,
+            created,
             permissions,
             file_type,
             mimetype,
@@ -123,3 +180,72 @@ fn file_mimetype(path: &path::Path) -> Option<String> {
         Err(_) => None,
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::net::UnixListener;
+
+    use super::*;
+    use crate::util::test_dir::TempDir;
+
+    fn mkfifo(path: &path::Path) {
+        let path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        assert_eq!(unsafe { libc::mkfifo(path.as_ptr(), 0o644) }, 0);
+    }
+
+    #[test]
+    fn classifies_fifos() {
+        let dir = TempDir::new();
+        let path = dir.path().join("fifo");
+        mkfifo(path.as_path());
+        let metadata = JoshutoMetadata::from(path.as_path()).unwrap();
+        assert_eq!(metadata.file_type.special_name(), Some("fifo"));
+    }
+
+    #[test]
+    fn classifies_sockets() {
+        let dir = TempDir::new();
+        let path = dir.path().join("socket");
+        let _listener = UnixListener::bind(&path).unwrap();
+        let metadata = JoshutoMetadata::from(path.as_path()).unwrap();
+        assert_eq!(metadata.file_type.special_name(), Some("socket"));
+    }
+
+    #[test]
+    fn classifies_char_devices() {
+        let metadata = JoshutoMetadata::from(path::Path::new("/dev/null")).unwrap();
+        assert!(metadata.file_type.is_special());
+        assert!(matches!(metadata.file_type, FileType::CharDevice));
+    }
+
+    #[test]
+    fn regular_files_and_directories_are_not_special() {
+        let dir = TempDir::new();
+        let file = dir.touch("file");
+        assert!(!JoshutoMetadata::from(file.as_path())
+            .unwrap()
+            .file_type
+            .is_special());
+        assert!(!JoshutoMetadata::from(dir.path())
+            .unwrap()
+            .file_type
+            .is_special());
+    }
+
+    #[test]
+    fn special_target_follows_symlinks() {
+        let dir = TempDir::new();
+        let fifo = dir.path().join("fifo");
+        mkfifo(fifo.as_path());
+        let link = dir.path().join("link");
+        std::os::unix::fs::symlink(&fifo, &link).unwrap();
+
+        let metadata = JoshutoMetadata::from(link.as_path()).unwrap();
+        assert!(metadata.file_type.is_symlink());
+        let target = FileType::special_target(link.as_path());
+        assert_eq!(target.and_then(|t| t.special_name()), Some("fifo"));
+        assert!(FileType::special_target(dir.touch("file").as_path()).is_none());
+    }
+}
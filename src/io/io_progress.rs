@@ -0,0 +1,85 @@
+use std::collections::VecDeque;
+use std::time;
+
+// speed is averaged over the progress reported within this window
+const SPEED_WINDOW: time::Duration = time::Duration::from_secs(5);
+// too short of a window gives wildly varying speeds, so don't report one yet
+const SPEED_MIN_ELAPSED: time::Duration = time::Duration::from_secs(1);
+// weight of the newest estimate when smoothing the time remaining
+const ETA_SMOOTHING: f64 = 0.2;
+
+/*
+ * keeps track of how many bytes an io worker has processed over time,
+ * a new one is created for each worker
+ */
+#[derive(Clone, Debug)]
+pub struct IOProgress {
+    samples: VecDeque<(time::Instant, u64)>,
+    total: Option<u64>,
+    eta: Option<f64>,
+}
+
+impl IOProgress {
+    pub fn new(total: Option<u64>) -> Self {
+        let mut samples = VecDeque::with_capacity(16);
+        samples.push_back((time::Instant::now(), 0));
+        Self {
+            samples,
+            total,
+            eta: None,
+        }
+    }
+
+    pub fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    // the total is counted by the worker itself, so it can arrive late
+    pub fn set_total(&mut self, total: u64) {
+        self.total = Some(total);
+    }
+
+    pub fn processed(&self) -> u64 {
+        self.samples.back().map(|s| s.1).unwrap_or(0)
+    }
+
+    pub fn update(&mut self, processed: u64) {
+        let now = time::Instant::now();
+        self.samples.push_back((now, processed));
+        /* keep one sample from before the window so it stays fully covered */
+        while self.samples.len() > 2 && now.duration_since(self.samples[1].0) >= SPEED_WINDOW {
+            self.samples.pop_front();
+        }
+
+        let remaining = self.total.map(|total| total.saturating_sub(processed));
+        self.eta = match (remaining, self.speed()) {
+            (Some(remaining), Some(speed)) if speed > 0 => {
+                let estimate = remaining as f64 / speed as f64;
+                match self.eta {
+                    Some(eta) => Some(ETA_SMOOTHING * estimate + (1.0 - ETA_SMOOTHING) * eta),
+                    None => Some(estimate),
+                }
+            }
+            _ => self.eta,
+        };
+    }
+
+    /*
+     * smoothed estimate of the time remaining, unknown until a speed
+     * can be measured or if the total size was never computed
+     */
+    pub fn eta(&self) -> Option<time::Duration> {
+        self.eta.map(time::Duration::from_secs_f64)
+    }
+
+    // bytes per second over the window, if enough time has passed to tell
+    pub fn speed(&self) -> Option<u64> {
+        let (first_time, first_processed) = *self.samples.front()?;
+        let elapsed = time::Instant::now().duration_since(first_time);
+        if elapsed < SPEED_MIN_ELAPSED {
+            return None;
+        }
+        let bytes = self.processed().saturating_sub(first_processed);
+        Some((bytes as f64 / elapsed.as_secs_f64()) as u64)
+    }
+}
@@ -0,0 +1,70 @@
+use std::path;
+
+/*
+ * what became of each entry of a multi-file operation,
+ * so a partial failure doesn't hide behind a single status line
+ */
+#[derive(Clone, Debug)]
+pub struct IOResults {
+    pub operation: &'static str,
+    pub succeeded: Vec<path::PathBuf>,
+    pub failed: Vec<(path::PathBuf, String)>,
+}
+
+impl IOResults {
+    pub fn new(operation: &'static str) -> Self {
+        Self {
+            operation,
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+
+    // records the outcome for path, handing back the value if it succeeded
+    pub fn push<T>(&mut self, path: &path::Path, res: std::io::Result<T>) -> Option<T> {
+        match res {
+            Ok(s) => {
+                self.succeeded.push(path.to_path_buf());
+                Some(s)
+            }
+            Err(e) => {
+                self.failed.push((path.to_path_buf(), e.to_string()));
+                None
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.succeeded.len() + self.failed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn summary(&self) -> String {
+        format!(
+            "{}: {} succeeded, {} failed",
+            self.operation,
+            self.succeeded.len(),
+            self.failed.len()
+        )
+    }
+
+    // one line per entry, failures first so they aren't cut off
+    pub fn lines(&self) -> Vec<String> {
+        let name = |p: &path::Path| match p.file_name() {
+            Some(s) => s.to_string_lossy().to_string(),
+            None => p.to_string_lossy().to_string(),
+        };
+        let failed = self
+            .failed
+            .iter()
+            .map(|(p, e)| format!("  failed  {}: {}", name(p), e));
+        let succeeded = self
+            .succeeded
+            .iter()
+            .map(|p| format!("  done    {}", name(p)));
+        failed.chain(succeeded).collect()
+    }
+}
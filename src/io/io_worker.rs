@@ -8,6 +8,16 @@ use crate::util::event::Event;
 pub struct Options {
     pub overwrite: bool,
     pub skip_exist: bool,
+    // copy what symlinks point to instead of the links themselves
+    pub dereference: bool,
+    // paste here instead of the current directory
+    pub destination: Option<path::PathBuf>,
+    // also copy extended attributes, which is where ACLs and SELinux contexts live
+    pub preserve_xattrs: bool,
+    // give copies the access and modification times of the originals
+    pub preserve_times: bool,
+    // flush every pasted file to disk before going on, and the destination once done
+    pub fsync: bool,
 }
 
 impl std::default::Default for Options {
@@ -15,12 +25,26 @@ impl std::default::Default for Options {
         Self {
             overwrite: false,
             skip_exist: false,
+            dereference: false,
+            destination: None,
+            preserve_xattrs: false,
+            preserve_times: false,
+            fsync: false,
         }
     }
 }
 
+// what an io worker reports while it runs
+#[derive(Clone, Copy, Debug)]
+pub enum IOWorkerProgress {
+    // number of bytes to be processed, once counted
+    Total(u64),
+    // number of bytes processed so far
+    Processed(u64),
+}
+
 pub struct IOWorkerObserver {
-    pub src: path::PathBuf,
+    pub src: Vec<path::PathBuf>,
     pub dest: path::PathBuf,
     pub handle: std::thread::JoinHandle<()>,
 }
@@ -32,8 +56,12 @@ impl IOWorkerObserver {
 
         let handle = thread::spawn(move || {
             worker.start();
-            while let Ok(copied) = worker.recv() {
-                let _ = event_tx.send(Event::IOWorkerProgress(copied));
+            while let Ok(progress) = worker.recv() {
+                let event = match progress {
+                    IOWorkerProgress::Total(total) => Event::IOWorkerTotal(total),
+                    IOWorkerProgress::Processed(copied) => Event::IOWorkerProgress(copied),
+                };
+                let _ = event_tx.send(event);
             }
             let res = worker.join();
             let _ = event_tx.send(Event::IOWorkerResult(res));
@@ -48,19 +76,40 @@ impl IOWorkerObserver {
 }
 
 pub struct IOWorkerThread {
-    pub src: path::PathBuf,
+    // every directory entries are taken from, reloaded once the worker is done
+    pub src: Vec<path::PathBuf>,
     pub dest: path::PathBuf,
     pub handle: thread::JoinHandle<std::io::Result<u64>>,
     pub tx_start: mpsc::Sender<()>,
-    pub rx: mpsc::Receiver<u64>,
+    pub rx: mpsc::Receiver<IOWorkerProgress>,
 }
 
 impl IOWorkerThread {
+    // a worker running work once started, for jobs that report no progress
+    pub fn spawn<F>(src: Vec<path::PathBuf>, dest: path::PathBuf, work: F) -> Self
+    where
+        F: FnOnce() -> std::io::Result<u64> + Send + 'static,
+    {
+        let (tx_start, rx_start) = mpsc::channel();
+        let (_tx, rx) = mpsc::channel();
+        let handle = thread::spawn(move || match rx_start.recv() {
+            Ok(_) => work(),
+            Err(_) => Ok(0),
+        });
+        Self {
+            src,
+            dest,
+            handle,
+            tx_start,
+            rx,
+        }
+    }
+
     pub fn start(&self) {
         self.tx_start.send(());
     }
 
-    pub fn recv(&self) -> Result<u64, mpsc::RecvError> {
+    pub fn recv(&self) -> Result<IOWorkerProgress, mpsc::RecvError> {
         self.rx.recv()
     }
 
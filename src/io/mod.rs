@@ -1,3 +1,7 @@
+mod io_progress;
+mod io_results;
 mod io_worker;
 
-pub use self::io_worker::{IOWorkerObserver, IOWorkerThread, Options};
+pub use self::io_progress::IOProgress;
+pub use self::io_results::IOResults;
+pub use self::io_worker::{IOWorkerObserver, IOWorkerProgress, IOWorkerThread, Options};
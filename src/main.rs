@@ -5,6 +5,7 @@ mod error;
 mod fs;
 mod history;
 mod io;
+mod preview;
 mod run;
 mod tab;
 mod ui;
@@ -31,11 +32,7 @@ const PREVIEW_FILE: &str = "preview.toml";
 lazy_static! {
     // dynamically builds the config hierarchy
     static ref CONFIG_HIERARCHY: Vec<PathBuf> = {
-        let mut temp = vec![];
-        match xdg::BaseDirectories::with_prefix(PROGRAM_NAME) {
-            Ok(dirs) => temp.push(dirs.get_config_home()),
-            Err(e) => eprintln!("{}", e),
-        };
+        let mut temp = util::xdg_dirs::config_dirs();
         // adds the default config files to the config hierarchy if running through cargo
         if cfg!(debug_assertions) {
             temp.push(PathBuf::from("./config"));
@@ -57,6 +54,9 @@ pub struct Args {
     path: Option<PathBuf>,
     #[structopt(short = "v", long = "version")]
     version: bool,
+    // browse without changing anything on disk
+    #[structopt(long = "readonly")]
+    readonly: bool,
 }
 
 fn main() {
@@ -77,10 +77,14 @@ fn main() {
         }
     }
 
+    // load the theme up front so parse errors are reported before the ui starts
+    lazy_static::initialize(&THEME_T);
+
     let config = JoshutoConfig::get_config();
     let keymap = JoshutoCommandMapping::get_config();
 
-    match run(config, keymap) {
+    ui::install_panic_hook();
+    match run(config, keymap, args.readonly) {
         Ok(_) => {}
         Err(e) => {
             eprintln!("{}", e);
@@ -0,0 +1,83 @@
+use std::fs;
+use std::io::Read;
+use std::path;
+use std::time;
+
+// how much of the start of a file is checked for signs of binary data
+const BINARY_CHECK_LEN: usize = 1024;
+pub const BYTES_PER_ROW_MAX: usize = 16;
+
+// like git, a file counts as binary if its start contains a nul byte
+pub fn is_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_CHECK_LEN).any(|b| *b == 0)
+}
+
+// the widest row of bytes that fits: 8 digit offset, hex bytes, ascii gutter
+pub fn bytes_per_row(width: u16) -> usize {
+    let width = width as usize;
+    [BYTES_PER_ROW_MAX, 8, 4]
+        .iter()
+        .cloned()
+        .find(|n| 8 + 2 + n * 3 + 1 + n <= width)
+        .unwrap_or(1)
+}
+
+#[derive(Clone, Debug)]
+pub struct HexPreview {
+    pub path: path::PathBuf,
+    pub modified: time::SystemTime,
+    pub bytes: Vec<u8>,
+    pub binary: bool,
+}
+
+impl HexPreview {
+    /*
+     * reads at most limit bytes from the start of the file,
+     * never less than needed to tell whether it is binary
+     */
+    pub fn from_path(
+        path: &path::Path,
+        modified: time::SystemTime,
+        limit: usize,
+    ) -> std::io::Result<Self> {
+        let limit = limit.max(BINARY_CHECK_LEN);
+        let mut bytes = Vec::with_capacity(limit);
+        fs::File::open(path)?
+            .take(limit as u64)
+            .read_to_end(&mut bytes)?;
+        let binary = is_binary(&bytes);
+        Ok(Self {
+            path: path.to_path_buf(),
+            modified,
+            bytes,
+            binary,
+        })
+    }
+
+    // offset, hex bytes and printable characters of each row
+    pub fn rows(&self, per_row: usize) -> impl Iterator<Item = String> + '_ {
+        self.bytes
+            .chunks(per_row)
+            .enumerate()
+            .map(move |(i, chunk)| {
+                let mut hex = String::with_capacity(per_row * 3);
+                for b in chunk {
+                    hex.push_str(&format!("{:02x} ", b));
+                }
+                let ascii: String = chunk
+                    .iter()
+                    .map(|b| match *b {
+                        0x20..=0x7e => *b as char,
+                        _ => '.',
+                    })
+                    .collect();
+                format!(
+                    "{:08x}  {:<width$} {}",
+                    i * per_row,
+                    hex,
+                    ascii,
+                    width = per_row * 3
+                )
+            })
+    }
+}
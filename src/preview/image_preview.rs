@@ -0,0 +1,197 @@
+use std::collections::{HashMap, HashSet};
+use std::path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time;
+
+use image::GenericImageView;
+use tui::style::Color;
+
+use crate::util::event::Event;
+
+// number of rendered images kept around before the cache is cleared
+const CACHE_CAPACITY: usize = 64;
+// how long the cursor has to stay on an image before it is decoded
+const REQUEST_DELAY: time::Duration = time::Duration::from_millis(60);
+
+const IMAGE_EXTENSIONS: [&str; 5] = ["bmp", "gif", "jpeg", "jpg", "png"];
+
+pub fn is_image(path: &path::Path) -> bool {
+    match path.extension().and_then(|s| s.to_str()) {
+        Some(ext) => {
+            let ext = ext.to_lowercase();
+            IMAGE_EXTENSIONS.iter().any(|s| *s == ext.as_str())
+        }
+        None => false,
+    }
+}
+
+// whether the terminal can display the 256 colors used by image previews
+pub fn terminal_supports_color() -> bool {
+    if std::env::var_os("COLORTERM").is_some() {
+        return true;
+    }
+    match std::env::var("TERM") {
+        Ok(term) => term.contains("256color"),
+        Err(_) => false,
+    }
+}
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+pub struct ImagePreviewKey {
+    pub path: path::PathBuf,
+    pub modified: time::SystemTime,
+    pub width: u16,
+    pub height: u16,
+}
+
+/*
+ * an image rendered into terminal cells, each cell holds two vertically
+ * stacked pixels drawn as an upper half block: fg on top, bg below
+ */
+#[derive(Clone, Debug)]
+pub struct ImagePreview {
+    pub width: u16,
+    pub height: u16,
+    pub cells: Vec<(Color, Color)>,
+}
+
+impl ImagePreview {
+    pub fn from_path(path: &path::Path, width: u16, height: u16) -> Option<Self> {
+        if width == 0 || height == 0 {
+            return None;
+        }
+        let img = image::open(path).ok()?;
+        let img = img.thumbnail(width as u32, height as u32 * 2);
+        let (img_width, img_height) = img.dimensions();
+        let img = img.to_rgb8();
+
+        let cell_rows = (img_height + 1) / 2;
+        let mut cells = Vec::with_capacity((img_width * cell_rows) as usize);
+        for y in 0..cell_rows {
+            for x in 0..img_width {
+                let top = img.get_pixel(x, y * 2);
+                let fg = rgb_to_ansi256(top[0], top[1], top[2]);
+                let bg = if y * 2 + 1 < img_height {
+                    let bottom = img.get_pixel(x, y * 2 + 1);
+                    rgb_to_ansi256(bottom[0], bottom[1], bottom[2])
+                } else {
+                    Color::Reset
+                };
+                cells.push((fg, bg));
+            }
+        }
+        Some(Self {
+            width: img_width as u16,
+            height: cell_rows as u16,
+            cells,
+        })
+    }
+}
+
+pub struct ImagePreviewCache {
+    previews: HashMap<ImagePreviewKey, Option<ImagePreview>>,
+    pending: HashSet<ImagePreviewKey>,
+    // bumped whenever the wanted preview changes, so older jobs know to stop
+    generation: Arc<AtomicUsize>,
+}
+
+impl ImagePreviewCache {
+    pub fn new() -> Self {
+        Self {
+            previews: HashMap::new(),
+            pending: HashSet::new(),
+            generation: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn get(&self, key: &ImagePreviewKey) -> Option<&ImagePreview> {
+        self.previews.get(key)?.as_ref()
+    }
+
+    pub fn insert(&mut self, key: ImagePreviewKey, preview: Option<ImagePreview>) {
+        self.pending.remove(&key);
+        if self.previews.len() >= CACHE_CAPACITY {
+            self.previews.clear();
+        }
+        self.previews.insert(key, preview);
+    }
+
+    // stops the jobs in progress, their previews are no longer wanted
+    pub fn cancel(&mut self) {
+        if !self.pending.is_empty() {
+            self.pending.clear();
+            self.generation.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /*
+     * decodes the image on a separate thread unless it is cached or in progress.
+     * the thread waits a moment first and gives up if another preview was
+     * requested meanwhile, so scrolling past images doesn't decode each one
+     */
+    pub fn request(&mut self, key: ImagePreviewKey, event_tx: mpsc::Sender<Event>) {
+        if self.previews.contains_key(&key) || self.pending.contains(&key) {
+            return;
+        }
+        self.pending.clear();
+        let generation = self.generation.fetch_add(1, Ordering::SeqCst) + 1;
+        self.pending.insert(key.clone());
+        let current = self.generation.clone();
+        thread::spawn(move || {
+            let is_current = || current.load(Ordering::SeqCst) == generation;
+            thread::sleep(REQUEST_DELAY);
+            if !is_current() {
+                return;
+            }
+            let preview = ImagePreview::from_path(key.path.as_path(), key.width, key.height);
+            if is_current() {
+                let _ = event_tx.send(Event::ImagePreview(key, preview));
+            }
+        });
+    }
+}
+
+// approximates a color with the closest entry of the xterm 256 color palette
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> Color {
+    fn cube_index(v: u8) -> u8 {
+        if v < 48 {
+            0
+        } else if v < 115 {
+            1
+        } else {
+            (v - 35) / 40
+        }
+    }
+    const CUBE_VALUES: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+    let (ri, gi, bi) = (cube_index(r), cube_index(g), cube_index(b));
+    let cube_color = (
+        CUBE_VALUES[ri as usize],
+        CUBE_VALUES[gi as usize],
+        CUBE_VALUES[bi as usize],
+    );
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let gray_index = if average > 238 {
+        23
+    } else {
+        (average.saturating_sub(3) / 10) as u8
+    };
+    let gray_value = 8 + 10 * gray_index;
+    let gray_color = (gray_value, gray_value, gray_value);
+
+    let distance = |c: (u8, u8, u8)| -> u32 {
+        let dr = c.0 as i32 - r as i32;
+        let dg = c.1 as i32 - g as i32;
+        let db = c.2 as i32 - b as i32;
+        (dr * dr + dg * dg + db * db) as u32
+    };
+
+    if distance(gray_color) < distance(cube_color) {
+        Color::Indexed(232 + gray_index)
+    } else {
+        Color::Indexed(16 + 36 * ri + 6 * gi + bi)
+    }
+}
@@ -0,0 +1,128 @@
+pub mod hex_preview;
+pub mod image_preview;
+pub mod symlink_info;
+
+use tui::layout::Rect;
+
+use crate::context::JoshutoContext;
+use crate::fs::FileType;
+use crate::ui::{self, TuiBackend};
+
+use self::hex_preview::{HexPreview, BYTES_PER_ROW_MAX};
+use self::image_preview::{is_image, terminal_supports_color, ImagePreviewKey};
+use self::symlink_info::SymlinkInfo;
+
+// the key of the image preview for the entry under the cursor, if it is one
+pub fn image_preview_key(context: &JoshutoContext, f_size: Rect) -> Option<ImagePreviewKey> {
+    if !context.config_t.image_preview || !context.config_t.right_pane.shows_preview() {
+        return None;
+    }
+    let entry = context.curr_tab_ref().curr_list_ref()?.get_curr_ref()?;
+    let metadata = entry.metadata()?;
+    if !entry.file_type().is_file()
+        || !is_image(entry.file_path())
+        || metadata.len > context.config_t.max_preview_size
+    {
+        return None;
+    }
+
+    let area = ui::preview_area(f_size, &context.config_t);
+    Some(ImagePreviewKey {
+        path: entry.file_path().clone(),
+        modified: metadata.modified,
+        width: area.width,
+        height: area.height,
+    })
+}
+
+// the hex dump of the binary file under the cursor, if one was loaded for it
+pub fn hex_preview(context: &JoshutoContext) -> Option<&HexPreview> {
+    if !context.config_t.hex_preview {
+        return None;
+    }
+    let entry = context.curr_tab_ref().curr_list_ref()?.get_curr_ref()?;
+    let metadata = entry.metadata()?;
+    context
+        .hex_preview
+        .as_ref()
+        .filter(|p| p.binary && p.path == *entry.file_path() && p.modified == metadata.modified)
+}
+
+fn load_hex_preview(context: &mut JoshutoContext, f_size: Rect) {
+    if !context.config_t.hex_preview || !context.config_t.right_pane.shows_preview() {
+        return;
+    }
+    let (path, modified) = match context
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|s| s.get_curr_ref())
+    {
+        Some(entry) if entry.file_type().is_file() => match entry.metadata() {
+            Some(m) => (entry.file_path().clone(), m.modified),
+            None => return,
+        },
+        _ => return,
+    };
+    if let Some(p) = context.hex_preview.as_ref() {
+        if p.path == path && p.modified == modified {
+            return;
+        }
+    }
+    /* only as much as fits in the pane is read */
+    let area = ui::preview_area(f_size, &context.config_t);
+    let limit = area.height as usize * BYTES_PER_ROW_MAX;
+    context.hex_preview = HexPreview::from_path(path.as_path(), modified, limit).ok();
+}
+
+// where the symlink under the cursor leads, if it was resolved since it last changed
+pub fn symlink_info(context: &JoshutoContext) -> Option<&SymlinkInfo> {
+    let entry = context.curr_tab_ref().curr_list_ref()?.get_curr_ref()?;
+    match entry.file_type() {
+        FileType::Symlink(link) => context
+            .symlink_info
+            .as_ref()
+            .filter(|i| i.path == *entry.file_path() && i.link == *link),
+        _ => None,
+    }
+}
+
+fn load_symlink_info(context: &mut JoshutoContext) {
+    if !context.config_t.right_pane.shows_preview() {
+        return;
+    }
+    let (path, link) = match context
+        .curr_tab_ref()
+        .curr_list_ref()
+        .and_then(|s| s.get_curr_ref())
+    {
+        Some(entry) => match entry.file_type() {
+            FileType::Symlink(link) => (entry.file_path().clone(), link.clone()),
+            _ => return,
+        },
+        None => return,
+    };
+    if symlink_info(context).is_some() {
+        return;
+    }
+    context.symlink_info = Some(SymlinkInfo::from_path(path.as_path(), link));
+}
+
+pub fn load_preview(context: &mut JoshutoContext, backend: &mut TuiBackend) {
+    let f_size = match backend.terminal_mut().size() {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    load_hex_preview(context, f_size);
+    load_symlink_info(context);
+    if !terminal_supports_color() {
+        return;
+    }
+    match image_preview_key(context, f_size) {
+        Some(key) => {
+            let event_tx = context.events.event_tx.clone();
+            context.image_previews.request(key, event_tx);
+        }
+        /* the cursor left the image before it was decoded */
+        None => context.image_previews.cancel(),
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// same limit the kernel uses before giving up with ELOOP
+const MAX_LINK_DEPTH: usize = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkStatus {
+    Directory,
+    File,
+    Broken,
+    Loop,
+}
+
+/*
+ * follows a chain of symlinks one hop at a time, returning every
+ * intermediate target and what the chain ends in
+ */
+fn follow_links(path: &Path) -> (Vec<PathBuf>, LinkStatus) {
+    let mut chain = Vec::new();
+    let mut visited: HashSet<PathBuf> = HashSet::new();
+    let mut curr = path.to_path_buf();
+    visited.insert(curr.clone());
+
+    for _ in 0..MAX_LINK_DEPTH {
+        let target = match fs::read_link(&curr) {
+            Ok(t) => t,
+            Err(_) => return (chain, LinkStatus::Broken),
+        };
+        /* relative targets are relative to the directory holding the link */
+        let target = match curr.parent() {
+            Some(parent) if target.is_relative() => parent.join(target),
+            _ => target,
+        };
+        chain.push(target.clone());
+        if !visited.insert(target.clone()) {
+            return (chain, LinkStatus::Loop);
+        }
+
+        match fs::symlink_metadata(&target) {
+            Ok(metadata) if metadata.file_type().is_symlink() => curr = target,
+            Ok(metadata) if metadata.is_dir() => return (chain, LinkStatus::Directory),
+            Ok(_) => return (chain, LinkStatus::File),
+            Err(_) => return (chain, LinkStatus::Broken),
+        }
+    }
+    (chain, LinkStatus::Loop)
+}
+
+// where a symlink leads, resolved once when the cursor lands on it
+#[derive(Clone, Debug)]
+pub struct SymlinkInfo {
+    pub path: PathBuf,
+    // the target as written in the link
+    pub link: String,
+    pub chain: Vec<PathBuf>,
+    pub status: LinkStatus,
+    // only known when the chain ends in a file or directory
+    pub resolved: Option<PathBuf>,
+}
+
+impl SymlinkInfo {
+    pub fn from_path(path: &Path, link: String) -> Self {
+        let (chain, status) = follow_links(path);
+        let resolved = match status {
+            LinkStatus::Directory | LinkStatus::File => fs::canonicalize(path).ok(),
+            _ => None,
+        };
+        Self {
+            path: path.to_path_buf(),
+            link,
+            chain,
+            status,
+            resolved,
+        }
+    }
+}
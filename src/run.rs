@@ -1,8 +1,12 @@
-use crate::commands::CommandKeybind;
+use std::time::{Duration, Instant};
+
+use crate::commands::{
+    report_results, run_command, CommandKeybind, Grep, JoshutoCommand, ReloadDirList,
+};
 use crate::config::{JoshutoCommandMapping, JoshutoConfig};
 use crate::context::JoshutoContext;
-use crate::history::DirectoryHistory;
-use crate::io::IOWorkerObserver;
+use crate::io::{IOProgress, IOWorkerObserver};
+use crate::preview;
 use crate::tab::JoshutoTab;
 use crate::ui;
 use crate::ui::widgets::{TuiCommandMenu, TuiView};
@@ -10,10 +14,21 @@ use crate::util::event::Event;
 use crate::util::format;
 use crate::util::load_child::LoadChild;
 
-pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io::Result<()> {
+// how often the current directory is checked for changes while following the newest entry
+const FOLLOW_NEWEST_INTERVAL: Duration = Duration::from_secs(1);
+// how often the worker spinner advances while io workers are running
+const SPINNER_INTERVAL: Duration = Duration::from_millis(100);
+
+pub fn run(
+    config_t: JoshutoConfig,
+    keymap_t: JoshutoCommandMapping,
+    readonly: bool,
+) -> std::io::Result<()> {
     let mut backend: ui::TuiBackend = ui::TuiBackend::new()?;
 
     let mut context = JoshutoContext::new(config_t);
+    context.readonly = readonly;
+    context.key_bindings = keymap_t.bindings();
     let curr_path = std::env::current_dir()?;
 
     {
@@ -25,16 +40,23 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
         LoadChild::load_child(&mut context)?;
 
         // render our view
+        load_visible_metadata(&mut context, &mut backend);
+        preview::load_preview(&mut context, &mut backend);
         let view = TuiView::new(&context);
         backend.render(view);
     }
 
     let mut io_observer = None;
+    let mut io_progress = IOProgress::new(None);
+    // per-entry outcomes of the running worker, if it reports them
+    let mut io_results = None;
+    let mut last_refresh = Instant::now();
     while !context.exit {
         /* checking if there are workers that need to be run */
         if !context.worker_queue.is_empty() {
             if io_observer.is_none() {
                 let worker = context.worker_queue.pop_front().unwrap();
+                io_progress = IOProgress::new(None);
                 io_observer = {
                     let event_tx = context.events.event_tx.clone();
                     let observer = IOWorkerObserver::new(worker, event_tx);
@@ -44,14 +66,23 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
             }
         }
 
-        let event = match context.events.next() {
+        let event = match tick_interval(&context) {
+            Some(timeout) => context.events.next_timeout(timeout),
+            None => context.events.next(),
+        };
+        let event = match event {
             Ok(event) => event,
             Err(_) => return Ok(()), // TODO
         };
 
         match event {
             Event::IOWorkerProgress(p) => {
-                context.worker_msg = Some(format!("{} copied", format::file_size_to_string(p)));
+                io_progress.update(p);
+                context.worker_msg = Some(worker_progress_msg(&io_progress));
+            }
+            Event::IOWorkerTotal(total) => {
+                io_progress.set_total(total);
+                context.worker_msg = Some(worker_progress_msg(&io_progress));
             }
             Event::IOWorkerResult(res) => {
                 match io_observer {
@@ -59,9 +90,9 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
                         let src = handle.src.clone();
                         let dest = handle.dest.clone();
                         handle.join();
-                        let msg = match res {
+                        let msg = match res.as_ref() {
                             Ok(s) => {
-                                let size_string = format::file_size_to_string(s);
+                                let size_string = format::file_size_to_string(*s);
                                 format!(
                                     "io_worker completed successfully: {} processed",
                                     size_string
@@ -69,19 +100,128 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
                             }
                             Err(e) => format!("io_worker was not completed: {}", e.to_string()),
                         };
-                        context.message_queue.push_back(msg);
-                        let options = &context.config_t.sort_option;
-                        for tab in context.tabs.iter_mut() {
-                            tab.history.reload(&src, options)?;
-                            tab.history.reload(&dest, options)?;
+                        for src in src.iter() {
+                            ReloadDirList::reload_path(src, &mut context)?;
                         }
+                        ReloadDirList::reload_path(&dest, &mut context)?;
+                        follow_newest(&mut context);
                         LoadChild::load_child(&mut context)?;
+                        match io_results.take() {
+                            Some(results) if res.is_ok() => {
+                                report_results(&results, msg, &mut context, &mut backend)
+                            }
+                            _ => context.message_queue.push_back(msg),
+                        }
                     }
                     None => {}
                 }
                 io_observer = None;
+                io_results = None;
                 context.worker_msg = None;
                 context.worker_busy = false;
+                context.spinner_index = 0;
+            }
+            Event::IOWorkerWarning(msg) => context.message_queue.push_back(msg),
+            Event::IOWorkerResults(results) => io_results = Some(results),
+            Event::ImagePreview(key, preview) => {
+                context.image_previews.insert(key, preview);
+            }
+            Event::DirReload(path, flatten_depth, contents) => match contents {
+                Ok(contents) => {
+                    for tab in context.tabs.iter_mut() {
+                        /* skipping listings flattened or unflattened since the read */
+                        if let Some(dirlist) = tab
+                            .history
+                            .get_mut(&path)
+                            .filter(|d| d.flatten_depth == flatten_depth)
+                        {
+                            if let Err(e) = dirlist.set_contents(contents.clone()) {
+                                context.message_queue.push_back(e.to_string());
+                            }
+                        }
+                    }
+                    follow_newest(&mut context);
+                    LoadChild::load_child(&mut context)?;
+                }
+                Err(e) => context.message_queue.push_back(e.to_string()),
+            },
+            Event::DirSizeProgress(path, size) => {
+                if context.size_pending.contains_key(&path) {
+                    context.size_msg = Some(format!(
+                        "Calculating size of {}: {} files, {}",
+                        path.to_string_lossy(),
+                        size.files,
+                        format::file_size_to_string(size.bytes)
+                    ));
+                }
+            }
+            Event::DirSizeResult(path, size) => {
+                for tab in context.tabs.iter_mut() {
+                    if let Some(dirlist) = tab.history.get_mut(&path) {
+                        dirlist.recursive_size = Some(size.bytes);
+                    }
+                }
+                if let Some(true) = context.size_pending.remove(&path) {
+                    context.message_queue.push_back(format!(
+                        "{}: {} files, {}",
+                        path.to_string_lossy(),
+                        size.files,
+                        format::file_size_to_string(size.bytes)
+                    ));
+                }
+                if context.size_pending.is_empty() {
+                    context.size_msg = None;
+                }
+            }
+            Event::GrepDone => {}
+            Event::Synced(elapsed) => context.message_queue.push_back(format!(
+                "Pending writes flushed to disk in {:.1}s",
+                elapsed.as_secs_f64()
+            )),
+            Event::Tick => {
+                let mut changed = false;
+                if context.config_t.follow_newest {
+                    let options = &context.config_t.sort_option;
+                    let curr_tab = &mut context.tabs[context.curr_tab_index];
+                    let reloaded = match curr_tab.curr_list_mut() {
+                        Some(curr_list) if curr_list.modified_on_disk() => {
+                            curr_list.reload_contents(options).map(|_| true)
+                        }
+                        _ => Ok(false),
+                    };
+                    match reloaded {
+                        Ok(true) => {
+                            follow_newest(&mut context);
+                            LoadChild::load_child(&mut context)?;
+                            changed = true;
+                        }
+                        Ok(false) => {}
+                        Err(e) => {
+                            context.message_queue.push_back(e.to_string());
+                            changed = true;
+                        }
+                    }
+                }
+                let refresh_interval = Duration::from_secs(context.config_t.auto_refresh_interval);
+                if context.config_t.auto_refresh_interval > 0
+                    && !context.worker_busy
+                    && last_refresh.elapsed() >= refresh_interval
+                {
+                    last_refresh = Instant::now();
+                    match ReloadDirList::reload(context.curr_tab_index, &mut context) {
+                        Ok(_) => {
+                            follow_newest(&mut context);
+                            LoadChild::load_child(&mut context)?;
+                        }
+                        Err(e) => context.message_queue.push_back(e.to_string()),
+                    }
+                    changed = true;
+                }
+                if context.worker_busy {
+                    context.spinner_index = context.spinner_index.wrapping_add(1);
+                } else if !changed {
+                    continue;
+                }
             }
             Event::Input(key) => {
                 /* Message handling */
@@ -95,9 +235,7 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
                             .push_back(format!("Unknown keycode: {:?}", key));
                     }
                     Some(CommandKeybind::SimpleKeybind(command)) => {
-                        if let Err(e) = command.execute(&mut context, &mut backend) {
-                            context.message_queue.push_back(e.to_string());
-                        }
+                        execute_command(command.as_ref(), &mut context, &mut backend);
                     }
                     Some(CommandKeybind::CompositeKeybind(m)) => {
                         let cmd = {
@@ -106,18 +244,97 @@ pub fn run(config_t: JoshutoConfig, keymap_t: JoshutoCommandMapping) -> std::io:
                         };
 
                         if let Some(command) = cmd {
-                            if let Err(e) = command.execute(&mut context, &mut backend) {
-                                context.message_queue.push_back(e.to_string());
-                            }
+                            execute_command(command.as_ref(), &mut context, &mut backend);
                         }
                     }
                 }
                 context.events.flush();
             }
         }
+        /* checked after every event, as a prompt may have taken GrepDone */
+        let grep_results = context.grep_results.lock().unwrap().take();
+        if let Some(results) = grep_results {
+            if let Err(e) = Grep::show_results(results, &mut context, &mut backend) {
+                context.message_queue.push_back(e.to_string());
+            }
+        }
+        load_visible_metadata(&mut context, &mut backend);
+        preview::load_preview(&mut context, &mut backend);
         let view = TuiView::new(&context);
         backend.render(view);
     }
+    /* a failed save shouldn't keep the other from being written */
+    let frecency_res = context.frecency.save();
+    let open_with_res = context.open_with_history.save();
+    frecency_res?;
+    open_with_res?;
 
     Ok(())
 }
+
+// runs a command from the keymap
+fn execute_command(
+    command: &dyn JoshutoCommand,
+    context: &mut JoshutoContext,
+    backend: &mut ui::TuiBackend,
+) {
+    if let Err(e) = run_command(command, context, backend) {
+        context.message_queue.push_back(e.to_string());
+    }
+}
+
+fn load_visible_metadata(context: &mut JoshutoContext, backend: &mut ui::TuiBackend) {
+    if let Ok(f_size) = backend.terminal_mut().size() {
+        LoadChild::load_visible_metadata(context, f_size.height as usize);
+    }
+}
+
+// how long to wait for an event before ticking, None if nothing needs ticks
+fn tick_interval(context: &JoshutoContext) -> Option<Duration> {
+    if context.worker_busy {
+        return Some(SPINNER_INTERVAL);
+    }
+    let follow = if context.config_t.follow_newest {
+        Some(FOLLOW_NEWEST_INTERVAL)
+    } else {
+        None
+    };
+    let refresh = match context.config_t.auto_refresh_interval {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    };
+    match (follow, refresh) {
+        (Some(follow), Some(refresh)) => Some(follow.min(refresh)),
+        (follow, refresh) => follow.or(refresh),
+    }
+}
+
+// moves the cursor of the current directory to its newest entry when following
+fn follow_newest(context: &mut JoshutoContext) {
+    if !context.config_t.follow_newest {
+        return;
+    }
+    if let Some(curr_list) = context.curr_tab_mut().curr_list_mut() {
+        curr_list.select_newest();
+    }
+}
+
+fn worker_progress_msg(progress: &IOProgress) -> String {
+    let processed = format::file_size_to_string(progress.processed());
+    let mut msg = match progress.total() {
+        Some(total) => format!(
+            "{} / {} copied",
+            processed,
+            format::file_size_to_string(total)
+        ),
+        None => format!("{} copied", processed),
+    };
+    if let Some(speed) = progress.speed() {
+        msg.push_str(&format!(" ({}/s)", format::file_size_to_string(speed)));
+    }
+    match progress.eta() {
+        Some(eta) => msg.push_str(&format!(" ETA: {}", format::duration_to_string(eta))),
+        None => msg.push_str(" ETA: unknown"),
+    }
+    msg
+}
@@ -4,9 +4,40 @@ use crate::fs::JoshutoDirList;
 use crate::history::{DirectoryHistory, JoshutoHistory};
 use crate::util::sort;
 
+// the listings beside the current one, which can be scrolled on their own
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SidePane {
+    Parent,
+    Child,
+}
+
+impl SidePane {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "parent" => Some(SidePane::Parent),
+            "child" => Some(SidePane::Child),
+            _ => None,
+        }
+    }
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            SidePane::Parent => "parent",
+            SidePane::Child => "child",
+        }
+    }
+}
+
 pub struct JoshutoTab {
     pub history: JoshutoHistory,
     pub curr_path: PathBuf,
+    /*
+     * lines the parent and child listings are scrolled from where their
+     * cursor puts them, with the directory that was scrolled. showing
+     * another directory there leaves it unscrolled
+     */
+    parent_scroll: Option<(PathBuf, isize)>,
+    child_scroll: Option<(PathBuf, isize)>,
 }
 
 impl JoshutoTab {
@@ -14,7 +45,12 @@ impl JoshutoTab {
         let mut history = JoshutoHistory::new();
         history.populate_to_root(&curr_path, sort_option)?;
 
-        Ok(Self { curr_path, history })
+        Ok(Self {
+            curr_path,
+            history,
+            parent_scroll: None,
+            child_scroll: None,
+        })
     }
 
     pub fn curr_list_ref(&self) -> Option<&JoshutoDirList> {
@@ -51,4 +87,36 @@ impl JoshutoTab {
 
         self.history.get_mut(path.as_path())
     }
+
+    fn side_list_ref(&self, pane: SidePane) -> Option<&JoshutoDirList> {
+        match pane {
+            SidePane::Parent => self.parent_list_ref(),
+            SidePane::Child => self.child_list_ref(),
+        }
+    }
+
+    pub fn pane_scroll(&self, pane: SidePane) -> isize {
+        let scroll = match pane {
+            SidePane::Parent => &self.parent_scroll,
+            SidePane::Child => &self.child_scroll,
+        };
+        match (scroll, self.side_list_ref(pane)) {
+            (Some((path, offset)), Some(list)) if path == list.file_path() => *offset,
+            _ => 0,
+        }
+    }
+
+    // moves the listing in pane by movement lines, never past its length
+    pub fn scroll_pane(&mut self, pane: SidePane, movement: isize) {
+        let (path, len) = match self.side_list_ref(pane) {
+            Some(list) => (list.file_path().clone(), list.contents.len() as isize),
+            None => return,
+        };
+        let offset = (self.pane_scroll(pane) + movement).max(-len).min(len);
+        let scroll = Some((path, offset));
+        match pane {
+            SidePane::Parent => self.parent_scroll = scroll,
+            SidePane::Child => self.child_scroll = scroll,
+        }
+    }
 }
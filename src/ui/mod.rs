@@ -1,18 +1,36 @@
-use tui::layout::Constraint;
+use std::cmp;
+
+use tui::layout::{Constraint, Direction, Layout, Rect};
+
+use crate::config::{JoshutoConfig, LeftPaneMode};
 
 mod tui_backend;
 pub mod widgets;
 
 pub use tui_backend::*;
 
-pub const DEFAULT_LAYOUT: [tui::layout::Constraint; 3] = [
-    Constraint::Ratio(1, 8),
-    Constraint::Ratio(3, 8),
-    Constraint::Ratio(4, 8),
-];
+// widths of the parent, current and right panes from column_ratio, hidden ones get none
+pub fn layout_constraints(config: &JoshutoConfig, show_right: bool) -> [Constraint; 3] {
+    let (left, middle, right) = config.column_ratio;
+    let left = match config.left_pane {
+        LeftPaneMode::Parent => left,
+        LeftPaneMode::None => 0,
+    };
+    let right = if show_right { right } else { 0 };
+    let total = cmp::max(left + middle + right, 1) as u32;
+    [
+        Constraint::Ratio(left as u32, total),
+        Constraint::Ratio(middle as u32, total),
+        Constraint::Ratio(right as u32, total),
+    ]
+}
 
-pub const NO_PREVIEW_LAYOUT: [tui::layout::Constraint; 3] = [
-    Constraint::Ratio(1, 8),
-    Constraint::Ratio(7, 8),
-    Constraint::Ratio(0, 8),
-];
+// the area of the right pane when a preview is shown
+pub fn preview_area(f_size: Rect, config: &JoshutoConfig) -> Rect {
+    let layout_rect = Layout::default()
+        .direction(Direction::Horizontal)
+        .margin(1)
+        .constraints(layout_constraints(config, true).as_ref())
+        .split(f_size);
+    layout_rect[2]
+}
@@ -1,5 +1,7 @@
 use std::io::stdout;
 use std::io::Write;
+use std::panic;
+use std::thread;
 
 use termion::raw::{IntoRawMode, RawTerminal};
 use termion::screen::AlternateScreen;
@@ -53,3 +55,39 @@ impl TuiBackend {
         Ok(())
     }
 }
+
+/*
+ * puts the terminal back the way it was found before a panic is reported,
+ * otherwise the message is printed to the alternate screen in raw mode and
+ * lost along with it, leaving the shell without echo. only the main thread
+ * takes the ui down with it, panics elsewhere leave the terminal alone
+ */
+pub fn install_panic_hook() {
+    let original = unsafe {
+        let mut termios: libc::termios = std::mem::zeroed();
+        if libc::tcgetattr(libc::STDOUT_FILENO, &mut termios) == 0 {
+            Some(termios)
+        } else {
+            None
+        }
+    };
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if thread::current().name() == Some("main") {
+            let mut stdout = stdout();
+            let _ = write!(
+                stdout,
+                "{}{}",
+                termion::screen::ToMainScreen,
+                termion::cursor::Show
+            );
+            let _ = stdout.flush();
+            if let Some(termios) = original.as_ref() {
+                unsafe {
+                    libc::tcsetattr(libc::STDOUT_FILENO, libc::TCSANOW, termios);
+                }
+            }
+        }
+        default_hook(info);
+    }));
+}
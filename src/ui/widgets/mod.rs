@@ -1,18 +1,26 @@
+pub mod tui_dir_summary;
 pub mod tui_dirlist;
 pub mod tui_dirlist_detailed;
 pub mod tui_footer;
+pub mod tui_hex_preview;
+pub mod tui_image_preview;
 pub mod tui_menu;
 pub mod tui_prompt;
+pub mod tui_symlink_info;
 pub mod tui_tab;
 pub mod tui_textfield;
 pub mod tui_topbar;
 pub mod tui_view;
 
+pub use self::tui_dir_summary::TuiDirSummary;
 pub use self::tui_dirlist::TuiDirList;
 pub use self::tui_dirlist_detailed::TuiDirListDetailed;
 pub use self::tui_footer::TuiFooter;
+pub use self::tui_hex_preview::TuiHexPreview;
+pub use self::tui_image_preview::TuiImagePreview;
 pub use self::tui_menu::{TuiCommandMenu, TuiMenu};
 pub use self::tui_prompt::TuiPrompt;
+pub use self::tui_symlink_info::TuiSymlinkInfo;
 pub use self::tui_tab::TuiTabBar;
 pub use self::tui_textfield::TuiTextField;
 pub use self::tui_topbar::TuiTopBar;
@@ -0,0 +1,83 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::{Paragraph, Text, Widget};
+
+use crate::fs::JoshutoDirList;
+use crate::util::format;
+
+pub struct TuiDirSummary<'a> {
+    dirlist: &'a JoshutoDirList,
+    // whether the recursive size is still being added up
+    calculating: bool,
+}
+
+impl<'a> TuiDirSummary<'a> {
+    pub fn new(dirlist: &'a JoshutoDirList, calculating: bool) -> Self {
+        Self {
+            dirlist,
+            calculating,
+        }
+    }
+}
+
+impl<'a> Widget for TuiDirSummary<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        let label_style = Style::default().fg(Color::Cyan);
+        let contents = &self.dirlist.contents;
+
+        let dir_count = contents.iter().filter(|e| e.file_type().is_dir()).count();
+        let file_count = contents.len() - dir_count;
+
+        /* only the files listed directly, unless the recursive size was computed */
+        let size = match self.dirlist.recursive_size {
+            Some(s) => format!("{} (total)", format::file_size_to_string(s)),
+            None => {
+                let size: u64 = contents
+                    .iter()
+                    .filter(|e| !e.file_type().is_dir())
+                    .filter_map(|e| e.metadata())
+                    .map(|m| m.len)
+                    .sum();
+                if self.calculating {
+                    format!(
+                        "{} (files), calculating total...",
+                        format::file_size_to_string(size)
+                    )
+                } else {
+                    format!("{} (files)", format::file_size_to_string(size))
+                }
+            }
+        };
+
+        let newest = contents
+            .iter()
+            .filter_map(|e| e.metadata().map(|m| (e, m.modified)))
+            .max_by_key(|(_, modified)| *modified);
+        let newest = match newest {
+            Some((e, modified)) => {
+                format!("{} ({})", e.file_name(), format::mtime_to_string(modified))
+            }
+            None => String::from("-"),
+        };
+
+        let text = [
+            Text::styled("entries: ", label_style),
+            Text::raw(format!(
+                "{} ({} dirs, {} files)\n",
+                contents.len(),
+                dir_count,
+                file_count
+            )),
+            Text::styled("size:    ", label_style),
+            Text::raw(format!("{}\n", size)),
+            Text::styled("newest:  ", label_style),
+            Text::raw(format!("{}\n", newest)),
+        ];
+        Paragraph::new(text.iter()).wrap(true).render(area, buf);
+    }
+}
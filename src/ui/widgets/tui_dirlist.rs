@@ -1,18 +1,32 @@
 use tui::buffer::Buffer;
 use tui::layout::Rect;
-use tui::style::{Color, Modifier, Style};
+use tui::style::{Color, Style};
 use tui::widgets::Widget;
 use unicode_width::UnicodeWidthStr;
 
+use crate::config::JoshutoConfig;
 use crate::fs::JoshutoDirList;
+use crate::util::format;
 
 pub struct TuiDirList<'a> {
     dirlist: &'a JoshutoDirList,
+    config: &'a JoshutoConfig,
+    // lines to shift the listing by, from where the cursor would put it
+    scroll: isize,
 }
 
 impl<'a> TuiDirList<'a> {
-    pub fn new(dirlist: &'a JoshutoDirList) -> Self {
-        Self { dirlist }
+    pub fn new(dirlist: &'a JoshutoDirList, config: &'a JoshutoConfig) -> Self {
+        Self {
+            dirlist,
+            config,
+            scroll: 0,
+        }
+    }
+
+    pub fn scroll(mut self, scroll: isize) -> Self {
+        self.scroll = scroll;
+        self
     }
 }
 
@@ -22,11 +36,12 @@ impl<'a> Widget for TuiDirList<'a> {
             return;
         }
 
-        if area.width < 4 {
+        let (padding_left, padding_right) = self.config.listing_padding;
+        if (area.width as usize) < padding_left + padding_right + 4 {
             return;
         }
 
-        let x = area.left();
+        let x = area.left() + padding_left as u16;
         let y = area.top();
 
         let dir_len = self.dirlist.contents.len();
@@ -38,66 +53,50 @@ impl<'a> Widget for TuiDirList<'a> {
 
         let curr_index = self.dirlist.index.unwrap();
         let skip_dist = curr_index / area.height as usize * area.height as usize;
+        let skip_dist = (skip_dist as isize + self.scroll)
+            .max(0)
+            .min(dir_len as isize - 1) as usize;
 
-        let screen_index = if skip_dist > 0 {
-            curr_index % skip_dist
-        } else {
-            curr_index
-        };
+        /* scrolling can move the cursor out of sight */
+        let screen_index = curr_index.checked_sub(skip_dist);
 
-        let area_width = area.width as usize - 1;
+        let area_width = area.width as usize - padding_left - padding_right - 1;
+        let fit = |name: &str, width: usize, keep_extension: bool| {
+            let width = self
+                .config
+                .max_name_width
+                .map_or(width, |max| width.min(max));
+            format::truncate_name(name, width, keep_extension, self.config.name_truncation)
+        };
         for (i, entry) in self.dirlist.contents[skip_dist..]
             .iter()
             .enumerate()
             .take(area.height as usize)
         {
             let name = entry.file_name();
-            let name_width = name.width();
 
-            let style = if i == screen_index {
-                entry.get_style().modifier(Modifier::REVERSED)
+            let style = if Some(i) == screen_index {
+                entry.get_cursor_style()
             } else {
                 entry.get_style()
             };
 
-            let file_type = &entry.metadata.file_type;
-            if file_type.is_dir() {
-                if name_width <= area_width {
-                    buf.set_stringn(x, y + i as u16, name, area_width, style);
-                } else {
-                    buf.set_stringn(x, y + i as u16, name, area_width - 1, style);
-                    buf.set_string(x + area_width as u16 - 1, y + i as u16, "…", style);
-                }
+            let (x, area_width) = if self.config.show_icons {
+                let icon = self.config.icons.get(entry);
+                let icon_width = (icon.width() + 1).min(area_width);
+                buf.set_stringn(x, y + i as u16, format!("{} ", icon), icon_width, style);
+                (x + icon_width as u16, area_width - icon_width)
             } else {
-                if name_width < area_width {
-                    buf.set_stringn(x, y + i as u16, name, area_width, style);
-                } else {
-                    match name.rfind('.') {
-                        None => {
-                            buf.set_stringn(x, y + i as u16, name, area_width, style);
-                        }
-                        Some(p_ind) => {
-                            let ext_width = name[p_ind..].width();
-                            let file_name_width = area_width - ext_width - 1;
+                (x, area_width)
+            };
 
-                            buf.set_stringn(
-                                x,
-                                y + i as u16,
-                                &name[..p_ind],
-                                file_name_width,
-                                style,
-                            );
-                            buf.set_string(x + file_name_width as u16, y + i as u16, "…", style);
-                            buf.set_string(
-                                x + file_name_width as u16 + 1,
-                                y + i as u16,
-                                &name[p_ind..],
-                                style,
-                            );
-                        }
-                    }
-                }
-            }
+            let keep_extension = !entry.file_type().is_dir();
+            buf.set_string(
+                x,
+                y + i as u16,
+                fit(name, area_width, keep_extension),
+                style,
+            );
         }
     }
 }
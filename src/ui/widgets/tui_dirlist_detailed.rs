@@ -1,31 +1,35 @@
 use tui::buffer::Buffer;
 use tui::layout::Rect;
-use tui::style::{Color, Modifier, Style};
+use tui::style::{Color, Style};
 use tui::widgets::Widget;
 use unicode_width::UnicodeWidthStr;
 
-use crate::fs::JoshutoDirList;
+use crate::config::{JoshutoConfig, LineNumberMode};
+use crate::fs::{JoshutoDirEntry, JoshutoDirList};
 use crate::util::format;
 
 const FILE_SIZE_WIDTH: usize = 8;
+const EXTENSION_WIDTH: usize = 6;
 
 pub struct TuiDirListDetailed<'a> {
     dirlist: &'a JoshutoDirList,
+    config: &'a JoshutoConfig,
 }
 
 impl<'a> TuiDirListDetailed<'a> {
-    pub fn new(dirlist: &'a JoshutoDirList) -> Self {
-        Self { dirlist }
+    pub fn new(dirlist: &'a JoshutoDirList, config: &'a JoshutoConfig) -> Self {
+        Self { dirlist, config }
     }
 }
 
 impl<'a> Widget for TuiDirListDetailed<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        if area.width < 4 || area.height < 1 {
+        let (padding_left, padding_right) = self.config.listing_padding;
+        if (area.width as usize) < padding_left + padding_right + 4 || area.height < 1 {
             return;
         }
 
-        let x = area.left();
+        let x = area.left() + padding_left as u16;
         let y = area.top();
 
         let curr_index = match self.dirlist.index {
@@ -45,67 +49,106 @@ impl<'a> Widget for TuiDirListDetailed<'a> {
             curr_index
         };
 
-        let area_width = area.width as usize;
+        let gap = self.config.listing_column_gap;
+        /* reserve room for the line numbers, names are drawn after them */
+        let area_width = area.width as usize - padding_left - padding_right;
+        /* the numbers are left out when they'd leave no room for the listing */
+        let number_width = match self.config.line_numbers {
+            LineNumberMode::None => 0,
+            _ => self.dirlist.contents.len().to_string().len() + gap,
+        };
+        let number_width = if area_width > number_width + FILE_SIZE_WIDTH + gap + 3 {
+            number_width
+        } else {
+            0
+        };
+        let number_x = x;
+        let x = x + number_width as u16;
+        let area_width = area_width - number_width;
+        // names of files end a gap before their size
+        let name_area_width = area_width.saturating_sub(FILE_SIZE_WIDTH + gap);
+        let fit = |name: &str, width: usize, keep_extension: bool| {
+            let width = self
+                .config
+                .max_name_width
+                .map_or(width, |max| width.min(max));
+            format::truncate_name(name, width, keep_extension, self.config.name_truncation)
+        };
+
         for (i, entry) in self.dirlist.contents[skip_dist..]
             .iter()
             .enumerate()
             .take(area.height as usize)
         {
+            if number_width > 0 {
+                let index = skip_dist + i;
+                /* like vim, the cursor line keeps its absolute number */
+                let number = match self.config.line_numbers {
+                    LineNumberMode::Relative if index > curr_index => index - curr_index,
+                    LineNumberMode::Relative if index < curr_index => curr_index - index,
+                    _ => index + 1,
+                };
+                let number = format!("{:>width$}", number, width = number_width - gap);
+                buf.set_string(number_x, y + i as u16, number, Style::default());
+            }
+
             let name = entry.file_name();
-            let name_width = name.width();
 
             let style = if i == screen_index {
-                entry.get_style().modifier(Modifier::REVERSED)
+                entry.get_cursor_style()
             } else {
                 entry.get_style()
             };
 
-            let file_type = &entry.metadata.file_type;
+            /* the icon takes room from the name, the size stays where it is */
+            let (x, area_width, name_area_width) = if self.config.show_icons {
+                let icon = self.config.icons.get(entry);
+                let icon_width = (icon.width() + 1).min(name_area_width);
+                buf.set_stringn(x, y + i as u16, format!("{} ", icon), icon_width, style);
+                (
+                    x + icon_width as u16,
+                    area_width - icon_width,
+                    name_area_width - icon_width,
+                )
+            } else {
+                (x, area_width, name_area_width)
+            };
+
+            let file_type = entry.file_type();
             if file_type.is_dir() {
-                if name_width <= area_width {
-                    buf.set_stringn(x, y + i as u16, name, area_width, style);
-                } else {
-                    buf.set_stringn(x, y + i as u16, name, area_width - 1, style);
-                    buf.set_string(x + area_width as u16 - 1, y + i as u16, "…", style);
-                }
+                buf.set_string(x, y + i as u16, fit(name, area_width, false), style);
             // TODO: print out symlink path
             //            } else if file_type.is_symlink() {
-            } else {
-                if name_width < area_width - FILE_SIZE_WIDTH {
-                    buf.set_stringn(x, y + i as u16, name, area_width - FILE_SIZE_WIDTH, style);
-                } else {
-                    match name.rfind('.') {
-                        None => {
-                            buf.set_stringn(
-                                x,
-                                y + i as u16,
-                                name,
-                                area_width - FILE_SIZE_WIDTH,
-                                style,
-                            );
-                        }
-                        Some(p_ind) => {
-                            let ext_width = name[p_ind..].width();
-                            let file_name_width = area_width - FILE_SIZE_WIDTH - ext_width - 2;
-
-                            buf.set_stringn(
-                                x,
-                                y + i as u16,
-                                &name[..p_ind],
-                                file_name_width,
-                                style,
-                            );
-                            buf.set_string(x + file_name_width as u16, y + i as u16, "…", style);
-                            buf.set_string(
-                                x + file_name_width as u16 + 1,
-                                y + i as u16,
-                                &name[p_ind..],
-                                style,
-                            );
-                        }
-                    }
+            } else if self.config.extension_column
+                && area_width > FILE_SIZE_WIDTH + EXTENSION_WIDTH + gap * 2 + 1
+            {
+                /* dotfiles like .bashrc have no extension */
+                let (stem, ext) = match name.rfind('.') {
+                    Some(p_ind) if p_ind > 0 => (&name[..p_ind], &name[p_ind + 1..]),
+                    _ => (name, ""),
+                };
+                let stem_width = name_area_width - EXTENSION_WIDTH - gap;
+                buf.set_string(x, y + i as u16, fit(stem, stem_width, false), style);
+                if !ext.is_empty() {
+                    let ext_string = format!("{:>width$}", ext, width = EXTENSION_WIDTH);
+                    buf.set_stringn(
+                        x + (name_area_width - EXTENSION_WIDTH) as u16,
+                        y + i as u16,
+                        ext_string,
+                        EXTENSION_WIDTH,
+                        style,
+                    );
                 }
-                let file_size_string = format::file_size_to_string(entry.metadata.len);
+                let file_size_string = file_size_string(entry);
+                buf.set_string(
+                    x + (area_width - FILE_SIZE_WIDTH) as u16,
+                    y + i as u16,
+                    file_size_string,
+                    style,
+                );
+            } else {
+                buf.set_string(x, y + i as u16, fit(name, name_area_width, true), style);
+                let file_size_string = file_size_string(entry);
                 buf.set_string(
                     x + (area_width - FILE_SIZE_WIDTH) as u16,
                     y + i as u16,
@@ -116,3 +159,11 @@ impl<'a> Widget for TuiDirListDetailed<'a> {
         }
     }
 }
+
+// blank until the metadata of the entry has been read
+fn file_size_string(entry: &JoshutoDirEntry) -> String {
+    match entry.metadata() {
+        Some(m) => format::file_size_to_string(m.len),
+        None => String::new(),
+    }
+}
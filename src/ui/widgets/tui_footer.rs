@@ -20,19 +20,24 @@ impl<'a> Widget for TuiFooter<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         use std::os::unix::fs::PermissionsExt;
 
-        let mode = self.entry.metadata.permissions.mode();
+        let metadata = match self.entry.metadata() {
+            Some(m) => m,
+            None => return,
+        };
+
+        let mode = metadata.permissions.mode();
         let mode = format::mode_to_string(mode);
 
         let mode_style = Style::default().fg(Color::Cyan);
 
-        let mtime = self.entry.metadata.modified;
+        let mtime = metadata.modified;
         let mtime = format::mtime_to_string(mtime);
 
-        let size = self.entry.metadata.len;
+        let size = metadata.len;
         let size = format::file_size_to_string(size);
 
         #[cfg(unix)]
-        let mimetype = match self.entry.metadata.mimetype.as_ref() {
+        let mimetype = match metadata.mimetype.as_ref() {
             Some(s) => s,
             None => "",
         };
@@ -49,12 +54,16 @@ impl<'a> Widget for TuiFooter<'a> {
             Text::raw(mimetype),
         ];
 
-        match &self.entry.metadata.file_type {
+        match self.entry.file_type() {
             FileType::Symlink(s) => {
                 text.push(Text::styled(" -> ", mode_style));
                 text.push(Text::styled(s, mode_style));
             }
-            _ => {}
+            file_type => {
+                if let Some(name) = file_type.special_name() {
+                    text.push(Text::styled(name, mode_style));
+                }
+            }
         }
 
         Paragraph::new(text.iter()).wrap(true).render(area, buf);
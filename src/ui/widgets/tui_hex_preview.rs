@@ -0,0 +1,41 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::Widget;
+
+use crate::preview::hex_preview::{bytes_per_row, HexPreview};
+
+pub struct TuiHexPreview<'a> {
+    preview: &'a HexPreview,
+}
+
+impl<'a> TuiHexPreview<'a> {
+    pub fn new(preview: &'a HexPreview) -> Self {
+        Self { preview }
+    }
+}
+
+impl<'a> Widget for TuiHexPreview<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        let offset_style = Style::default().fg(Color::Cyan);
+        let style = Style::default();
+        let per_row = bytes_per_row(area.width);
+        for (i, row) in self
+            .preview
+            .rows(per_row)
+            .take(area.height as usize)
+            .enumerate()
+        {
+            let y = area.y + i as u16;
+            /* the offset is the first 8 characters of every row */
+            buf.set_stringn(area.x, y, &row[..8], area.width as usize, offset_style);
+            if area.width > 8 {
+                buf.set_stringn(area.x + 8, y, &row[8..], area.width as usize - 8, style);
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::{Paragraph, Text, Widget};
+
+use crate::fs::JoshutoDirEntry;
+use crate::preview::image_preview::ImagePreview;
+use crate::util::format;
+
+pub struct TuiImagePreview<'a> {
+    entry: &'a JoshutoDirEntry,
+    preview: Option<&'a ImagePreview>,
+}
+
+impl<'a> TuiImagePreview<'a> {
+    pub fn new(entry: &'a JoshutoDirEntry, preview: Option<&'a ImagePreview>) -> Self {
+        Self { entry, preview }
+    }
+}
+
+impl<'a> Widget for TuiImagePreview<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        match self.preview {
+            Some(preview) => {
+                let width = preview.width.min(area.width);
+                let height = preview.height.min(area.height);
+                for y in 0..height {
+                    for x in 0..width {
+                        let (fg, bg) = preview.cells[(y * preview.width + x) as usize];
+                        buf.get_mut(area.x + x, area.y + y)
+                            .set_symbol("▀")
+                            .set_fg(fg)
+                            .set_bg(bg);
+                    }
+                }
+            }
+            None => {
+                /* not decoded (yet) or no color support, show what we know */
+                let label_style = Style::default().fg(Color::Cyan);
+                let metadata = match self.entry.metadata() {
+                    Some(m) => m,
+                    None => return,
+                };
+                let text = [
+                    Text::styled("name:  ", label_style),
+                    Text::raw(format!("{}\n", self.entry.file_name())),
+                    Text::styled("size:  ", label_style),
+                    Text::raw(format!("{}\n", format::file_size_to_string(metadata.len))),
+                    Text::styled("mtime: ", label_style),
+                    Text::raw(format!("{}\n", format::mtime_to_string(metadata.modified))),
+                ];
+                Paragraph::new(text.iter()).wrap(true).render(area, buf);
+            }
+        }
+    }
+}
@@ -7,15 +7,22 @@ use crate::context::JoshutoContext;
 use crate::ui::TuiBackend;
 use crate::util::event::Event;
 
-use super::TuiView;
+use super::{TuiMenu, TuiView};
 
 pub struct TuiPrompt<'a> {
     prompt: &'a str,
+    menu: Option<&'a [&'a str]>,
 }
 
 impl<'a> TuiPrompt<'a> {
     pub fn new(prompt: &'a str) -> Self {
-        Self { prompt }
+        Self { prompt, menu: None }
+    }
+
+    // lines shown above the prompt, cut off if they don't fit
+    pub fn menu(mut self, options: &'a [&'a str]) -> Self {
+        self.menu = Some(options);
+        self
     }
 
     pub fn get_key(&mut self, backend: &mut TuiBackend, context: &JoshutoContext) -> Key {
@@ -35,6 +42,18 @@ impl<'a> TuiPrompt<'a> {
                     frame.render_widget(view, f_size);
                 }
 
+                if let Some(options) = self.menu {
+                    let max_len = (f_size.height as usize).saturating_sub(2);
+                    let options = &options[..options.len().min(max_len)];
+                    let rect = Rect {
+                        x: 0,
+                        y: (f_size.height as usize - options.len() - 2) as u16,
+                        width: f_size.width,
+                        height: options.len() as u16,
+                    };
+                    frame.render_widget(TuiMenu::new(options), rect);
+                }
+
                 let prompt_style = Style::default().fg(Color::LightYellow);
 
                 let text = [Text::styled(self.prompt, prompt_style)];
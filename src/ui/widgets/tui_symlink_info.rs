@@ -0,0 +1,56 @@
+use tui::buffer::Buffer;
+use tui::layout::Rect;
+use tui::style::{Color, Style};
+use tui::widgets::{Paragraph, Text, Widget};
+
+use crate::preview::symlink_info::{LinkStatus, SymlinkInfo};
+
+pub struct TuiSymlinkInfo<'a> {
+    info: &'a SymlinkInfo,
+}
+
+impl<'a> TuiSymlinkInfo<'a> {
+    pub fn new(info: &'a SymlinkInfo) -> Self {
+        Self { info }
+    }
+}
+
+impl<'a> Widget for TuiSymlinkInfo<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 1 || area.height < 1 {
+            return;
+        }
+
+        let label_style = Style::default().fg(Color::Cyan);
+        let ok_style = Style::default().fg(Color::Green);
+        let err_style = Style::default().fg(Color::Red);
+
+        let resolved = match self.info.resolved.as_ref() {
+            Some(p) => p.to_string_lossy().into_owned(),
+            None => String::from("-"),
+        };
+        let status = match self.info.status {
+            LinkStatus::Directory => Text::styled("ok (directory)\n", ok_style),
+            LinkStatus::File => Text::styled("ok (file)\n", ok_style),
+            LinkStatus::Broken => Text::styled("broken, target does not exist\n", err_style),
+            LinkStatus::Loop => Text::styled("broken, symlink loop\n", err_style),
+        };
+
+        let mut text = vec![
+            Text::styled("target:   ", label_style),
+            Text::raw(format!("{}\n", self.info.link)),
+            Text::styled("resolved: ", label_style),
+            Text::raw(format!("{}\n", resolved)),
+            Text::styled("status:   ", label_style),
+            status,
+        ];
+        /* only worth showing when links point to other links */
+        if self.info.chain.len() > 1 {
+            text.push(Text::styled("chain:\n", label_style));
+            for p in self.info.chain.iter() {
+                text.push(Text::raw(format!("  -> {}\n", p.to_string_lossy())));
+            }
+        }
+        Paragraph::new(text.iter()).wrap(true).render(area, buf);
+    }
+}
@@ -1,27 +1,38 @@
 use tui::buffer::Buffer;
 use tui::layout::Rect;
-use tui::style::{Modifier, Style};
 use tui::widgets::{Paragraph, Text, Widget};
 
+use crate::THEME_T;
+
 pub struct TuiTabBar<'a> {
     name: &'a str,
     curr: usize,
     len: usize,
+    target: Option<usize>,
 }
 
 impl<'a> TuiTabBar<'a> {
-    pub fn new(name: &'a str, curr: usize, len: usize) -> Self {
-        Self { name, curr, len }
+    pub fn new(name: &'a str, curr: usize, len: usize, target: Option<usize>) -> Self {
+        Self {
+            name,
+            curr,
+            len,
+            target,
+        }
     }
 }
 
 impl<'a> Widget for TuiTabBar<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let selected = Style::default().modifier(Modifier::REVERSED);
+        let selected = THEME_T.tab.as_style();
 
+        let target = match self.target {
+            Some(i) => format!(" -> {}", i + 1),
+            None => String::new(),
+        };
         let text = [
             Text::styled(format!("{}: {}", self.curr + 1, self.name), selected),
-            Text::raw(format!("/{}", self.len)),
+            Text::raw(format!("/{}{}", self.len, target)),
         ];
 
         Paragraph::new(text.iter()).wrap(true).render(area, buf);
@@ -12,6 +12,33 @@ use crate::util::event::Event;
 
 use super::{TuiMenu, TuiView};
 
+const DIR_CANDIDATES_LIMIT: usize = 10;
+
+/*
+ * subdirectories matching the last component of a path being typed,
+ * hidden ones only once the component starts with a dot
+ */
+fn dir_candidates(input: &str) -> Vec<String> {
+    let (dir, partial) = match input.rfind('/') {
+        Some(i) => (&input[..=i], &input[i + 1..]),
+        None => ("./", input),
+    };
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut candidates: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(partial))
+        .filter(|name| partial.starts_with('.') || !name.starts_with('.'))
+        .collect();
+    candidates.sort();
+    candidates.truncate(DIR_CANDIDATES_LIMIT);
+    candidates
+}
+
 struct CompletionTracker {
     pub index: usize,
     pub pos: usize,
@@ -35,6 +62,7 @@ pub struct TuiTextField<'a> {
     _prefix: &'a str,
     _suffix: &'a str,
     _menu: Option<TuiMenu<'a>>,
+    _dir_completion: bool,
 }
 
 impl<'a> TuiTextField<'a> {
@@ -58,6 +86,12 @@ impl<'a> TuiTextField<'a> {
         self
     }
 
+    // shows the subdirectories matching the input as it is typed
+    pub fn dir_completion(mut self, dir_completion: bool) -> Self {
+        self._dir_completion = dir_completion;
+        self
+    }
+
     pub fn get_input(
         &mut self,
         backend: &mut TuiBackend,
@@ -79,6 +113,12 @@ impl<'a> TuiTextField<'a> {
         let terminal = backend.terminal_mut();
 
         loop {
+            let dir_candidates = if self._dir_completion {
+                dir_candidates(line_buffer.as_str())
+            } else {
+                Vec::new()
+            };
+
             terminal
                 .draw(|mut frame| {
                     let f_size: Rect = frame.size();
@@ -92,7 +132,16 @@ impl<'a> TuiTextField<'a> {
                         frame.render_widget(view, f_size);
                     }
 
-                    if let Some(menu) = self._menu.take() {
+                    let dir_candidates_str: Vec<&str> =
+                        dir_candidates.iter().map(|s| s.as_str()).collect();
+                    let menu = match self._menu.take() {
+                        Some(menu) => Some(menu),
+                        None if !dir_candidates_str.is_empty() => {
+                            Some(TuiMenu::new(&dir_candidates_str))
+                        }
+                        None => None,
+                    };
+                    if let Some(menu) = menu {
                         let menu_len = menu.len();
                         let menu_y = if menu_len + 2 > f_size.height as usize {
                             0
@@ -245,6 +294,7 @@ impl<'a> std::default::Default for TuiTextField<'a> {
             _prefix: "",
             _suffix: "",
             _menu: None,
+            _dir_completion: false,
         }
     }
 }
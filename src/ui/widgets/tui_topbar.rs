@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::path::{Component, Path};
 
 use tui::buffer::Buffer;
 use tui::layout::Rect;
@@ -7,14 +7,50 @@ use tui::widgets::{Paragraph, Text, Widget};
 
 use crate::{HOSTNAME, USERNAME};
 
+const ELLIPSIS: &str = "…";
+
+const READONLY_STR: &str = " [read-only]";
+
 pub struct TuiTopBar<'a> {
     path: &'a Path,
+    readonly: bool,
 }
 
 impl<'a> TuiTopBar<'a> {
-    pub fn new(path: &'a Path) -> Self {
-        Self { path }
+    pub fn new(path: &'a Path, readonly: bool) -> Self {
+        Self { path, readonly }
+    }
+}
+
+/*
+ * splits the path into breadcrumb segments. when they do not fit in width,
+ * segments after the first are replaced by a single ellipsis until they do,
+ * always keeping the current directory
+ */
+fn breadcrumbs(path: &Path, width: usize) -> Vec<String> {
+    let mut segments: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            Component::RootDir => None,
+            c => Some(c.as_os_str().to_string_lossy().into_owned()),
+        })
+        .collect();
+
+    let display_len =
+        |segments: &[String]| -> usize { segments.iter().map(|s| s.chars().count() + 1).sum() };
+
+    let mut collapsed = false;
+    while display_len(&segments) > width {
+        if collapsed && segments.len() > 3 {
+            segments.remove(2);
+        } else if !collapsed && segments.len() > 2 {
+            segments[1] = ELLIPSIS.to_string();
+            collapsed = true;
+        } else {
+            break;
+        }
     }
+    segments
 }
 
 impl<'a> Widget for TuiTopBar<'a> {
@@ -27,13 +63,31 @@ impl<'a> Widget for TuiTopBar<'a> {
             .fg(Color::LightBlue)
             .modifier(Modifier::BOLD);
 
-        let curr_path_str = self.path.to_string_lossy();
+        let separator_style = Style::default().fg(Color::LightBlue);
+
+        let user_str = format!("{}@{} ", *USERNAME, *HOSTNAME);
+        let mut path_width = (area.width as usize).saturating_sub(user_str.chars().count());
+        if self.readonly {
+            path_width = path_width.saturating_sub(READONLY_STR.len());
+        }
 
-        let text = [
-            Text::styled(format!("{}@{} ", *USERNAME, *HOSTNAME), username_style),
-            Text::styled(curr_path_str, path_style),
-        ];
+        let mut text = vec![Text::styled(user_str, username_style)];
+        if self.path.has_root() {
+            text.push(Text::styled("/", separator_style));
+        }
+        let segments = breadcrumbs(self.path, path_width);
+        let len = segments.len();
+        for (i, segment) in segments.into_iter().enumerate() {
+            text.push(Text::styled(segment, path_style));
+            if i + 1 < len {
+                text.push(Text::styled("/", separator_style));
+            }
+        }
+        if self.readonly {
+            let readonly_style = Style::default().fg(Color::LightRed);
+            text.push(Text::styled(READONLY_STR, readonly_style));
+        }
 
-        Paragraph::new(text.iter()).wrap(true).render(area, buf);
+        Paragraph::new(text.iter()).render(area, buf);
     }
 }
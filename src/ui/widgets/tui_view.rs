@@ -1,19 +1,29 @@
 use tui::buffer::Buffer;
 use tui::layout::{Direction, Layout, Rect};
-use tui::style::{Color, Style};
+use tui::style::{Color, Modifier, Style};
 use tui::widgets::{Paragraph, Text, Widget};
 
-use super::{TuiDirList, TuiDirListDetailed, TuiFooter, TuiTabBar, TuiTopBar};
+use super::{
+    TuiDirList, TuiDirListDetailed, TuiDirSummary, TuiFooter, TuiHexPreview, TuiImagePreview,
+    TuiSymlinkInfo, TuiTabBar, TuiTopBar,
+};
+use crate::config::{DirPreviewMode, LeftPaneMode};
 use crate::context::JoshutoContext;
+use crate::preview;
+use crate::tab::SidePane;
+use crate::THEME_T;
 
 const TAB_VIEW_WIDTH: u16 = 15;
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+// kept at the right end of the status bar in read-only mode
+const READONLY_MODE_STR: &str = " RO";
 
 pub struct TuiView<'a> {
     pub context: &'a JoshutoContext,
     pub show_bottom_status: bool,
 }
 
-use super::super::{DEFAULT_LAYOUT, NO_PREVIEW_LAYOUT};
+use super::super::layout_constraints;
 
 impl<'a> TuiView<'a> {
     pub fn new(context: &'a JoshutoContext) -> Self {
@@ -30,14 +40,35 @@ impl<'a> Widget for TuiView<'a> {
 
         let curr_tab = self.context.curr_tab_ref();
 
+        let config = &self.context.config_t;
         let curr_list = curr_tab.curr_list_ref();
-        let parent_list = curr_tab.parent_list_ref();
-        let child_list = curr_tab.child_list_ref();
-
-        let constraints = match child_list {
-            Some(_) => DEFAULT_LAYOUT,
-            None => NO_PREVIEW_LAYOUT,
+        let parent_list = curr_tab
+            .parent_list_ref()
+            .filter(|_| config.left_pane == LeftPaneMode::Parent);
+        let child_list = curr_tab
+            .child_list_ref()
+            .filter(|_| config.right_pane.shows_listing());
+
+        /* symlinks show where they lead instead of a preview of their target */
+        let symlink_entry = curr_list
+            .and_then(|s| s.get_curr_ref())
+            .filter(|e| e.file_type().is_symlink())
+            .filter(|_| config.right_pane.shows_preview());
+
+        let image_key = match (child_list, symlink_entry) {
+            (None, None) => preview::image_preview_key(self.context, f_size),
+            _ => None,
+        };
+        let hex_preview = match (child_list, symlink_entry, image_key.as_ref()) {
+            (None, None, None) => preview::hex_preview(self.context),
+            _ => None,
         };
+
+        let show_right = child_list.is_some()
+            || image_key.is_some()
+            || hex_preview.is_some()
+            || symlink_entry.is_some();
+        let constraints = layout_constraints(config, show_right);
         let layout_rect = Layout::default()
             .direction(Direction::Horizontal)
             .margin(1)
@@ -46,6 +77,7 @@ impl<'a> Widget for TuiView<'a> {
 
         {
             let curr_path = curr_tab.curr_path.as_path();
+            let readonly = curr_list.map(|s| !s.writable).unwrap_or(false);
 
             if self.context.tabs.len() > 1 {
                 let topbar_width = if f_size.width > TAB_VIEW_WIDTH {
@@ -60,7 +92,7 @@ impl<'a> Widget for TuiView<'a> {
                     width: topbar_width,
                     height: 1,
                 };
-                TuiTopBar::new(curr_path).render(rect, buf);
+                TuiTopBar::new(curr_path, readonly).render(rect, buf);
 
                 let rect = Rect {
                     x: topbar_width,
@@ -73,8 +105,13 @@ impl<'a> Widget for TuiView<'a> {
                 } else {
                     ""
                 };
-                TuiTabBar::new(name, self.context.curr_tab_index, self.context.tabs.len())
-                    .render(rect, buf);
+                TuiTabBar::new(
+                    name,
+                    self.context.curr_tab_index,
+                    self.context.tabs.len(),
+                    self.context.target_tab,
+                )
+                .render(rect, buf);
             } else {
                 let topbar_width = f_size.width;
 
@@ -84,24 +121,31 @@ impl<'a> Widget for TuiView<'a> {
                     width: topbar_width,
                     height: 1,
                 };
-                TuiTopBar::new(curr_path).render(rect, buf);
+                TuiTopBar::new(curr_path, readonly).render(rect, buf);
             }
         }
 
         if let Some(curr_list) = parent_list.as_ref() {
-            TuiDirList::new(&curr_list).render(layout_rect[0], buf);
+            TuiDirList::new(&curr_list, &self.context.config_t)
+                .scroll(curr_tab.pane_scroll(SidePane::Parent))
+                .render(layout_rect[0], buf);
         };
 
         if let Some(curr_list) = curr_list.as_ref() {
-            TuiDirListDetailed::new(&curr_list).render(layout_rect[1], buf);
+            TuiDirListDetailed::new(&curr_list, &self.context.config_t).render(layout_rect[1], buf);
+            let readonly_width = if self.context.readonly {
+                READONLY_MODE_STR.len() as u16
+            } else {
+                0
+            };
             let rect = Rect {
                 x: 0,
                 y: f_size.height - 1,
-                width: f_size.width,
+                width: f_size.width.saturating_sub(readonly_width),
                 height: 1,
             };
 
-            let message_style = Style::default().fg(Color::Yellow);
+            let message_style = THEME_T.message.as_style();
 
             if self.show_bottom_status {
                 /* draw the bottom status bar */
@@ -109,18 +153,55 @@ impl<'a> Widget for TuiView<'a> {
                     let text = [Text::styled(&self.context.message_queue[0], message_style)];
 
                     Paragraph::new(text.iter()).wrap(true).render(rect, buf);
-                } else if let Some(msg) = self.context.worker_msg.as_ref() {
+                } else if self.context.worker_busy {
+                    let frame = SPINNER_FRAMES[self.context.spinner_index % SPINNER_FRAMES.len()];
+                    let msg = match self.context.worker_msg.as_ref() {
+                        Some(msg) => format!("{} {}", frame, msg),
+                        None => frame.to_string(),
+                    };
+                    let text = [Text::styled(msg, message_style)];
+
+                    Paragraph::new(text.iter()).wrap(true).render(rect, buf);
+                } else if let Some(msg) = self.context.size_msg.as_ref() {
                     let text = [Text::styled(msg, message_style)];
 
                     Paragraph::new(text.iter()).wrap(true).render(rect, buf);
                 } else if let Some(entry) = curr_list.get_curr_ref() {
                     TuiFooter::new(entry).render(rect, buf);
                 }
+                if self.context.readonly && f_size.width >= readonly_width {
+                    let style = Style::default()
+                        .fg(Color::LightRed)
+                        .modifier(Modifier::BOLD);
+                    buf.set_string(rect.x + rect.width, rect.y, READONLY_MODE_STR, style);
+                }
             }
         };
 
-        if let Some(curr_list) = child_list.as_ref() {
-            TuiDirList::new(&curr_list).render(layout_rect[2], buf);
-        };
+        if symlink_entry.is_some() {
+            if let Some(info) = preview::symlink_info(self.context) {
+                TuiSymlinkInfo::new(info).render(layout_rect[2], buf);
+            }
+        } else if let Some(curr_list) = child_list.as_ref() {
+            match self.context.config_t.dir_preview {
+                DirPreviewMode::Listing => TuiDirList::new(&curr_list, &self.context.config_t)
+                    .scroll(curr_tab.pane_scroll(SidePane::Child))
+                    .render(layout_rect[2], buf),
+                DirPreviewMode::Summary => {
+                    let calculating = self
+                        .context
+                        .size_pending
+                        .contains_key(curr_list.file_path());
+                    TuiDirSummary::new(&curr_list, calculating).render(layout_rect[2], buf)
+                }
+            }
+        } else if let Some(key) = image_key.as_ref() {
+            if let Some(entry) = curr_list.and_then(|s| s.get_curr_ref()) {
+                let image = self.context.image_previews.get(key);
+                TuiImagePreview::new(entry, image).render(layout_rect[2], buf);
+            }
+        } else if let Some(hex_preview) = hex_preview {
+            TuiHexPreview::new(hex_preview).render(layout_rect[2], buf);
+        }
     }
 }
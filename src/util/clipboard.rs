@@ -0,0 +1,37 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// programs that read clipboard contents from stdin, tried in order
+const CLIPBOARD_PROGRAMS: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("pbcopy", &[]),
+];
+
+// places contents on the system clipboard using the first available program
+pub fn set_contents(contents: &str) -> std::io::Result<()> {
+    for (program, args) in CLIPBOARD_PROGRAMS.iter() {
+        let mut child = match Command::new(program)
+            .args(args.iter())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(contents.as_bytes())?;
+        }
+        let status = child.wait()?;
+        if status.success() {
+            return Ok(());
+        }
+    }
+    Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        "No clipboard program found (wl-copy, xclip, xsel or pbcopy)",
+    ))
+}
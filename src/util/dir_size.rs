@@ -0,0 +1,54 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::util::event::Event;
+
+// least time between progress reports, so huge trees don't flood the event loop
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(200);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DirSize {
+    pub files: u64,
+    pub bytes: u64,
+}
+
+// adds up the files under path, not following symlinks, like recursive_size
+fn add_up(path: &Path, size: &mut DirSize, report: &mut dyn FnMut(&DirSize)) {
+    let entries = match fs::read_dir(path) {
+        Ok(s) => s,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        match fs::symlink_metadata(entry.path()) {
+            Ok(metadata) if metadata.is_dir() => add_up(entry.path().as_path(), size, report),
+            Ok(metadata) => {
+                size.files += 1;
+                size.bytes += metadata.len();
+                report(size);
+            }
+            Err(_) => {}
+        }
+    }
+}
+
+/*
+ * adds up the size of everything below path on a separate thread,
+ * sending DirSizeProgress now and then and DirSizeResult once done
+ */
+pub fn spawn(path: PathBuf, event_tx: mpsc::Sender<Event>) {
+    thread::spawn(move || {
+        let mut size = DirSize::default();
+        let mut last_report = Instant::now();
+        let mut report = |size: &DirSize| {
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                last_report = Instant::now();
+                let _ = event_tx.send(Event::DirSizeProgress(path.clone(), *size));
+            }
+        };
+        add_up(path.as_path(), &mut size, &mut report);
+        let _ = event_tx.send(Event::DirSizeResult(path.clone(), size));
+    });
+}
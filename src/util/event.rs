@@ -1,15 +1,41 @@
 use std::io;
 use std::sync::mpsc;
 use std::thread;
+use std::time;
 
 use termion::event::Key;
 use termion::input::TermRead;
 
+use crate::fs::JoshutoDirEntry;
+use crate::io::IOResults;
+use crate::preview::image_preview::{ImagePreview, ImagePreviewKey};
+use crate::util::dir_size::DirSize;
+
 #[derive(Debug)]
 pub enum Event {
     Input(Key),
     IOWorkerProgress(u64),
+    // size of everything the worker is going to process, once counted
+    IOWorkerTotal(u64),
     IOWorkerResult(std::io::Result<u64>),
+    IOWorkerWarning(String),
+    // what became of each entry, sent before the worker finishes
+    IOWorkerResults(IOResults),
+    ImagePreview(ImagePreviewKey, Option<ImagePreview>),
+    // contents of a directory as read with the given flatten depth
+    DirReload(
+        std::path::PathBuf,
+        Option<usize>,
+        std::io::Result<Vec<JoshutoDirEntry>>,
+    ),
+    // a search is done, its results are in the context
+    GrepDone,
+    // how much of a directory has been added up so far, and the final total
+    DirSizeProgress(std::path::PathBuf, DirSize),
+    DirSizeResult(std::path::PathBuf, DirSize),
+    // sync is done, after this long
+    Synced(std::time::Duration),
+    Tick,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -78,11 +104,32 @@ impl Events {
         }
     }
 
+    // the channels alone, for tests that must not read from stdin
+    #[cfg(test)]
+    pub fn without_input() -> Self {
+        let (input_tx, _) = mpsc::sync_channel(1);
+        let (event_tx, event_rx) = mpsc::channel();
+        Events {
+            event_tx,
+            event_rx,
+            input_tx,
+        }
+    }
+
     pub fn next(&self) -> Result<Event, mpsc::RecvError> {
         let event = self.event_rx.recv()?;
         Ok(event)
     }
 
+    // like next, but gives a Tick if nothing happened within timeout
+    pub fn next_timeout(&self, timeout: time::Duration) -> Result<Event, mpsc::RecvError> {
+        match self.event_rx.recv_timeout(timeout) {
+            Ok(event) => Ok(event),
+            Err(mpsc::RecvTimeoutError::Timeout) => Ok(Event::Tick),
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(mpsc::RecvError),
+        }
+    }
+
     pub fn flush(&self) {
         let _ = self.input_tx.send(());
     }
@@ -1,6 +1,9 @@
-use std::time;
+use std::{fs, path, time};
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::unix;
+use crate::config::NameTruncation;
 
 pub fn file_size_to_string(file_size: u64) -> String {
     const FILE_UNITS: [&str; 6] = ["B", "K", "M", "G", "T", "E"];
@@ -22,6 +25,43 @@ pub fn file_size_to_string(file_size: u64) -> String {
     }
 }
 
+/*
+ * counts paths and adds up their sizes, e.g. "12 files, 1 directory (34 M)".
+ * directories count as one item and are not descended into
+ */
+pub fn selection_summary<P: AsRef<path::Path>>(paths: &[P]) -> String {
+    let mut files = 0;
+    let mut dirs = 0;
+    let mut size = 0;
+    for path in paths {
+        match fs::symlink_metadata(path.as_ref()) {
+            Ok(metadata) if metadata.is_dir() => dirs += 1,
+            Ok(metadata) => {
+                files += 1;
+                size += metadata.len();
+            }
+            Err(_) => files += 1,
+        }
+    }
+
+    let plural = |n: usize, one: &str, many: &str| -> String {
+        format!("{} {}", n, if n == 1 { one } else { many })
+    };
+    let mut summary = match (files, dirs) {
+        (_, 0) => plural(files, "file", "files"),
+        (0, _) => plural(dirs, "directory", "directories"),
+        _ => format!(
+            "{}, {}",
+            plural(files, "file", "files"),
+            plural(dirs, "directory", "directories")
+        ),
+    };
+    if files > 0 {
+        summary.push_str(&format!(" ({})", file_size_to_string(size).trim()));
+    }
+    summary
+}
+
 pub fn mode_to_string(mode: u32) -> String {
     unix::stringify_mode(mode)
 }
@@ -32,3 +72,125 @@ pub fn mtime_to_string(mtime: time::SystemTime) -> String {
     let datetime: chrono::DateTime<chrono::offset::Utc> = mtime.into();
     datetime.format(MTIME_FORMATTING).to_string()
 }
+
+pub fn duration_to_string(duration: time::Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 60 * 60 {
+        format!("{}h {}m", secs / (60 * 60), secs / 60 % 60)
+    } else if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+// the longest start of s that is at most width columns wide
+fn take_width(s: &str, width: usize) -> &str {
+    let mut taken = 0;
+    for (i, ch) in s.char_indices() {
+        taken += ch.width().unwrap_or(0);
+        if taken > width {
+            return &s[..i];
+        }
+    }
+    s
+}
+
+// the longest end of s that is at most width columns wide
+fn take_width_end(s: &str, width: usize) -> &str {
+    let mut taken = 0;
+    for (i, ch) in s.char_indices().rev() {
+        taken += ch.width().unwrap_or(0);
+        if taken > width {
+            return &s[i + ch.len_utf8()..];
+        }
+    }
+    s
+}
+
+/*
+ * fits name into width columns, with … where some of it was cut out.
+ * with keep_extension, the extension is kept whole as long as it fits.
+ * End cuts off the end of the rest, Middle keeps its start and end,
+ * e.g. report-2020…-final.pdf
+ */
+pub fn truncate_name(
+    name: &str,
+    width: usize,
+    keep_extension: bool,
+    truncation: NameTruncation,
+) -> String {
+    if name.width() <= width {
+        return name.to_string();
+    }
+    if width == 0 {
+        return String::new();
+    }
+    /* dotfiles like .bashrc have no extension */
+    let (stem, ext) = match name.rfind('.') {
+        Some(i) if keep_extension && i > 0 && name[i..].width() < width => name.split_at(i),
+        _ => (name, ""),
+    };
+    let room = width - ext.width() - 1;
+    match truncation {
+        NameTruncation::End => format!("{}…{}", take_width(stem, room), ext),
+        NameTruncation::Middle => {
+            let head = take_width(stem, room - room / 2);
+            let tail = take_width_end(stem, room - head.width());
+            format!("{}…{}{}", head, tail, ext)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn middle(name: &str, width: usize) -> String {
+        truncate_name(name, width, false, NameTruncation::Middle)
+    }
+
+    #[test]
+    fn short_names_are_left_alone() {
+        assert_eq!(middle("abc", 10), "abc");
+    }
+
+    #[test]
+    fn names_that_fit_exactly_are_left_alone() {
+        assert_eq!(middle("abcdef", 6), "abcdef");
+    }
+
+    #[test]
+    fn middle_keeps_start_and_end() {
+        /* odd widths leave an even number of columns around the … */
+        assert_eq!(middle("abcdefghij", 5), "ab…ij");
+        /* even widths give the extra column to the start */
+        assert_eq!(middle("abcdefghij", 6), "abc…ij");
+    }
+
+    #[test]
+    fn middle_keeps_the_extension() {
+        let s = truncate_name("report-2020-final.pdf", 12, true, NameTruncation::Middle);
+        assert_eq!(s, "repo…nal.pdf");
+    }
+
+    #[test]
+    fn middle_counts_columns_of_wide_characters() {
+        let s = middle("日本語のファイル", 7);
+        assert_eq!(s, "日…イル");
+        assert_eq!(s.width(), 7);
+    }
+
+    #[test]
+    fn tiny_widths() {
+        assert_eq!(middle("abcdef", 2), "a…");
+        assert_eq!(middle("abcdef", 1), "…");
+        assert_eq!(middle("abcdef", 0), "");
+    }
+
+    #[test]
+    fn end_cuts_off_the_end() {
+        let s = truncate_name("abcdefghij", 5, false, NameTruncation::End);
+        assert_eq!(s, "abcd…");
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::time;
+
+use super::xdg_dirs;
+
+const FRECENCY_FILE: &str = "frecency";
+
+// once the sum of all ranks exceeds this, every rank is aged down
+const MAX_TOTAL_RANK: f64 = 9000.0;
+const AGING_FACTOR: f64 = 0.99;
+
+const HOUR: u64 = 60 * 60;
+const DAY: u64 = 24 * HOUR;
+const WEEK: u64 = 7 * DAY;
+
+#[derive(Clone, Debug)]
+struct FrecencyEntry {
+    rank: f64,
+    last_access: u64,
+}
+
+impl FrecencyEntry {
+    // weighs the visit count by how recently the directory was visited
+    fn score(&self, now: u64) -> f64 {
+        let age = now.saturating_sub(self.last_access);
+        if age < HOUR {
+            self.rank * 4.0
+        } else if age < DAY {
+            self.rank * 2.0
+        } else if age < WEEK {
+            self.rank / 2.0
+        } else {
+            self.rank / 4.0
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct JoshutoFrecency {
+    entries: HashMap<PathBuf, FrecencyEntry>,
+}
+
+impl JoshutoFrecency {
+    pub fn new() -> Self {
+        Self {
+            entries: HashMap::new(),
+        }
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        xdg_dirs::data_file(FRECENCY_FILE)
+    }
+
+    // reads the table from disk, starting empty if it does not exist yet
+    pub fn load() -> Self {
+        let mut frecency = Self::new();
+        let file_path = xdg_dirs::find_data_file(FRECENCY_FILE);
+        let file = match file_path.and_then(|p| fs::File::open(p).ok()) {
+            Some(f) => f,
+            None => return frecency,
+        };
+
+        let reader = std::io::BufReader::new(file);
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut fields = line.splitn(3, '\t');
+            let rank = fields.next().and_then(|s| s.parse::<f64>().ok());
+            let last_access = fields.next().and_then(|s| s.parse::<u64>().ok());
+            let path = fields.next().map(PathBuf::from);
+            if let (Some(rank), Some(last_access), Some(path)) = (rank, last_access, path) {
+                frecency
+                    .entries
+                    .insert(path, FrecencyEntry { rank, last_access });
+            }
+        }
+        frecency
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let file_path = match Self::file_path() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let mut file = fs::File::create(file_path)?;
+        for (path, entry) in self.entries.iter() {
+            if let Some(s) = path.to_str() {
+                writeln!(file, "{}\t{}\t{}", entry.rank, entry.last_access, s)?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn visit(&mut self, path: &Path) {
+        let now = now_secs();
+        let entry = self
+            .entries
+            .entry(path.to_path_buf())
+            .or_insert(FrecencyEntry {
+                rank: 0.0,
+                last_access: now,
+            });
+        entry.rank += 1.0;
+        entry.last_access = now;
+
+        let total: f64 = self.entries.values().map(|e| e.rank).sum();
+        if total > MAX_TOTAL_RANK {
+            self.entries
+                .values_mut()
+                .for_each(|e| e.rank *= AGING_FACTOR);
+            self.entries.retain(|_, e| e.rank >= 1.0);
+        }
+    }
+
+    /*
+     * returns the directories containing every keyword (case insensitive),
+     * best match first
+     */
+    pub fn query(&self, keywords: &str) -> Vec<&Path> {
+        let now = now_secs();
+        let keywords: Vec<String> = keywords
+            .split_whitespace()
+            .map(|s| s.to_lowercase())
+            .collect();
+
+        let mut matches: Vec<(&Path, f64)> = self
+            .entries
+            .iter()
+            .filter(|(path, _)| path.is_dir())
+            .filter(|(path, _)| {
+                let path_str = path.to_string_lossy().to_lowercase();
+                keywords.iter().all(|k| path_str.contains(k.as_str()))
+            })
+            .map(|(path, entry)| (path.as_path(), entry.score(now)))
+            .collect();
+
+        matches.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+        matches.into_iter().map(|(path, _)| path).collect()
+    }
+}
+
+fn now_secs() -> u64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use ignore::gitignore::Gitignore;
+use ignore::Match;
+
+/*
+ * the .gitignore rules that apply to a directory inside a git repository,
+ * collected from the directory up to the root of the repository
+ */
+pub struct GitIgnoreFilter {
+    // deepest .gitignore first, those take precedence
+    matchers: Vec<Gitignore>,
+}
+
+impl GitIgnoreFilter {
+    pub fn new(dir: &Path) -> Option<Self> {
+        let mut matchers = Vec::new();
+        for ancestor in dir.ancestors() {
+            let gitignore_path = ancestor.join(".gitignore");
+            if gitignore_path.is_file() {
+                let (gitignore, _) = Gitignore::new(gitignore_path);
+                matchers.push(gitignore);
+            }
+            if ancestor.join(".git").exists() {
+                return Some(Self { matchers });
+            }
+        }
+        // not inside a git repository
+        None
+    }
+
+    /*
+     * entries inside an ignored directory are ignored too, which matters
+     * for listings of an ignored directory and flattened listings
+     */
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        for matcher in self.matchers.iter() {
+            match matcher.matched_path_or_any_parents(path, is_dir) {
+                Match::None => continue,
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+            }
+        }
+        false
+    }
+}
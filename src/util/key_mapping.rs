@@ -62,3 +62,30 @@ pub fn str_to_key(s: &str) -> Option<Key> {
     }
     None
 }
+
+// the inverse of str_to_key, for showing keybindings
+pub fn key_to_str(key: &Key) -> String {
+    match *key {
+        Key::Backspace => "backspace".to_string(),
+        Key::BackTab => "backtab".to_string(),
+        Key::Left => "left".to_string(),
+        Key::Right => "right".to_string(),
+        Key::Up => "up".to_string(),
+        Key::Down => "down".to_string(),
+        Key::Home => "home".to_string(),
+        Key::End => "end".to_string(),
+        Key::PageUp => "page_up".to_string(),
+        Key::PageDown => "page_down".to_string(),
+        Key::Delete => "delete".to_string(),
+        Key::Insert => "insert".to_string(),
+        Key::Esc => "escape".to_string(),
+        Key::F(n) => format!("f{}", n),
+        Key::Char('\n') => "enter".to_string(),
+        Key::Char('\t') => "tab".to_string(),
+        Key::Char(' ') => "space".to_string(),
+        Key::Char(c) => c.to_string(),
+        Key::Ctrl(c) => format!("ctrl+{}", c),
+        Key::Alt(c) => format!("alt+{}", c),
+        _ => "?".to_string(),
+    }
+}
@@ -1,7 +1,10 @@
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
+use crate::config::DirPreviewMode;
 use crate::context::JoshutoContext;
 use crate::history::DirectoryHistory;
+use crate::util::dir_size;
 
 pub struct LoadChild {}
 
@@ -23,8 +26,53 @@ impl LoadChild {
                 curr_tab
                     .history
                     .create_or_soft_update(path.as_path(), &context.config_t.sort_option)?;
+
+                if context.config_t.dir_preview == DirPreviewMode::Summary {
+                    if let Some(child_list) = curr_tab.history.get_mut(path.as_path()) {
+                        /* the summary adds up the size and mtime of every entry */
+                        child_list.load_metadata(0..child_list.contents.len());
+                        /* big trees take a while, so they are added up on a separate thread */
+                        if context.config_t.dir_preview_recursive_size
+                            && child_list.recursive_size.is_none()
+                            && !context.size_pending.contains_key(&path)
+                        {
+                            context.size_pending.insert(path.clone(), false);
+                            dir_size::spawn(path.clone(), context.events.event_tx.clone());
+                        }
+                    }
+                }
             }
         }
         Ok(())
     }
+
+    // reads the metadata of entries that can be on screen in the current tab
+    pub fn load_visible_metadata(context: &mut JoshutoContext, rows: usize) {
+        let curr_tab = &mut context.tabs[context.curr_tab_index];
+        if let Some(curr_list) = curr_tab.curr_list_mut() {
+            curr_list.load_metadata_around(rows);
+        }
+        if let Some(parent_list) = curr_tab.parent_list_mut() {
+            parent_list.load_metadata_around(rows);
+        }
+        if let Some(child_list) = curr_tab.child_list_mut() {
+            child_list.load_metadata_around(rows);
+        }
+    }
+}
+
+// total size of all files under a directory, not following symlinks
+pub fn recursive_size(path: &Path) -> u64 {
+    let entries = match fs::read_dir(path) {
+        Ok(s) => s,
+        Err(_) => return 0,
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| match fs::symlink_metadata(entry.path()) {
+            Ok(metadata) if metadata.is_dir() => recursive_size(entry.path().as_path()),
+            Ok(metadata) => metadata.len(),
+            Err(_) => 0,
+        })
+        .sum()
 }
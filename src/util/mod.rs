@@ -1,6 +1,17 @@
+pub mod clipboard;
+pub mod dir_size;
 pub mod event;
 pub mod format;
+pub mod frecency;
+pub mod gitignore;
 pub mod key_mapping;
 pub mod load_child;
+pub mod open_with_history;
+pub mod process;
+pub mod relative_path;
 pub mod sort;
+pub mod sort_overrides;
+#[cfg(test)]
+pub mod test_dir;
 pub mod unix;
+pub mod xdg_dirs;
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use super::xdg_dirs;
+
+const OPEN_WITH_FILE: &str = "open_with";
+
+/*
+ * the program last used with open_file_with for each file extension,
+ * learned at runtime on top of the mimetype config
+ */
+#[derive(Debug)]
+pub struct JoshutoOpenWithHistory {
+    commands: HashMap<String, String>,
+}
+
+impl JoshutoOpenWithHistory {
+    pub fn new() -> Self {
+        Self {
+            commands: HashMap::new(),
+        }
+    }
+
+    fn file_path() -> Option<PathBuf> {
+        xdg_dirs::data_file(OPEN_WITH_FILE)
+    }
+
+    pub fn extension(path: &Path) -> Option<String> {
+        path.extension()
+            .and_then(|s| s.to_str())
+            .map(|s| s.to_lowercase())
+    }
+
+    // reads the associations from disk, starting empty if there are none yet
+    pub fn load() -> Self {
+        let mut history = Self::new();
+        let file_path = xdg_dirs::find_data_file(OPEN_WITH_FILE);
+        let file = match file_path.and_then(|p| fs::File::open(p).ok()) {
+            Some(f) => f,
+            None => return history,
+        };
+
+        let reader = std::io::BufReader::new(file);
+        for line in reader.lines().filter_map(|l| l.ok()) {
+            let mut fields = line.splitn(2, '\t');
+            if let (Some(ext), Some(command)) = (fields.next(), fields.next()) {
+                history
+                    .commands
+                    .insert(ext.to_string(), command.to_string());
+            }
+        }
+        history
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let file_path = match Self::file_path() {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        let mut file = fs::File::create(file_path)?;
+        for (ext, command) in self.commands.iter() {
+            writeln!(file, "{}\t{}", ext, command)?;
+        }
+        Ok(())
+    }
+
+    pub fn get(&self, ext: &str) -> Option<&str> {
+        self.commands.get(ext).map(|s| s.as_str())
+    }
+
+    pub fn remember(&mut self, ext: String, command: String) {
+        self.commands.insert(ext, command);
+    }
+
+    pub fn forget(&mut self, ext: &str) -> bool {
+        self.commands.remove(ext).is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+    }
+}
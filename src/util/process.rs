@@ -0,0 +1,32 @@
+use std::io;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// how often a child is checked on while waiting for it with a timeout
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/*
+ * waits for child to exit, killing it once timeout seconds have passed.
+ * a timeout of 0 waits for as long as it takes
+ */
+pub fn wait_timeout(child: &mut process::Child, timeout: u64) -> io::Result<process::ExitStatus> {
+    if timeout == 0 {
+        return child.wait();
+    }
+    let deadline = Instant::now() + Duration::from_secs(timeout);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("killed after running for {}s", timeout),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
@@ -0,0 +1,36 @@
+use std::path::{Component, Path, PathBuf};
+
+/*
+ * the path leading from the directory from to to, both absolute.
+ * None if they only have the root in common, a relative path climbing
+ * all the way up is less useful than the absolute one then
+ */
+pub fn relative_path(from: &Path, to: &Path) -> Option<PathBuf> {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+
+    let common = from
+        .iter()
+        .zip(to.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let shares_ancestor = from[..common].iter().any(|c| match c {
+        Component::Normal(_) => true,
+        _ => false,
+    });
+    if !shares_ancestor {
+        return None;
+    }
+
+    let mut path = PathBuf::new();
+    for _ in common..from.len() {
+        path.push("..");
+    }
+    for c in to[common..].iter() {
+        path.push(c.as_os_str());
+    }
+    if path.as_os_str().is_empty() {
+        path.push(".");
+    }
+    Some(path)
+}
@@ -1,13 +1,18 @@
 use std::cmp;
 use std::fs;
-use std::time;
+use std::path::Path;
 
 use serde_derive::Deserialize;
 
 use crate::fs::JoshutoDirEntry;
+use crate::util::gitignore::GitIgnoreFilter;
+use crate::util::sort_overrides;
+
+pub type FilterFunc = Box<dyn Fn(&Result<fs::DirEntry, std::io::Error>) -> bool>;
 
 #[derive(Clone, Copy, Debug, Deserialize)]
 pub enum SortType {
+    Created,
     Lexical,
     Mtime,
     Natural,
@@ -17,6 +22,7 @@ pub enum SortType {
 impl SortType {
     pub fn parse(s: &str) -> Option<Self> {
         match s {
+            "created" | "birthtime" => Some(SortType::Created),
             "lexical" => Some(SortType::Lexical),
             "mtime" => Some(SortType::Mtime),
             "natural" => Some(SortType::Natural),
@@ -26,12 +32,20 @@ impl SortType {
     }
     pub fn as_str(&self) -> &str {
         match *self {
+            SortType::Created => "created",
             SortType::Lexical => "lexical",
             SortType::Mtime => "mtime",
             SortType::Natural => "natural",
             SortType::Size => "size",
         }
     }
+    // whether sorting compares file metadata rather than just names
+    pub fn needs_metadata(&self) -> bool {
+        match *self {
+            SortType::Created | SortType::Mtime | SortType::Size => true,
+            SortType::Lexical | SortType::Natural => false,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -40,14 +54,15 @@ pub struct SortOption {
     pub directories_first: bool,
     pub case_sensitive: bool,
     pub reverse: bool,
+    pub respect_gitignore: bool,
     pub sort_method: SortType,
 }
 
 impl SortOption {
     pub fn compare(&self, f1: &JoshutoDirEntry, f2: &JoshutoDirEntry) -> cmp::Ordering {
         if self.directories_first {
-            let f1_isdir = f1.file_path().is_dir();
-            let f2_isdir = f2.file_path().is_dir();
+            let f1_isdir = f1.is_dir();
+            let f2_isdir = f2.is_dir();
 
             if f1_isdir && !f2_isdir {
                 return cmp::Ordering::Less;
@@ -79,6 +94,7 @@ impl SortOption {
                     alphanumeric_sort::compare_str(&f1_name, &f2_name)
                 }
             }
+            SortType::Created => created_sort(f1, f2),
             SortType::Mtime => mtime_sort(f1, f2),
             SortType::Size => size_sort(f1, f2),
         };
@@ -93,12 +109,28 @@ impl SortOption {
         return res;
     }
 
-    pub fn filter_func(&self) -> fn(&Result<fs::DirEntry, std::io::Error>) -> bool {
+    // this option with the sort method remembered for dir, if any
+    pub fn for_dir(&self, dir: &Path) -> Self {
+        let mut sort_option = self.clone();
+        if let Some((sort_method, reverse)) = sort_overrides::get(dir) {
+            sort_option.sort_method = sort_method;
+            sort_option.reverse = reverse;
+        }
+        sort_option
+    }
+
+    pub fn filter_func(&self, dir: &Path) -> FilterFunc {
         if self.show_hidden {
-            no_filter
-        } else {
-            filter_hidden
+            return Box::new(no_filter);
         }
+        if self.respect_gitignore {
+            if let Some(gitignore) = GitIgnoreFilter::new(dir) {
+                return Box::new(move |result| {
+                    filter_hidden(result) && !filter_gitignored(result, &gitignore)
+                });
+            }
+        }
+        Box::new(filter_hidden)
     }
 }
 
@@ -109,6 +141,7 @@ impl std::default::Default for SortOption {
             directories_first: true,
             case_sensitive: false,
             reverse: false,
+            respect_gitignore: false,
             sort_method: SortType::Natural,
         }
     }
@@ -132,26 +165,40 @@ fn filter_hidden(result: &Result<fs::DirEntry, std::io::Error>) -> bool {
     }
 }
 
-fn mtime_sort(file1: &JoshutoDirEntry, file2: &JoshutoDirEntry) -> cmp::Ordering {
-    fn compare(
-        file1: &JoshutoDirEntry,
-        file2: &JoshutoDirEntry,
-    ) -> Result<cmp::Ordering, std::io::Error> {
-        let f1_meta: fs::Metadata = std::fs::metadata(file1.file_path())?;
-        let f2_meta: fs::Metadata = std::fs::metadata(file2.file_path())?;
-
-        let f1_mtime: time::SystemTime = f1_meta.modified()?;
-        let f2_mtime: time::SystemTime = f2_meta.modified()?;
-
-        Ok(if f1_mtime >= f2_mtime {
-            cmp::Ordering::Less
-        } else {
-            cmp::Ordering::Greater
-        })
+fn filter_gitignored(
+    result: &Result<fs::DirEntry, std::io::Error>,
+    gitignore: &GitIgnoreFilter,
+) -> bool {
+    match result {
+        Err(_) => false,
+        Ok(entry) => {
+            let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+            gitignore.is_ignored(entry.path().as_path(), is_dir)
+        }
     }
-    compare(&file1, &file2).unwrap_or(cmp::Ordering::Less)
+}
+
+// newest first, using the metadata gathered when the directory was read
+fn mtime_sort(file1: &JoshutoDirEntry, file2: &JoshutoDirEntry) -> cmp::Ordering {
+    let f1_mtime = file1.metadata().map(|m| m.modified);
+    let f2_mtime = file2.metadata().map(|m| m.modified);
+    f2_mtime.cmp(&f1_mtime)
+}
+
+/*
+ * newest first. entries without a creation time are placed by their mtime,
+ * so a filesystem that records none ends up sorted by mtime
+ */
+fn created_sort(file1: &JoshutoDirEntry, file2: &JoshutoDirEntry) -> cmp::Ordering {
+    let f1_created = file1.metadata().map(|m| m.created.unwrap_or(m.modified));
+    let f2_created = file2.metadata().map(|m| m.created.unwrap_or(m.modified));
+    f2_created
+        .cmp(&f1_created)
+        .then_with(|| mtime_sort(file1, file2))
 }
 
 fn size_sort(file1: &JoshutoDirEntry, file2: &JoshutoDirEntry) -> cmp::Ordering {
-    file1.metadata.len.cmp(&file2.metadata.len)
+    let f1_len = file1.metadata().map(|m| m.len);
+    let f2_len = file2.metadata().map(|m| m.len);
+    f1_len.cmp(&f2_len)
 }
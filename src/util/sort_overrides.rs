@@ -0,0 +1,76 @@
+use lazy_static::lazy_static;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use super::sort::SortType;
+use super::xdg_dirs;
+
+const SORT_OVERRIDES_FILE: &str = "sort_overrides";
+const REVERSE_SUFFIX: &str = " --reverse";
+
+lazy_static! {
+    static ref SORT_OVERRIDES: Mutex<HashMap<PathBuf, (SortType, bool)>> = Mutex::new(load());
+}
+
+// the sort method and whether it is reversed, written like the sort command takes them
+fn parse_override(s: &str) -> Option<(SortType, bool)> {
+    match s.strip_suffix(REVERSE_SUFFIX) {
+        Some(method) => SortType::parse(method).map(|s| (s, true)),
+        None => SortType::parse(s).map(|s| (s, false)),
+    }
+}
+
+// reads the remembered sort methods, starting empty if there are none yet
+fn load() -> HashMap<PathBuf, (SortType, bool)> {
+    let mut overrides = HashMap::new();
+    let file_path = xdg_dirs::find_data_file(SORT_OVERRIDES_FILE);
+    let file = match file_path.and_then(|p| fs::File::open(p).ok()) {
+        Some(f) => f,
+        None => return overrides,
+    };
+
+    let reader = std::io::BufReader::new(file);
+    for line in reader.lines().filter_map(|l| l.ok()) {
+        let mut fields = line.splitn(2, '\t');
+        let sort_method = fields.next().and_then(parse_override);
+        let path = fields.next().map(PathBuf::from);
+        if let (Some(sort_method), Some(path)) = (sort_method, path) {
+            overrides.insert(path, sort_method);
+        }
+    }
+    overrides
+}
+
+fn save(overrides: &HashMap<PathBuf, (SortType, bool)>) -> std::io::Result<()> {
+    let file_path = match xdg_dirs::data_file(SORT_OVERRIDES_FILE) {
+        Some(p) => p,
+        None => return Ok(()),
+    };
+    let mut file = fs::File::create(file_path)?;
+    for (path, (sort_method, reverse)) in overrides.iter() {
+        if let Some(s) = path.to_str() {
+            let suffix = if *reverse { REVERSE_SUFFIX } else { "" };
+            writeln!(file, "{}{}\t{}", sort_method.as_str(), suffix, s)?;
+        }
+    }
+    Ok(())
+}
+
+// the sort method and direction remembered for dir, overriding the global ones
+pub fn get(dir: &Path) -> Option<(SortType, bool)> {
+    SORT_OVERRIDES.lock().unwrap().get(dir).copied()
+}
+
+// remembers or forgets the sort method for dir, saving the change right away
+pub fn set(dir: &Path, sort_method: Option<(SortType, bool)>) -> std::io::Result<()> {
+    let mut overrides = SORT_OVERRIDES.lock().unwrap();
+    match sort_method {
+        Some(s) => overrides.insert(dir.to_path_buf(), s),
+        None => overrides.remove(dir),
+    };
+    save(&overrides)
+}
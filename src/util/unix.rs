@@ -60,3 +60,25 @@ pub fn set_mode(path: &Path, mode: u32) {
         }
     }
 }
+
+// flushes every pending write of every filesystem, which may take a while
+pub fn sync() {
+    unsafe {
+        libc::sync();
+    }
+}
+
+// flushes the file or directory at path to disk, directories for their entries
+pub fn fsync(path: &Path) -> std::io::Result<()> {
+    std::fs::File::open(path)?.sync_all()
+}
+
+// whether we may create, rename and delete entries in the directory at path
+pub fn is_writable(path: &Path) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+
+    match std::ffi::CString::new(path.as_os_str().as_bytes()) {
+        Ok(s) => unsafe { libc::access(s.as_ptr(), libc::W_OK) == 0 },
+        Err(_) => false,
+    }
+}
@@ -0,0 +1,39 @@
+use std::path::PathBuf;
+
+use crate::PROGRAM_NAME;
+
+/*
+ * where joshuto keeps its files, following the XDG base directory spec:
+ * $XDG_CONFIG_HOME and $XDG_DATA_HOME when set, otherwise ~/.config
+ * and ~/.local/share, each with a joshuto subdirectory
+ */
+fn base_dirs() -> Option<xdg::BaseDirectories> {
+    match xdg::BaseDirectories::with_prefix(PROGRAM_NAME) {
+        Ok(dirs) => Some(dirs),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+// user config directory first, then the system wide ones in $XDG_CONFIG_DIRS
+pub fn config_dirs() -> Vec<PathBuf> {
+    let dirs = match base_dirs() {
+        Some(dirs) => dirs,
+        None => return Vec::new(),
+    };
+    let mut config_dirs = vec![dirs.get_config_home()];
+    config_dirs.extend(dirs.get_config_dirs());
+    config_dirs
+}
+
+// path to a persistent data file, creating its parent directories
+pub fn data_file(name: &str) -> Option<PathBuf> {
+    base_dirs()?.place_data_file(name).ok()
+}
+
+// path to a persistent data file if it exists, for reading without creating anything
+pub fn find_data_file(name: &str) -> Option<PathBuf> {
+    base_dirs()?.find_data_file(name)
+}